@@ -10,6 +10,7 @@ use clap::Parser;
 #[derive(Clone, Debug, Parser)]
 pub enum Backend {
     Rust,
+    Typescript,
     Mdbook,
 }
 
@@ -17,6 +18,7 @@ impl std::fmt::Display for Backend {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
             Backend::Rust => "rust",
+            Backend::Typescript => "typescript",
             Backend::Mdbook => "doc-mdbook",
         })
     }
@@ -27,6 +29,7 @@ impl std::str::FromStr for Backend {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "rust" => Ok(Backend::Rust),
+            "typescript" => Ok(Backend::Typescript),
             "doc-mdbook" => Ok(Backend::Mdbook),
             _ => Err("Unrecognized backend".into()),
         }
@@ -37,6 +40,7 @@ impl From<Backend> for zksync_error_codegen::arguments::Backend {
     fn from(value: Backend) -> Self {
         match value {
             Backend::Rust => Self::Rust,
+            Backend::Typescript => Self::Typescript,
             Backend::Mdbook => Self::Mdbook,
         }
     }
@@ -0,0 +1,19 @@
+//!
+//! Discovery of the definition files provided by cargo packages.
+//!
+
+use clap::Args;
+
+#[derive(Clone, Debug, Args)]
+pub struct CargoArguments {
+    /// Manifest of the cargo workspace providing the files referenced by
+    /// `cargo://` links. By default, it is looked up from the current
+    /// directory.
+    #[arg(long = "manifest-path")]
+    pub manifest_path: Option<String>,
+
+    /// Only look for the files referenced by `cargo://` links in this package
+    /// and its dependencies.
+    #[arg(long = "package")]
+    pub package: Option<String>,
+}
@@ -0,0 +1,96 @@
+//!
+//! Arguments of the backward-compatibility check.
+//!
+
+use clap::Args;
+
+use super::CargoArguments;
+use super::MergeMode;
+use super::NetworkArguments;
+
+/// Format of the compatibility report.
+#[derive(Clone, Debug, Default)]
+pub enum ReportFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl std::fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ReportFormat::Human => "human",
+            ReportFormat::Json => "json",
+        })
+    }
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "human" => Ok(ReportFormat::Human),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err("Unrecognized report format".into()),
+        }
+    }
+}
+
+///
+/// Compares the current error definitions with a baseline, taken either from
+/// other files or from the same files at a git revision. Exits with a non-zero
+/// code if any change breaks backward compatibility.
+///
+#[derive(Debug, Args)]
+pub struct CompatibilityArguments {
+    /// Link to the current master JSON file.
+    #[arg(long = "root-definitions")]
+    pub root: String,
+
+    /// Links to additional current JSON files.
+    #[arg(long = "additional-definitions")]
+    pub additional_definition_files: Vec<String>,
+
+    /// Link to the baseline master JSON file. Defaults to the current one,
+    /// read at `--baseline-git-ref`.
+    #[arg(
+        long = "baseline-root-definitions",
+        required_unless_present = "baseline_git_ref"
+    )]
+    pub baseline_root: Option<String>,
+
+    /// Links to additional baseline JSON files. Default to the current ones
+    /// if the baseline is read at `--baseline-git-ref` and has no root of its
+    /// own.
+    #[arg(long = "baseline-additional-definitions")]
+    pub baseline_additional_definition_files: Vec<String>,
+
+    /// Git revision to read the baseline files from, instead of the working
+    /// tree.
+    #[arg(long = "baseline-git-ref")]
+    pub baseline_git_ref: Option<String>,
+
+    /// Format of the report: `human` or `json`.
+    #[arg(long = "format",
+          default_value_t = ReportFormat::Human,
+          value_parser = clap::value_parser!(ReportFormat))]
+    pub format: ReportFormat,
+
+    /// How to treat conflicting attributes of the same definition found in
+    /// several files: `strict` fails, `lenient` keeps the first one and warns.
+    #[arg(long = "merge-mode",
+          default_value_t = MergeMode::Strict,
+          value_parser = clap::value_parser!(MergeMode))]
+    pub merge_mode: MergeMode,
+
+    #[command(flatten)]
+    pub network: NetworkArguments,
+
+    #[command(flatten)]
+    pub cargo: CargoArguments,
+
+    /// Be verbose and produce debug output.
+    #[arg(long = "verbose", short = 'v')]
+    pub verbose: bool,
+}
@@ -1,24 +1,150 @@
+use super::lint::lint_levels;
 use super::Arguments;
+use super::CargoArguments;
+use super::CompatibilityArguments;
+use super::LintArguments;
+use super::NetworkArguments;
+use super::RegistryArguments;
+
+impl From<NetworkArguments> for zksync_error_codegen::loader::network::NetworkConfig {
+    fn from(val: NetworkArguments) -> Self {
+        let NetworkArguments {
+            offline,
+            lockfile,
+            cache_directory,
+        } = val;
+        Self {
+            lockfile: lockfile.map(Into::into),
+            cache_directory: cache_directory.map(Into::into),
+            offline,
+        }
+    }
+}
+
+impl From<RegistryArguments> for zksync_error_codegen::loader::registry::RegistryConfig {
+    fn from(val: RegistryArguments) -> Self {
+        let RegistryArguments {
+            registry,
+            force_registry,
+        } = val;
+        Self {
+            path: registry.map(Into::into),
+            update: true,
+            force: force_registry,
+        }
+    }
+}
+
+impl From<CargoArguments> for zksync_error_codegen::loader::cargo::CargoConfig {
+    fn from(val: CargoArguments) -> Self {
+        let CargoArguments {
+            manifest_path,
+            package,
+        } = val;
+        Self {
+            manifest_path: manifest_path.map(Into::into),
+            directory: None,
+            package,
+        }
+    }
+}
 
 impl From<Arguments> for zksync_error_codegen::arguments::GenerationArguments {
     fn from(val: Arguments) -> Self {
         let Arguments {
+            command: _,
             root: definitions,
             backend,
-            verbose,
+            verbose: _,
             output_directory,
             additional_definition_files: additional_inputs,
             backend_args,
+            merge_mode,
+            lints,
+            network,
+            registry,
+            cargo,
         } = val;
         zksync_error_codegen::arguments::GenerationArguments {
-            verbose,
-            root_link: definitions,
+            root_link: definitions.expect("Required by the argument parser"),
             outputs: vec![(
                 output_directory.into(),
-                backend.into(),
+                backend.expect("Required by the argument parser").into(),
                 backend_args.into_iter().collect(),
             )],
             input_links: additional_inputs,
+            network: network.into(),
+            registry: registry.into(),
+            cargo: cargo.into(),
+            merge_mode: merge_mode.into(),
+            lints: lint_levels(lints),
+        }
+    }
+}
+
+impl From<LintArguments> for zksync_error_codegen::arguments::LintArguments {
+    fn from(val: LintArguments) -> Self {
+        let LintArguments {
+            root,
+            additional_definition_files,
+            format: _,
+            lints,
+            merge_mode,
+            network,
+            cargo,
+            verbose: _,
+        } = val;
+        zksync_error_codegen::arguments::LintArguments {
+            root_link: root,
+            input_links: additional_definition_files,
+            network: network.into(),
+            cargo: cargo.into(),
+            merge_mode: merge_mode.into(),
+            lints: lint_levels(lints),
+        }
+    }
+}
+
+impl From<CompatibilityArguments> for zksync_error_codegen::arguments::CompatibilityArguments {
+    fn from(val: CompatibilityArguments) -> Self {
+        let CompatibilityArguments {
+            root,
+            additional_definition_files,
+            baseline_root,
+            baseline_additional_definition_files,
+            baseline_git_ref,
+            format: _,
+            merge_mode,
+            network,
+            cargo,
+            verbose: _,
+        } = val;
+        let baseline = match baseline_root {
+            Some(baseline_root) => zksync_error_codegen::arguments::ModelSource {
+                root_link: baseline_root,
+                input_links: baseline_additional_definition_files,
+                revision: baseline_git_ref,
+            },
+            None => zksync_error_codegen::arguments::ModelSource {
+                root_link: root.clone(),
+                input_links: if baseline_additional_definition_files.is_empty() {
+                    additional_definition_files.clone()
+                } else {
+                    baseline_additional_definition_files
+                },
+                revision: baseline_git_ref,
+            },
+        };
+        zksync_error_codegen::arguments::CompatibilityArguments {
+            network: network.into(),
+            cargo: cargo.into(),
+            merge_mode: merge_mode.into(),
+            baseline,
+            current: zksync_error_codegen::arguments::ModelSource {
+                root_link: root,
+                input_links: additional_definition_files,
+                revision: None,
+            },
         }
     }
 }
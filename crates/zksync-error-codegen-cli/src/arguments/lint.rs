@@ -0,0 +1,69 @@
+//!
+//! Arguments of the lint pass.
+//!
+
+use clap::Args;
+
+use zksync_error_codegen::arguments::LintLevel;
+use zksync_error_codegen::arguments::LintLevels;
+use zksync_error_codegen::arguments::LintRule;
+
+use super::CargoArguments;
+use super::MergeMode;
+use super::NetworkArguments;
+use super::ReportFormat;
+
+///
+/// Checks the error definitions against the lint rules, and exits with a
+/// non-zero code if any denied rule is violated.
+///
+#[derive(Debug, Args)]
+pub struct LintArguments {
+    /// Link to the master JSON file.
+    #[arg(long = "root-definitions")]
+    pub root: String,
+
+    /// Links to additional JSON files.
+    #[arg(long = "additional-definitions")]
+    pub additional_definition_files: Vec<String>,
+
+    /// Format of the report: `human` or `json`.
+    #[arg(long = "format",
+          default_value_t = ReportFormat::Human,
+          value_parser = clap::value_parser!(ReportFormat))]
+    pub format: ReportFormat,
+
+    /// Level of a lint rule as `rule=level`, where the level is `allow`,
+    /// `warn` or `deny`. Overrides the level set in the definition files.
+    #[arg(long = "lint", value_parser(parse_lint_level))]
+    pub lints: Vec<(LintRule, LintLevel)>,
+
+    /// How to treat conflicting attributes of the same definition found in
+    /// several files: `strict` fails, `lenient` keeps the first one and warns.
+    #[arg(long = "merge-mode",
+          default_value_t = MergeMode::Strict,
+          value_parser = clap::value_parser!(MergeMode))]
+    pub merge_mode: MergeMode,
+
+    #[command(flatten)]
+    pub network: NetworkArguments,
+
+    #[command(flatten)]
+    pub cargo: CargoArguments,
+
+    /// Be verbose and produce debug output.
+    #[arg(long = "verbose", short = 'v')]
+    pub verbose: bool,
+}
+
+/// Parse `rule=level`, e.g. `missing-doc=deny`.
+pub fn parse_lint_level(s: &str) -> Result<(LintRule, LintLevel), String> {
+    let (rule, level) = s
+        .split_once('=')
+        .ok_or_else(|| "expected `rule=level` format".to_owned())?;
+    Ok((rule.trim().parse()?, level.trim().parse()?))
+}
+
+pub fn lint_levels(lints: Vec<(LintRule, LintLevel)>) -> LintLevels {
+    LintLevels(lints.into_iter().collect())
+}
@@ -0,0 +1,44 @@
+//!
+//! Treatment of the conflicting definitions found while merging files.
+//!
+
+/// Merge mode exposed through CLI.
+#[derive(Clone, Debug, Default)]
+pub enum MergeMode {
+    /// Fail on conflicting descriptions, messages, bindings and other
+    /// attributes of the same definition.
+    #[default]
+    Strict,
+    /// Keep the attributes found first, and warn about the conflicts.
+    Lenient,
+}
+
+impl std::fmt::Display for MergeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            MergeMode::Strict => "strict",
+            MergeMode::Lenient => "lenient",
+        })
+    }
+}
+
+impl std::str::FromStr for MergeMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "strict" => Ok(MergeMode::Strict),
+            "lenient" => Ok(MergeMode::Lenient),
+            _ => Err("Unrecognized merge mode".into()),
+        }
+    }
+}
+
+impl From<MergeMode> for zksync_error_codegen::arguments::MergeMode {
+    fn from(value: MergeMode) -> Self {
+        match value {
+            MergeMode::Strict => Self::Strict,
+            MergeMode::Lenient => Self::Lenient,
+        }
+    }
+}
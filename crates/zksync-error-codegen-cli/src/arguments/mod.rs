@@ -1,10 +1,25 @@
 pub mod backend;
+pub mod cargo;
+pub mod compatibility;
 pub mod conversion;
+pub mod lint;
+pub mod merge;
+pub mod network;
+pub mod registry;
+pub mod schema;
 
 use clap::Error as ClapError;
 use clap::Parser;
 
 pub use backend::Backend;
+pub use cargo::CargoArguments;
+pub use compatibility::CompatibilityArguments;
+pub use compatibility::ReportFormat;
+pub use lint::LintArguments;
+pub use merge::MergeMode;
+pub use network::NetworkArguments;
+pub use registry::RegistryArguments;
+pub use schema::SchemaArguments;
 
 ///
 /// Generates one of the following:
@@ -15,12 +30,16 @@ pub use backend::Backend;
 #[command(
     version,
     about,
-    long_about = "Generator of the error handling code in ZKsync components."
+    long_about = "Generator of the error handling code in ZKsync components.",
+    subcommand_negates_reqs = true
 )]
 pub struct Arguments {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Link to the master JSON file.
-    #[arg(long = "root-definitions")]
-    pub root: String,
+    #[arg(long = "root-definitions", required = true)]
+    pub root: Option<String>,
 
     /// Links to additional JSON file.
     #[arg(long = "additional-definitions")]
@@ -29,8 +48,9 @@ pub struct Arguments {
     /// Selected backend.
     #[arg(short = 'b',
           long = "backend",
+          required = true,
           value_parser = clap::value_parser!(Backend))]
-    pub backend: Backend,
+    pub backend: Option<Backend>,
 
     /// Be verbose and produce debug output.
     #[arg(long = "verbose", short = 'v')]
@@ -48,6 +68,42 @@ pub struct Arguments {
         value_parser(parse_key_val)
     )]
     pub backend_args: Vec<(String, String)>,
+
+    /// How to treat conflicting attributes of the same definition found in
+    /// several files: `strict` fails, `lenient` keeps the first one and warns.
+    #[arg(long = "merge-mode",
+          default_value_t = MergeMode::Strict,
+          value_parser = clap::value_parser!(MergeMode))]
+    pub merge_mode: MergeMode,
+
+    /// Level of a lint rule as `rule=level`, where the level is `allow`,
+    /// `warn` or `deny`. Overrides the level set in the definition files.
+    #[arg(long = "lint", value_parser(lint::parse_lint_level))]
+    pub lints: Vec<(
+        zksync_error_codegen::arguments::LintRule,
+        zksync_error_codegen::arguments::LintLevel,
+    )>,
+
+    #[command(flatten)]
+    pub network: NetworkArguments,
+
+    #[command(flatten)]
+    pub registry: RegistryArguments,
+
+    #[command(flatten)]
+    pub cargo: CargoArguments,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Compare two versions of the error definitions and report the changes
+    /// that break backward compatibility.
+    CheckCompatibility(CompatibilityArguments),
+    /// Check the error definitions against the lint rules.
+    Lint(LintArguments),
+    /// Write the JSON Schemas of the files with error definitions: one per
+    /// kind of file, and one accepting any of them.
+    Schema(SchemaArguments),
 }
 
 ///
@@ -0,0 +1,23 @@
+//!
+//! Access to the definition files in the network.
+//!
+
+use clap::Args;
+
+#[derive(Clone, Debug, Args)]
+pub struct NetworkArguments {
+    /// Never access the network; serve the files referenced by URLs only from
+    /// the cache, as recorded in the lockfile.
+    #[arg(long = "offline")]
+    pub offline: bool,
+
+    /// Lockfile recording the hashes of the files fetched from the network.
+    /// Defaults to `zksync-error-sources.lock` next to the root definitions.
+    #[arg(long = "lockfile")]
+    pub lockfile: Option<String>,
+
+    /// Directory holding the files fetched from the network. Defaults to
+    /// `$XDG_CACHE_HOME/zksync-error`.
+    #[arg(long = "cache-directory")]
+    pub cache_directory: Option<String>,
+}
@@ -0,0 +1,18 @@
+//!
+//! Registry of the generated error identifiers.
+//!
+
+use clap::Args;
+
+#[derive(Clone, Debug, Args)]
+pub struct RegistryArguments {
+    /// Registry recording the name and the fields of every generated error
+    /// identifier. Defaults to `errors.lock` next to the root definitions.
+    #[arg(long = "registry")]
+    pub registry: Option<String>,
+
+    /// Accept the errors removed or reshaped since they were recorded in the
+    /// registry, and overwrite their entries.
+    #[arg(long = "force-registry")]
+    pub force_registry: bool,
+}
@@ -0,0 +1,10 @@
+#[derive(Debug, clap::Args)]
+pub struct SchemaArguments {
+    /// Output directory for the schema files.
+    #[arg(long = "output", default_value = "schema")]
+    pub output_directory: String,
+
+    /// Be verbose and produce debug output.
+    #[arg(long = "verbose", short = 'v')]
+    pub verbose: bool,
+}
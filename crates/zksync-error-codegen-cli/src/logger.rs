@@ -0,0 +1,42 @@
+//!
+//! Logger printing the messages of the code generator to stderr.
+//!
+
+use log::Level;
+use log::LevelFilter;
+use log::Log;
+use log::Metadata;
+use log::Record;
+
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        match record.level() {
+            Level::Error => eprintln!("Error: {}", record.args()),
+            Level::Warn => eprintln!("Warning: {}", record.args()),
+            Level::Info | Level::Debug | Level::Trace => eprintln!("{}", record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Print warnings and errors, and also informational messages if `verbose`.
+pub fn init(verbose: bool) {
+    log::set_logger(&LOGGER).expect("Logger is initialized only once");
+    log::set_max_level(if verbose {
+        LevelFilter::Info
+    } else {
+        LevelFilter::Warn
+    });
+}
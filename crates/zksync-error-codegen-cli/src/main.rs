@@ -1,19 +1,82 @@
 pub mod arguments;
+pub mod logger;
+
+use std::process::ExitCode;
 
 use clap::Parser;
 
 use arguments::Arguments;
+use arguments::Command;
+use arguments::CompatibilityArguments;
+use arguments::LintArguments;
+use arguments::ReportFormat;
+use arguments::SchemaArguments;
 
+use zksync_error_codegen::compatibility::check_compatibility;
 use zksync_error_codegen::error::ProgramError;
+use zksync_error_codegen::lint::run_lints;
 use zksync_error_codegen::load_and_generate;
+use zksync_error_codegen::write_schemas;
+
+/// Exit code signaling that the definitions break backward compatibility, or
+/// violate denied lint rules.
+const EXIT_CHECK_FAILED: u8 = 1;
+/// Exit code signaling a failure to perform the requested operation.
+const EXIT_FAILURE: u8 = 2;
+
+fn check_compatibility_inner(arguments: CompatibilityArguments) -> Result<bool, ProgramError> {
+    let format = arguments.format.clone();
+    let report = check_compatibility(&arguments.into())?;
+    match format {
+        ReportFormat::Human => println!("{report}"),
+        ReportFormat::Json => println!("{:#}", report.to_json()),
+    }
+    Ok(!report.is_breaking())
+}
 
-fn main_inner(arguments: Arguments) -> Result<(), ProgramError> {
-    load_and_generate(arguments.into())
+fn lint_inner(arguments: LintArguments) -> Result<bool, ProgramError> {
+    let format = arguments.format.clone();
+    let report = run_lints(&arguments.into())?;
+    match format {
+        ReportFormat::Human => println!("{report}"),
+        ReportFormat::Json => println!("{:#}", report.to_json()),
+    }
+    Ok(!report.is_denied())
+}
+
+fn write_schemas_inner(arguments: SchemaArguments) -> Result<bool, ProgramError> {
+    let output_directory = arguments.output_directory.into();
+    write_schemas(&output_directory)?;
+    log::info!("Schemas written to {}", output_directory.display());
+    Ok(true)
+}
+
+fn main_inner(arguments: Arguments) -> Result<bool, ProgramError> {
+    match arguments.command {
+        Some(Command::CheckCompatibility(compatibility_arguments)) => {
+            check_compatibility_inner(compatibility_arguments)
+        }
+        Some(Command::Lint(lint_arguments)) => lint_inner(lint_arguments),
+        Some(Command::Schema(schema_arguments)) => write_schemas_inner(schema_arguments),
+        None => load_and_generate(arguments.into()).map(|()| true),
+    }
 }
 
-fn main() {
+fn main() -> ExitCode {
     let arguments = Arguments::parse();
-    if let Err(error) = main_inner(arguments) {
-        eprintln!("{error}")
+    let verbose = match &arguments.command {
+        Some(Command::CheckCompatibility(arguments)) => arguments.verbose,
+        Some(Command::Lint(arguments)) => arguments.verbose,
+        Some(Command::Schema(arguments)) => arguments.verbose,
+        None => arguments.verbose,
+    };
+    logger::init(verbose);
+    match main_inner(arguments) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(EXIT_CHECK_FAILED),
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::from(EXIT_FAILURE)
+        }
     }
 }
@@ -0,0 +1,114 @@
+//
+// AUTOGENERATED BASED ON A SET OF JSON FILES, DO NOT EDIT MANUALLY
+//
+
+/**
+ * Format specification of a placeholder in an error message, e.g. `>4` in
+ * `{index:>4}`. Follows the syntax of Rust's `format!`.
+ */
+export interface FormatSpec {
+  readonly fill?: string;
+  readonly align?: "<" | "^" | ">";
+  readonly sign?: "+" | "-";
+  readonly alternate?: boolean;
+  readonly zero?: boolean;
+  readonly width?: number;
+  readonly precision?: number;
+  /**
+   * Formatting trait: empty for `Display`, `?` for `Debug`, `x` for
+   * `LowerHex` and so on.
+   */
+  readonly kind?: string;
+}
+
+const RADIXES: { readonly [kind: string]: [number, string] } = {
+  x: [16, "0x"],
+  X: [16, "0x"],
+  o: [8, "0o"],
+  b: [2, "0b"],
+};
+
+function display(value: unknown): string {
+  if (value === undefined || value === null) {
+    return "<none>";
+  }
+  if (typeof value === "string") {
+    return value;
+  }
+  if (typeof value === "object" && value !== null) {
+    return JSON.stringify(value);
+  }
+  return String(value);
+}
+
+function debug(value: unknown): string {
+  if (typeof value === "string") {
+    return JSON.stringify(value);
+  }
+  return display(value);
+}
+
+/** Sign and digits of a number formatted according to `kind`. */
+function formatNumber(value: number | bigint, kind: string, spec: FormatSpec): [string, string] {
+  const negative = value < 0;
+  const magnitude = typeof value === "bigint" ? (negative ? -value : value) : Math.abs(value);
+  const sign = negative ? "-" : spec.sign === "+" ? "+" : "";
+
+  const radix = RADIXES[kind];
+  if (radix !== undefined) {
+    const [base, prefix] = radix;
+    const digits = magnitude.toString(base);
+    return [
+      sign + (spec.alternate ? prefix : ""),
+      kind === "X" ? digits.toUpperCase() : digits,
+    ];
+  }
+  if (kind === "e" || kind === "E") {
+    const digits = Number(magnitude).toExponential(spec.precision).replace("e+", "e");
+    return [sign, kind === "E" ? digits.toUpperCase() : digits];
+  }
+  if (typeof magnitude === "number" && spec.precision !== undefined) {
+    return [sign, magnitude.toFixed(spec.precision)];
+  }
+  return [sign, magnitude.toString()];
+}
+
+function pad(content: string, length: number, spec: FormatSpec, numeric: boolean): string {
+  const padding = (spec.width ?? 0) - length;
+  if (padding <= 0) {
+    return content;
+  }
+  const fill = spec.fill ?? " ";
+  switch (spec.align ?? (numeric ? ">" : "<")) {
+    case "<":
+      return content + fill.repeat(padding);
+    case ">":
+      return fill.repeat(padding) + content;
+    case "^": {
+      const left = Math.floor(padding / 2);
+      return fill.repeat(left) + content + fill.repeat(padding - left);
+    }
+  }
+}
+
+/** Format a field of an error the way the Rust code formats it in messages. */
+export function formatValue(value: unknown, spec: FormatSpec = {}): string {
+  const debugKind = spec.kind?.endsWith("?") ?? false;
+  const kind = (spec.kind ?? "").replace("?", "");
+
+  if (typeof value === "number" || typeof value === "bigint") {
+    const [sign, digits] = formatNumber(value, kind, spec);
+    const length = Array.from(sign + digits).length;
+    if (spec.zero) {
+      const zeros = "0".repeat(Math.max((spec.width ?? 0) - length, 0));
+      return sign + zeros + digits;
+    }
+    return pad(sign + digits, length, spec, true);
+  }
+
+  let content = debugKind ? debug(value) : display(value);
+  if (!debugKind && typeof value === "string" && spec.precision !== undefined) {
+    content = Array.from(content).slice(0, spec.precision).join("");
+  }
+  return pad(content, Array.from(content).length, spec, false);
+}
@@ -0,0 +1,9 @@
+//
+// AUTOGENERATED BASED ON A SET OF JSON FILES, DO NOT EDIT MANUALLY
+//
+
+export * from "./error";
+export * from "./format";
+export * from "./identifier";
+export * from "./message";
+export * from "./serialized";
@@ -0,0 +1,106 @@
+//
+// AUTOGENERATED BASED ON A SET OF JSON FILES, DO NOT EDIT MANUALLY
+//
+
+import type { ZksyncError } from "./error";
+import { decode, findByCode, getCode, getMetadata } from "./identifier";
+import { getMessage } from "./message";
+
+/** Error in the serialized form produced by the Rust code. */
+export interface SerializedError {
+  readonly code: number;
+  readonly message: string;
+  /**
+   * The error nested in its component and domain, e.g.
+   * `{ "Core": { "Sequencer": { "GenericError": { "message": "..." } } } }`.
+   */
+  readonly data: unknown;
+}
+
+export class DecodingError extends Error {
+  constructor(
+    message: string,
+    readonly serialized: unknown,
+  ) {
+    super(message);
+    this.name = "DecodingError";
+  }
+}
+
+function isObject(value: unknown): value is { readonly [key: string]: unknown } {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+function unwrap(data: unknown, key: string, serialized: unknown): unknown {
+  if (!isObject(data) || Object.keys(data).length !== 1 || !(key in data)) {
+    throw new DecodingError(`Expected an object with the single key "${key}".`, serialized);
+  }
+  return data[key];
+}
+
+/** Decode an error from its serialized form, checking its code and shape. */
+export function deserialize(serialized: SerializedError): ZksyncError {
+  const location = findByCode(decode(serialized.code));
+  if (location === undefined) {
+    throw new DecodingError(`Unknown error code ${serialized.code}.`, serialized);
+  }
+  const [domain, component, error] = location;
+  const variant = unwrap(
+    unwrap(serialized.data, domain.rustName, serialized),
+    component.rustName,
+    serialized,
+  );
+
+  // Errors without fields are serialized as their names.
+  let fields: unknown = {};
+  if (!(error.fields.length === 0 && variant === error.rustName)) {
+    fields = unwrap(variant, error.rustName, serialized);
+    if (!isObject(fields)) {
+      throw new DecodingError(`Expected the fields of the error ${error.identifier}.`, serialized);
+    }
+    for (const field of error.fields) {
+      if (!(field in fields)) {
+        throw new DecodingError(
+          `Missing field "${field}" of the error ${error.identifier}.`,
+          serialized,
+        );
+      }
+    }
+  }
+
+  return {
+    domain: domain.name,
+    component: component.name,
+    name: error.name,
+    fields,
+  } as unknown as ZksyncError;
+}
+
+/** Decode an error from the JSON text of its serialized form. */
+export function parse(json: string): ZksyncError {
+  const value: unknown = JSON.parse(json);
+  if (
+    !isObject(value) ||
+    typeof value.code !== "number" ||
+    typeof value.message !== "string" ||
+    !("data" in value)
+  ) {
+    throw new DecodingError(
+      "Expected an object with the fields `code`, `message` and `data`.",
+      value,
+    );
+  }
+  return deserialize(value as unknown as SerializedError);
+}
+
+/** Serialize an error the same way the Rust code does. */
+export function serialize(error: ZksyncError): SerializedError {
+  const [domain, component, metadata] = getMetadata(error);
+  const variant =
+    metadata.fields.length === 0 ? metadata.rustName : { [metadata.rustName]: error.fields };
+  return {
+    code: getCode(error),
+    message: getMessage(error),
+    data: { [domain.rustName]: { [component.rustName]: variant } },
+  };
+}
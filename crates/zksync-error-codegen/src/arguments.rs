@@ -1,15 +1,33 @@
 use std::path::PathBuf;
 
+use crate::loader::cargo::CargoConfig;
+use crate::loader::network::NetworkConfig;
+use crate::loader::registry::RegistryConfig;
+pub use zksync_error_model::lint::Level as LintLevel;
+pub use zksync_error_model::lint::LintLevels;
+pub use zksync_error_model::lint::Rule as LintRule;
+pub use zksync_error_model::merger::MergeMode;
+
+/// Arguments passed to a backend, as pairs of names and values.
+pub type BackendArguments = Vec<(String, String)>;
+
 pub struct GenerationArguments {
-    pub verbose: bool,
     pub root_link: String,
     pub input_links: Vec<String>,
-    pub outputs: Vec<(PathBuf, Backend, Vec<(String, String)>)>,
+    pub outputs: Vec<(PathBuf, Backend, BackendArguments)>,
+    pub network: NetworkConfig,
+    /// Registry of the generated error identifiers.
+    pub registry: RegistryConfig,
+    pub cargo: CargoConfig,
+    pub merge_mode: MergeMode,
+    /// Levels of the lint rules, overriding the ones set in the files.
+    pub lints: LintLevels,
 }
 
 #[derive(Clone, Debug)]
 pub enum Backend {
     Rust,
+    Typescript,
     Mdbook,
 }
 
@@ -17,7 +35,34 @@ impl std::fmt::Display for Backend {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
             Backend::Rust => "rust",
+            Backend::Typescript => "typescript",
             Backend::Mdbook => "doc-mdbook",
         })
     }
 }
+
+/// Where to take one of the two compared versions of the model from.
+pub struct ModelSource {
+    pub root_link: String,
+    pub input_links: Vec<String>,
+    /// Git revision to read the files from; the working tree if absent.
+    pub revision: Option<String>,
+}
+
+pub struct CompatibilityArguments {
+    pub network: NetworkConfig,
+    pub cargo: CargoConfig,
+    pub merge_mode: MergeMode,
+    pub baseline: ModelSource,
+    pub current: ModelSource,
+}
+
+pub struct LintArguments {
+    pub root_link: String,
+    pub input_links: Vec<String>,
+    pub network: NetworkConfig,
+    pub cargo: CargoConfig,
+    pub merge_mode: MergeMode,
+    /// Levels of the lint rules, overriding the ones set in the files.
+    pub lints: LintLevels,
+}
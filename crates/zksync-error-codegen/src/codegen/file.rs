@@ -1,7 +1,28 @@
 use std::path::PathBuf;
 
+use include_dir::Dir;
+
 #[derive(Debug, Clone)]
 pub struct File {
     pub relative_path: PathBuf,
     pub content: String,
 }
+
+impl File {
+    /// Copy the file `filename` of the templates of a backend unchanged.
+    pub fn copy_as_is(templates: &Dir, filename: &str) -> File {
+        let content = templates
+            .get_file(filename)
+            .unwrap_or_else(|| panic!("Missing file `{filename}`"))
+            .contents_utf8()
+            .unwrap_or_else(|| {
+                panic!("Internal error: decoding utf-8 string from file {filename}.")
+            });
+
+        log::debug!("Copy as is: {filename}: \n{content}");
+        File {
+            relative_path: PathBuf::from(filename),
+            content: content.into(),
+        }
+    }
+}
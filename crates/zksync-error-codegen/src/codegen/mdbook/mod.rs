@@ -43,57 +43,6 @@ fn initialize_tera() -> Result<Tera, <MDBookBackend as Backend<MDBookBackendConf
 }
 
 impl MDBookBackend {
-    fn copy_css(&mut self, _config: &MDBookBackendConfig) -> Result<File, GenerationError> {
-        let filename = "css/version-box.css";
-        let content = TEMPLATES_DIR
-            .get_file(filename)
-            .unwrap_or_else(|| panic!("Missing file `{filename}`"))
-            .contents_utf8()
-            .unwrap_or_else(|| {
-                panic!("Internal error: decoding utf-8 string from file {filename}.")
-            });
-
-        Ok(File {
-            relative_path: PathBuf::from(filename),
-            content: content.into(),
-        })
-    }
-    fn copy_js(&mut self, _config: &MDBookBackendConfig) -> Result<File, GenerationError> {
-        let filename = "js/version-box.js";
-        let content = TEMPLATES_DIR
-            .get_file(filename)
-            .unwrap_or_else(|| panic!("Missing file `{filename}`"))
-            .contents_utf8()
-            .unwrap_or_else(|| {
-                panic!("Internal error: decoding utf-8 string from file {filename}.")
-            });
-
-        Ok(File {
-            relative_path: PathBuf::from(filename),
-            content: content.into(),
-        })
-    }
-
-    fn copy_as_is(
-        &mut self,
-        filename: &str,
-        _config: &MDBookBackendConfig,
-    ) -> Result<File, GenerationError> {
-        let content = TEMPLATES_DIR
-            .get_file(filename)
-            .unwrap_or_else(|| panic!("Missing file `{filename}`"))
-            .contents_utf8()
-            .unwrap_or_else(|| {
-                panic!("Internal error: decoding utf-8 string from file {filename}.")
-            });
-
-        eprintln!("Copy as is: {filename}: \n{content}");
-        Ok(File {
-            relative_path: PathBuf::from(filename),
-            content: content.into(),
-        })
-    }
-
     fn generate_summary(
         &mut self,
         tera: &Tera,
@@ -194,9 +143,9 @@ impl Backend<MDBookBackendConfig> for MDBookBackend {
         let model = flatten(&self.model);
         let mut results = vec![
             self.generate_summary(&tera, &model, _config)?,
-            self.copy_as_is("book.toml", _config)?,
-            self.copy_as_is("css/version-box.css", _config)?,
-            self.copy_as_is("js/version-box.js", _config)?,
+            File::copy_as_is(&TEMPLATES_DIR, "book.toml"),
+            File::copy_as_is(&TEMPLATES_DIR, "css/version-box.css"),
+            File::copy_as_is(&TEMPLATES_DIR, "js/version-box.js"),
         ];
 
         for domain in model.domains.values() {
@@ -1,6 +1,7 @@
 pub mod file;
 pub mod mdbook;
 pub mod rust;
+pub mod typescript;
 
 use file::File;
 
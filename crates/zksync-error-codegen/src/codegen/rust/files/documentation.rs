@@ -17,7 +17,7 @@ impl RustBackend {
 
 
             fn get_model() -> ErrorHierarchy {
-                zksync_error_description::ErrorHierarchy::from_str(include_str!("../resources/error-model-dump.json"))
+                include_str!("../resources/error-model-dump.json").parse().expect("Always valid")
             }
 
             #[derive(Debug)]
@@ -9,9 +9,14 @@ use crate::codegen::rust::util::codegen::doc_tokens;
 use crate::codegen::rust::util::codegen::ident;
 use crate::codegen::rust::RustBackend;
 use crate::codegen::File;
+use zksync_error_model::inner::template::MessageSegment;
+use zksync_error_model::inner::template::Placeholder;
 use zksync_error_model::inner::ErrorDescription;
 use zksync_error_model::inner::ErrorDocumentation;
 use zksync_error_model::inner::FieldDescription;
+use zksync_error_model::inner::Lifecycle;
+use zksync_error_model::inner::Model;
+use zksync_error_model::inner::TypeExpression;
 
 fn error_documentation(description: &ErrorDescription) -> TokenStream {
     if let Some(ErrorDocumentation {
@@ -37,6 +42,107 @@ fn error_documentation(description: &ErrorDescription) -> TokenStream {
     }
 }
 
+/// Expression rendering `value` of the type `typ` as a `String`. Absent
+/// optional values are rendered as `<none>`, and lists and maps as JSON.
+fn display_expression(value: TokenStream, typ: &TypeExpression, spec: &str) -> TokenStream {
+    let format_string = format!("{{{spec}}}");
+    match typ {
+        TypeExpression::Optional(inner) => {
+            let inner = display_expression(quote! { value }, inner, spec);
+            quote! {
+                match #value {
+                    Some(value) => #inner,
+                    None => String::from("<none>"),
+                }
+            }
+        }
+        TypeExpression::List(_) | TypeExpression::Map(_, _) => quote! {
+            format!(#format_string, serde_json::to_string(#value).unwrap_or_default())
+        },
+        TypeExpression::Named(_) | TypeExpression::ByteArray(_) | TypeExpression::Bytes => {
+            quote! { format!(#format_string, #value) }
+        }
+    }
+}
+
+/// Translate the message of `error` into a call to `format!`, whose
+/// placeholders refer to the fields bound in the match arm. Fields that may
+/// be absent, lists and maps are formatted separately.
+fn message_expression(
+    model: &Model,
+    error: &ErrorDescription,
+) -> Result<TokenStream, GenerationError> {
+    let mut format_string = format!("{} ", error.get_identifier());
+    let mut arguments = Vec::new();
+    for segment in &error.message.segments {
+        match segment {
+            MessageSegment::Literal(text) => {
+                format_string.push_str(&text.replace('{', "{{").replace('}', "}}"))
+            }
+            MessageSegment::Placeholder(Placeholder { field, spec }) => {
+                let spec = spec
+                    .as_ref()
+                    .map(|spec| format!(":{spec}"))
+                    .unwrap_or_default();
+                let typ = error
+                    .fields
+                    .iter()
+                    .find(|f| &f.name == field)
+                    .map(|f| -> Result<_, GenerationError> {
+                        let typ = model.resolve_type(&f.r#type)?;
+                        Ok(if f.optional {
+                            TypeExpression::Optional(Box::new(typ))
+                        } else {
+                            typ
+                        })
+                    })
+                    .transpose()?;
+                match typ {
+                    Some(typ) if !matches!(typ, TypeExpression::Named(_)) => {
+                        format_string.push_str("{}");
+                        arguments.push(display_expression(ident(field), &typ, &spec));
+                    }
+                    _ => format_string.push_str(&format!("{{{field}{spec}}}")),
+                }
+            }
+        }
+    }
+    Ok(quote! { format!(#format_string #(, #arguments)*) })
+}
+
+/// Function giving the default value of a field, named after the component,
+/// the error and the field.
+fn default_function_name(error: &ErrorDescription, field: &FieldDescription) -> String {
+    format!(
+        "default_{}_{}_{}",
+        RustBackend::component_ident(&error.component),
+        RustBackend::error_ident(error),
+        field.name
+    )
+}
+
+fn deprecation(description: &ErrorDescription) -> TokenStream {
+    match &description.lifecycle {
+        Lifecycle::Deprecated {
+            message,
+            replacement,
+        } => {
+            let note = match replacement {
+                Some(replacement) => format!("{message} Use {replacement} instead.")
+                    .trim()
+                    .to_owned(),
+                None => message.clone(),
+            };
+            if note.is_empty() {
+                quote! { #[deprecated] }
+            } else {
+                quote! { #[deprecated(note = #note)] }
+            }
+        }
+        Lifecycle::Active | Lifecycle::Retired => quote! {},
+    }
+}
+
 fn component_doc(component: &ComponentDescription) -> TokenStream {
     doc_tokens(&format!(
         "{}
@@ -47,35 +153,82 @@ Domain: {}",
 }
 
 impl RustBackend {
+    fn field_type(&self, field: &FieldDescription) -> Result<TokenStream, GenerationError> {
+        let typ = self.rust_type(&self.model.resolve_type(&field.r#type)?)?;
+        Ok(if field.optional {
+            quote! { Option<#typ> }
+        } else {
+            typ
+        })
+    }
+
     fn error_variant(&self, error: &ErrorDescription) -> Result<TokenStream, GenerationError> {
         let ErrorDescription { code, fields, .. } = error;
         let mut field_tokens = Vec::new();
-        for FieldDescription { name, r#type } in fields {
-            let name = ident(name);
-            let typ = ident(&self.get_rust_type(r#type)?);
-            field_tokens.push(quote! { #name : #typ  });
+        for field in fields {
+            let name = ident(&field.name);
+            let typ = self.field_type(field)?;
+            let doc = doc_tokens(field.description.trim());
+            let serde_default = match (&field.default, field.optional) {
+                (Some(_), _) => {
+                    let function = default_function_name(error, field);
+                    quote! { #[serde(default = #function)] }
+                }
+                (None, true) => quote! { #[serde(default)] },
+                (None, false) => quote! {},
+            };
+            field_tokens.push(quote! { #doc #serde_default #name : #typ  });
         }
         let error_name = RustBackend::error_ident(error);
         let doc = error_documentation(error);
+        let deprecation = deprecation(error);
         let field_tokens_if_nonempty = if fields.is_empty() {
             quote! {}
         } else {
             quote! { {  #( #field_tokens , )* } }
         };
         Ok(quote! { #doc
+                     #deprecation
                      #error_name #field_tokens_if_nonempty = # code
         })
     }
 
+    /// Functions giving the default values of the fields of `error` that have
+    /// them, parsed from their JSON representations.
+    fn default_functions(&self, error: &ErrorDescription) -> Result<TokenStream, GenerationError> {
+        let mut functions = Vec::new();
+        for field in &error.fields {
+            if let Some(default) = &field.default {
+                let function = ident(&default_function_name(error, field));
+                let typ = self.field_type(field)?;
+                let json = default.to_string();
+                functions.push(quote! {
+                    #[allow(non_snake_case)]
+                    fn #function() -> #typ {
+                        serde_json::from_str(#json).expect("Invalid default value")
+                    }
+                });
+            }
+        }
+        Ok(quote! { #( #functions )* })
+    }
+
     pub fn generate_file_error_definitions(
         &mut self,
         config: &Config,
     ) -> Result<File, GenerationError> {
-        let definitions = self.model.components().map(|component| -> TokenStream {
+        let definitions = self.model.components().map(|component| -> Result<TokenStream, GenerationError> {
 
 
             let component_code = RustBackend::component_code_ident(&component.meta);
-            let error_variants = component.errors.iter().flat_map(|component| self.error_variant(component));
+            let error_variants = component
+                .emitted_errors()
+                .map(|error| self.error_variant(error))
+                .collect::<Result<Vec<_>, _>>()?;
+            let default_functions = component
+                .emitted_errors()
+                .map(|error| self.default_functions(error))
+                .collect::<Result<Vec<_>, _>>()?;
             let component_name = RustBackend::component_ident(&component.meta);
 
             let component_doc = component_doc(component);
@@ -92,31 +245,39 @@ impl RustBackend {
 
             let impl_custom_error_message = {
 
-                let branch_patterns = component.errors.iter().map(|error| {
+                let branch_patterns = component.emitted_errors().map(|error| {
                     let error_name = RustBackend::error_ident(error);
                     let field_tokens = if error.fields.is_empty() {
                         quote! { }
                     }
                     else {
-                        let pattern_fields = error.fields.iter().map( | field | ident(&field.name));
-                        quote! { {  #( #pattern_fields, )* } }
+                        let (mentioned, omitted): (Vec<_>, Vec<_>) = error
+                            .fields
+                            .iter()
+                            .partition(|field| error.message.mentions(&field.name));
+                        let pattern_fields = mentioned.iter().map(|field| ident(&field.name));
+                        let rest = (!omitted.is_empty()).then_some(quote! { .. });
+                        quote! { {  #( #pattern_fields, )* #rest } }
                     };
                     quote! { #component_name :: #error_name #field_tokens }
                 });
 
-                let messages = component.errors.iter().map(|error| { format!("{} {}", error.get_identifier(), error.message) } );
+                let messages = component
+                    .emitted_errors()
+                    .map(|error| message_expression(&self.model, error))
+                    .collect::<Result<Vec<_>, _>>()?;
                 quote! {
                     impl CustomErrorMessage for #component_name {
                         fn get_message(&self) -> String {
                             match self {
-                                #( #branch_patterns => { format! ( #messages ) } , )*
+                                #( #branch_patterns => { #messages } , )*
                             }
                         }
                     }
                 }
 
             };
-            quote! {
+            Ok(quote! {
 
                 #component_doc
                 #[repr(u32)]
@@ -130,6 +291,8 @@ impl RustBackend {
                     #( #error_variants , )*
                 }
 
+                #( #default_functions )*
+
                 impl std::error::Error for #component_name {}
 
                 impl NamedError for #component_name {
@@ -177,13 +340,14 @@ impl RustBackend {
                 }
 
                 #impl_custom_error_message
-            }
+            })
 
-        });
+        }).collect::<Result<Vec<_>, _>>()?;
 
         let contents = quote! {
             #![allow(unused)]
             #![allow(non_camel_case_types)]
+            #![allow(deprecated)]
 
             use crate::documentation::Documented;
             use crate::error::CustomErrorMessage;
@@ -203,3 +367,106 @@ impl RustBackend {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::rust::config::Config;
+    use crate::codegen::rust::RustBackend;
+    use crate::testing::model;
+    use crate::testing::model_of;
+    use crate::testing::ROOT;
+
+    #[test]
+    fn optional_and_defaulted_fields() {
+        let model = model(
+            r#"
+[[domains.components.errors]]
+name = "Failure"
+code = 1
+message = "Failure at {index} in {path}."
+fields = [
+  { name = "index", type = "uint", description = "Index of the batch." },
+  { name = "path", type = "string", optional = true },
+  { name = "retries", type = "uint", default = 3 },
+]
+"#,
+        );
+        let file = RustBackend::new(&model)
+            .generate_file_error_definitions(&Config { use_anyhow: false })
+            .unwrap();
+        let content = file.content;
+        for expected in [
+            "#[doc = \"Index of the batch.\"]\n        index: u32,",
+            "#[serde(default)]\n        path: Option<String>,",
+            "#[serde(default = \"default_Sequencer_Failure_retries\")]\n        retries: u32,",
+            "fn default_Sequencer_Failure_retries() -> u32 {\n    serde_json::from_str(\"3\")",
+            "None => String::from(\"<none>\")",
+        ] {
+            assert!(content.contains(expected), "`{expected}` in:\n{content}");
+        }
+    }
+
+    #[test]
+    fn unbound_field_types_are_reported() {
+        let mut model = model(
+            r#"
+[[domains.components.errors]]
+name = "Failure"
+code = 1
+message = "Failure in {path}."
+fields = [{ name = "path", type = "string" }]
+"#,
+        );
+        model
+            .types
+            .get_mut("string")
+            .unwrap()
+            .bindings
+            .remove("rust");
+        assert!(RustBackend::new(&model)
+            .generate_file_error_definitions(&Config { use_anyhow: false })
+            .is_err());
+    }
+
+    #[test]
+    fn declared_types_shadow_builtin_types() {
+        // Types of the same names as the built-in `bytes` and the constructor
+        // `map` keep their bindings, and their serialized form.
+        let model = model_of(&format!(
+            r#"
+[[types]]
+name = "bytes"
+description = "Sequence of bytes"
+bindings.rust = {{ name = "Vec<u8>", path = "" }}
+bindings.typescript = {{ name = "number[]", path = "" }}
+
+[[types]]
+name = "map"
+description = "String to string mapping."
+bindings.rust = {{ name = "Vec<(String,String)>", path = "" }}
+bindings.typescript = {{ name = "[string, string][]", path = "" }}
+{ROOT}
+[[domains.components.errors]]
+name = "Failure"
+code = 1
+message = "Failure."
+fields = [
+  {{ name = "payload", type = "bytes" }},
+  {{ name = "labels", type = "map" }},
+  {{ name = "hashes", type = "map<string, bytes<4>>" }},
+]
+"#
+        ));
+        let content = RustBackend::new(&model)
+            .generate_file_error_definitions(&Config { use_anyhow: false })
+            .unwrap()
+            .content;
+        for expected in [
+            "payload: Vec<u8>,",
+            "labels: Vec<(String, String)>,",
+            "hashes: std::collections::BTreeMap<String, crate::types::ByteArray<4>>,",
+        ] {
+            assert!(content.contains(expected), "`{expected}` in:\n{content}");
+        }
+    }
+}
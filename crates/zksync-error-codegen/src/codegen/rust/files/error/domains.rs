@@ -108,6 +108,7 @@ impl RustBackend {
                  domain,
                  domain_code,
                  components,
+                 component_values,
                  ..
              }| {
                 quote! {
@@ -118,7 +119,7 @@ impl RustBackend {
                     #[strum_discriminants(derive(serde::Serialize, serde::Deserialize, FromRepr))]
                     #[strum_discriminants(vis(pub))]
                     pub enum #domain {
-                        #( #components( #components ),)*
+                        #( #components( #components ) = #component_values ,)*
                     }
 
                     impl #domain {
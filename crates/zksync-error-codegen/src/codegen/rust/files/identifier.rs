@@ -30,16 +30,36 @@ impl RustBackend {
 
         };
 
+        let layout = &self.model.code_layout;
+        let layout_description = format!("Numeric codes use {layout}.");
+        let u32_literal = |value: u64| {
+            proc_macro2::Literal::u32_unsuffixed(u32::try_from(value).expect("Internal error"))
+        };
+        let domain_capacity = u32_literal(layout.domain_capacity());
+        let component_capacity = u32_literal(layout.component_capacity());
+        let error_capacity = u32_literal(layout.error_capacity());
+        let domain_multiplier = u32_literal(layout.domain_multiplier());
+        let component_multiplier = u32_literal(layout.component_multiplier());
+
         let impl_structured_error_code = quote! {
-                        impl StructuredErrorCode {
+            #[doc = #layout_description]
+            impl StructuredErrorCode {
+                pub const DOMAIN_CAPACITY: u32 = #domain_capacity;
+                pub const COMPONENT_CAPACITY: u32 = #component_capacity;
+                pub const ERROR_CAPACITY: u32 = #error_capacity;
+                const DOMAIN_MULTIPLIER: u32 = #domain_multiplier;
+                const COMPONENT_MULTIPLIER: u32 = #component_multiplier;
+
                 pub fn encode(&self) -> u32 {
-                    self.domain_code * 10000 + self.component_code * 1000 + self.error_code
+                    self.domain_code * Self::DOMAIN_MULTIPLIER
+                        + self.component_code * Self::COMPONENT_MULTIPLIER
+                        + self.error_code
                 }
 
                 pub fn decode(raw_code: u32) -> Self {
-                    let error_code = raw_code % 1000;
-                    let component_code = (raw_code / 1000) % 10;
-                    let domain_code = (raw_code / 10000) % 10;
+                    let error_code = raw_code % Self::ERROR_CAPACITY;
+                    let component_code = (raw_code / Self::COMPONENT_MULTIPLIER) % Self::COMPONENT_CAPACITY;
+                    let domain_code = (raw_code / Self::DOMAIN_MULTIPLIER) % Self::DOMAIN_CAPACITY;
                     StructuredErrorCode {
                         domain_code,
                         component_code,
@@ -64,9 +84,12 @@ impl RustBackend {
                     }
 
                     pub fn encode(&self) -> u32 {
-                        let domain_code: u32 = self.kind.domain_code();
-                        let component_code: u32 = self.kind.component_code();
-                        domain_code * 10000 + component_code * 1000 + self.code
+                        StructuredErrorCode {
+                            domain_code: self.kind.domain_code(),
+                            component_code: self.kind.component_code(),
+                            error_code: self.code,
+                        }
+                        .encode()
                     }
 
                     pub fn decode(code: StructuredErrorCode) -> Option<Self> {
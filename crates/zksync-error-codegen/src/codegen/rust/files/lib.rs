@@ -12,12 +12,14 @@ impl RustBackend {
         let imports = quote! {
 
             #![allow(unused)]
+            #![allow(deprecated)]
 
             pub mod error;
             pub mod identifier;
             pub mod kind;
             pub mod packed;
             pub mod serialized;
+            pub mod types;
             pub mod untyped;
             pub mod documentation;
 
@@ -35,7 +37,7 @@ impl RustBackend {
                 let inner_module = ident(&component.meta.identifier);
                 let enum_name = Self::component_ident(&component.meta);
                 let alias = Self::component_error_alias_ident(&component.meta);
-                let errors = component.errors.iter().map(Self::error_ident);
+                let errors = component.emitted_errors().map(Self::error_ident);
                 let macro_name = ident(&format!("{outer_module}_{inner_module}_generic_error"));
 
                 quote! {
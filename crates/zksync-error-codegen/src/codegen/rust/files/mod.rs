@@ -6,4 +6,5 @@ pub mod kind;
 pub mod lib;
 pub mod packed;
 pub mod serialized;
+pub mod types;
 pub mod untyped;
@@ -0,0 +1,107 @@
+use quote::quote;
+use std::path::PathBuf;
+
+use crate::codegen::rust::error::GenerationError;
+use crate::codegen::rust::RustBackend;
+use crate::codegen::File;
+
+impl RustBackend {
+    pub fn generate_file_types(&mut self) -> Result<File, GenerationError> {
+        let result = quote! {
+            /// Bytes of any length, represented by a `0x`-prefixed hex string.
+            #[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+            pub struct HexBytes(pub Vec<u8>);
+
+            /// Exactly `N` bytes, represented by a `0x`-prefixed hex string.
+            #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+            pub struct ByteArray<const N: usize>(pub [u8; N]);
+
+            fn encode(bytes: &[u8]) -> String {
+                let mut result = String::from("0x");
+                for byte in bytes {
+                    result.push_str(&format!("{byte:02x}"));
+                }
+                result
+            }
+
+            fn decode(input: &str) -> Result<Vec<u8>, String> {
+                let digits = input.strip_prefix("0x").unwrap_or(input);
+                if !digits.is_ascii() || digits.len() % 2 != 0 {
+                    return Err(format!("invalid hex string `{input}`"));
+                }
+                (0..digits.len())
+                    .step_by(2)
+                    .map(|i| {
+                        u8::from_str_radix(&digits[i..i + 2], 16)
+                            .map_err(|_| format!("invalid hex string `{input}`"))
+                    })
+                    .collect()
+            }
+
+            impl From<Vec<u8>> for HexBytes {
+                fn from(value: Vec<u8>) -> Self {
+                    Self(value)
+                }
+            }
+
+            impl std::fmt::Display for HexBytes {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.pad(&encode(&self.0))
+                }
+            }
+
+            impl serde::Serialize for HexBytes {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(&encode(&self.0))
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for HexBytes {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let input = <String as serde::Deserialize>::deserialize(deserializer)?;
+                    decode(&input).map(Self).map_err(serde::de::Error::custom)
+                }
+            }
+
+            impl<const N: usize> Default for ByteArray<N> {
+                fn default() -> Self {
+                    Self([0; N])
+                }
+            }
+
+            impl<const N: usize> From<[u8; N]> for ByteArray<N> {
+                fn from(value: [u8; N]) -> Self {
+                    Self(value)
+                }
+            }
+
+            impl<const N: usize> std::fmt::Display for ByteArray<N> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.pad(&encode(&self.0))
+                }
+            }
+
+            impl<const N: usize> serde::Serialize for ByteArray<N> {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(&encode(&self.0))
+                }
+            }
+
+            impl<'de, const N: usize> serde::Deserialize<'de> for ByteArray<N> {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let input = <String as serde::Deserialize>::deserialize(deserializer)?;
+                    let bytes = decode(&input).map_err(serde::de::Error::custom)?;
+                    let length = bytes.len();
+                    bytes.try_into().map(Self).map_err(|_| {
+                        serde::de::Error::custom(format!("expected {N} bytes, found {length}"))
+                    })
+                }
+            }
+        };
+
+        Ok(File {
+            content: Self::format_with_preamble(&result)?,
+            relative_path: PathBuf::from("src/types.rs"),
+        })
+    }
+}
@@ -6,6 +6,7 @@ pub mod util;
 pub use config::Config as RustBackendConfig;
 use error::GenerationError;
 use proc_macro2::TokenStream;
+use quote::quote;
 use util::codegen::ident;
 use util::codegen::sanitize;
 use zksync_error_model::inner::ComponentMetadata;
@@ -16,6 +17,7 @@ use zksync_error_model::error::ModelValidationError;
 use zksync_error_model::inner::ErrorDescription;
 use zksync_error_model::inner::FullyQualifiedTargetLanguageType;
 use zksync_error_model::inner::Model;
+use zksync_error_model::inner::TypeExpression;
 
 use super::Backend;
 use super::File;
@@ -44,6 +46,7 @@ impl Backend<RustBackendConfig> for RustBackend {
             self.generate_file_lib()?,
             self.generate_file_packed()?,
             self.generate_file_serialized()?,
+            self.generate_file_types()?,
             self.generate_file_untyped()?,
             self.generate_file_cargo(config)?,
             File {
@@ -69,14 +72,12 @@ impl RustBackend {
         let all_domains: Vec<_> = model
             .domains
             .values()
-            .cloned()
             .map(|domain| ident(&Self::domain_type_name(domain.meta.as_ref()).unwrap()))
             .collect();
 
         let all_domain_codes: Vec<_> = model
             .domains
             .values()
-            .cloned()
             .map(|domain| ident(&Self::domain_code_type_name(domain.meta.as_ref()).unwrap()))
             .collect();
 
@@ -113,6 +114,39 @@ impl RustBackend {
         Ok(Self::type_as_rust(typ))
     }
 
+    /// Translate a field type into a Rust type. Lists and maps become `Vec`
+    /// and `BTreeMap`, and byte strings the hex-encoded types of the
+    /// generated `types` module.
+    fn rust_type(&self, typ: &TypeExpression) -> Result<TokenStream, GenerationError> {
+        Ok(match typ {
+            TypeExpression::Named(name) => {
+                let binding = self.get_rust_type(name)?;
+                match syn::parse_str::<syn::Type>(&binding) {
+                    Ok(parsed) => quote! { #parsed },
+                    Err(_) => ident(&binding),
+                }
+            }
+            TypeExpression::List(item) => {
+                let item = self.rust_type(item)?;
+                quote! { Vec<#item> }
+            }
+            TypeExpression::Optional(inner) => {
+                let inner = self.rust_type(inner)?;
+                quote! { Option<#inner> }
+            }
+            TypeExpression::Map(key, value) => {
+                let key = self.rust_type(key)?;
+                let value = self.rust_type(value)?;
+                quote! { std::collections::BTreeMap<#key, #value> }
+            }
+            TypeExpression::ByteArray(size) => {
+                let size = proc_macro2::Literal::usize_unsuffixed(*size);
+                quote! { crate::types::ByteArray<#size> }
+            }
+            TypeExpression::Bytes => quote! { crate::types::HexBytes },
+        })
+    }
+
     fn component_type_name(component: &ComponentMetadata) -> Result<String, GenerationError> {
         let name = component
             .bindings
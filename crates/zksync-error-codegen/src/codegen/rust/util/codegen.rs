@@ -8,6 +8,8 @@ pub struct DomainContext {
     pub domain_code: TokenStream,
     pub components: Vec<TokenStream>,
     pub component_codes: Vec<TokenStream>,
+    /// Numeric codes of the components, in the same order as `components`.
+    pub component_values: Vec<u32>,
 }
 
 pub struct ComponentContext {
@@ -62,6 +64,11 @@ pub fn map_domains<'a, U>(
                 .values()
                 .map(|component| RustBackend::component_code_ident(&component.meta))
                 .collect(),
+            component_values: domain
+                .components
+                .values()
+                .map(|component| component.meta.code)
+                .collect(),
         };
         mapper(&domain_context)
     })
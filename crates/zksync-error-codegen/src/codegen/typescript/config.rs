@@ -0,0 +1,17 @@
+use crate::codegen::IBackendConfig;
+
+pub struct Config {
+    pub package_name: String,
+    pub package_version: String,
+}
+
+impl IBackendConfig for Config {}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            package_name: "zksync-error".into(),
+            package_version: "0.1.0".into(),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use zksync_error_model::inner::ComponentDescription;
+use zksync_error_model::inner::ErrorDescription;
+use zksync_error_model::inner::ErrorDocumentation;
+use zksync_error_model::inner::FieldDescription;
+use zksync_error_model::inner::Lifecycle;
+
+use crate::codegen::typescript::error::GenerationError;
+use crate::codegen::typescript::TypescriptBackend;
+use crate::codegen::Backend as _;
+use crate::codegen::File;
+
+fn error_documentation(error: &ErrorDescription) -> String {
+    let documentation = match &error.documentation {
+        Some(ErrorDocumentation {
+            description,
+            summary,
+            ..
+        }) => format!("{}\n\n{description}", summary.clone().unwrap_or_default()),
+        None => String::new(),
+    };
+    match &error.lifecycle {
+        Lifecycle::Deprecated {
+            message,
+            replacement,
+        } => {
+            let replacement = replacement
+                .as_ref()
+                .map(|replacement| format!(" Use {replacement} instead."))
+                .unwrap_or_default();
+            format!("{documentation}\n\n@deprecated {message}{replacement}")
+        }
+        Lifecycle::Active | Lifecycle::Retired => documentation,
+    }
+}
+
+/// Union of `members`, or `never` if there are none, to be placed right after
+/// the `=` of a type alias.
+fn union(members: &[String]) -> String {
+    if members.is_empty() {
+        " never".into()
+    } else {
+        members
+            .iter()
+            .map(|member| format!("\n  | {member}"))
+            .collect()
+    }
+}
+
+impl TypescriptBackend {
+    fn error_interface(&self, error: &ErrorDescription) -> Result<String, GenerationError> {
+        let mut fields = String::new();
+        for FieldDescription {
+            name,
+            r#type,
+            description,
+            optional,
+            ..
+        } in &error.fields
+        {
+            let typ = self.typescript_type(&self.model.resolve_type(r#type)?)?;
+            let optional = if *optional { "?" } else { "" };
+            fields += &Self::doc_comment(description, 6);
+            writeln!(fields, "      readonly {name}{optional}: {typ};").unwrap();
+        }
+        let fields = if fields.is_empty() {
+            "Record<string, never>".to_owned()
+        } else {
+            format!("{{\n{fields}    }}")
+        };
+
+        Ok(format!(
+            r#"{doc}  export interface {name} {{
+    readonly domain: {domain};
+    readonly component: {component};
+    readonly name: {error_name};
+    readonly fields: {fields};
+  }}
+"#,
+            doc = Self::doc_comment(&error_documentation(error), 2),
+            name = Self::error_type_name(error)?,
+            domain = Self::quoted(&Self::domain_type_name(&error.domain)?),
+            component = Self::quoted(&Self::component_type_name(&error.component)?),
+            error_name = Self::quoted(&Self::error_type_name(error)?),
+        ))
+    }
+
+    /// Namespace holding the interfaces of the errors of a component, and
+    /// the union of these interfaces.
+    fn component_definitions(
+        &self,
+        component: &ComponentDescription,
+    ) -> Result<String, GenerationError> {
+        let namespace = Self::component_type_name(&component.meta)?;
+        let doc = Self::doc_comment(
+            &format!(
+                "{}\n\nDomain: {}",
+                component.meta.description, component.meta.domain.name
+            ),
+            0,
+        );
+
+        let mut interfaces = Vec::new();
+        let mut members = Vec::new();
+        for error in component.emitted_errors() {
+            interfaces.push(self.error_interface(error)?);
+            members.push(format!("{namespace}.{}", Self::error_type_name(error)?));
+        }
+
+        Ok(format!(
+            "{doc}export namespace {namespace} {{\n{interfaces}}}\n\nexport type {namespace}Error ={union};\n",
+            interfaces = interfaces.join("\n"),
+            union = union(&members),
+        ))
+    }
+
+    fn type_imports(&self) -> String {
+        let imports: BTreeSet<_> = self
+            .model
+            .types
+            .values()
+            .filter_map(|t| t.bindings.get(Self::get_language_name()))
+            .filter(|t| !t.path.is_empty())
+            .map(|t| {
+                format!(
+                    "import type {{ {} }} from {};\n",
+                    t.name,
+                    Self::quoted(&t.path)
+                )
+            })
+            .collect();
+        if imports.is_empty() {
+            String::new()
+        } else {
+            imports.into_iter().collect::<String>() + "\n"
+        }
+    }
+
+    pub fn generate_file_error(&mut self) -> Result<File, GenerationError> {
+        let mut definitions = Vec::new();
+        let mut domains = Vec::new();
+        for domain in self.model.domains.values() {
+            let domain_name = Self::domain_type_name(&domain.meta)?;
+            let mut components = Vec::new();
+            for component in domain.components.values() {
+                definitions.push(self.component_definitions(component)?);
+                components.push(format!(
+                    "{}Error",
+                    Self::component_type_name(&component.meta)?
+                ));
+            }
+            definitions.push(format!(
+                "{}export type {domain_name}Error ={};\n",
+                Self::doc_comment(&domain.meta.description, 0),
+                union(&components)
+            ));
+            domains.push(format!("{domain_name}Error"));
+        }
+
+        let content = format!(
+            r#"{imports}{definitions}
+/**
+ * Any error observable in ZKsync components. Errors are told apart by their
+ * `domain`, `component` and `name`.
+ */
+export type ZksyncError ={union};
+"#,
+            imports = self.type_imports(),
+            definitions = definitions.join("\n"),
+            union = union(&domains),
+        );
+
+        Ok(File {
+            content: Self::with_preamble(&content),
+            relative_path: PathBuf::from("src/error.ts"),
+        })
+    }
+}
@@ -0,0 +1,167 @@
+use std::path::PathBuf;
+
+use zksync_error_model::inner::CodeLayout;
+
+use crate::codegen::typescript::error::GenerationError;
+use crate::codegen::typescript::TypescriptBackend;
+use crate::codegen::File;
+
+impl TypescriptBackend {
+    /// Codes and names of all domains, components and errors, laid out as
+    /// the `DomainMetadata` array of `identifier.ts`.
+    fn metadata_table(&self) -> Result<serde_json::Value, GenerationError> {
+        let mut domains = Vec::new();
+        for domain in self.model.domains.values() {
+            let mut components = Vec::new();
+            for component in domain.components.values() {
+                let mut errors = Vec::new();
+                for error in component.emitted_errors() {
+                    errors.push(serde_json::json!({
+                        "name": Self::error_type_name(error)?,
+                        "rustName": Self::error_rust_name(error)?,
+                        "code": error.code,
+                        "identifier": error.get_identifier().to_string(),
+                        "fields": error.fields.iter().map(|f| &f.name).collect::<Vec<_>>(),
+                    }));
+                }
+                components.push(serde_json::json!({
+                    "name": Self::component_type_name(&component.meta)?,
+                    "rustName": Self::component_rust_name(&component.meta)?,
+                    "code": component.meta.code,
+                    "identifier": component.meta.identifier,
+                    "errors": errors,
+                }));
+            }
+            domains.push(serde_json::json!({
+                "name": Self::domain_type_name(&domain.meta)?,
+                "rustName": Self::domain_rust_name(&domain.meta)?,
+                "code": domain.meta.code,
+                "identifier": domain.meta.identifier,
+                "components": components,
+            }));
+        }
+        Ok(serde_json::Value::Array(domains))
+    }
+
+    pub fn generate_file_identifier(&mut self) -> Result<File, GenerationError> {
+        let layout @ CodeLayout {
+            unit,
+            domain,
+            component,
+            error,
+        } = self.model.code_layout;
+
+        let content = format!(
+            r#"import type {{ ZksyncError }} from "./error";
+
+/** Numeric codes use {layout}. */
+export const CODE_LAYOUT = {{
+  unit: "{unit}",
+  domain: {domain},
+  component: {component},
+  error: {error},
+}} as const;
+
+export const DOMAIN_CAPACITY = {domain_capacity};
+export const COMPONENT_CAPACITY = {component_capacity};
+export const ERROR_CAPACITY = {error_capacity};
+const DOMAIN_MULTIPLIER = {domain_multiplier};
+const COMPONENT_MULTIPLIER = {component_multiplier};
+
+export interface StructuredErrorCode {{
+  readonly domainCode: number;
+  readonly componentCode: number;
+  readonly errorCode: number;
+}}
+
+export function encode(code: StructuredErrorCode): number {{
+  return (
+    code.domainCode * DOMAIN_MULTIPLIER +
+    code.componentCode * COMPONENT_MULTIPLIER +
+    code.errorCode
+  );
+}}
+
+export function decode(raw: number): StructuredErrorCode {{
+  return {{
+    domainCode: Math.floor(raw / DOMAIN_MULTIPLIER) % DOMAIN_CAPACITY,
+    componentCode: Math.floor(raw / COMPONENT_MULTIPLIER) % COMPONENT_CAPACITY,
+    errorCode: raw % ERROR_CAPACITY,
+  }};
+}}
+
+export interface ErrorMetadata {{
+  /** Name of the error in TypeScript. */
+  readonly name: string;
+  /** Name of the error in the serialized form produced by the Rust code. */
+  readonly rustName: string;
+  readonly code: number;
+  /** Identifier of the error, e.g. `[core-seq-1]`. */
+  readonly identifier: string;
+  readonly fields: readonly string[];
+}}
+
+export interface ComponentMetadata {{
+  readonly name: string;
+  readonly rustName: string;
+  readonly code: number;
+  readonly identifier: string;
+  readonly errors: readonly ErrorMetadata[];
+}}
+
+export interface DomainMetadata {{
+  readonly name: string;
+  readonly rustName: string;
+  readonly code: number;
+  readonly identifier: string;
+  readonly components: readonly ComponentMetadata[];
+}}
+
+export type ErrorLocation = readonly [DomainMetadata, ComponentMetadata, ErrorMetadata];
+
+export const DOMAINS: readonly DomainMetadata[] = {domains};
+
+export function findByCode(code: StructuredErrorCode): ErrorLocation | undefined {{
+  const domain = DOMAINS.find((d) => d.code === code.domainCode);
+  const component = domain?.components.find((c) => c.code === code.componentCode);
+  const error = component?.errors.find((e) => e.code === code.errorCode);
+  return domain && component && error ? [domain, component, error] : undefined;
+}}
+
+export function getMetadata(error: ZksyncError): ErrorLocation {{
+  const domain = DOMAINS.find((d) => d.name === error.domain);
+  const component = domain?.components.find((c) => c.name === error.component);
+  const metadata = component?.errors.find((e) => e.name === error.name);
+  if (!(domain && component && metadata)) {{
+    throw new Error(`Unknown error ${{error.domain}}.${{error.component}}.${{error.name}}`);
+  }}
+  return [domain, component, metadata];
+}}
+
+export function getCode(error: ZksyncError): number {{
+  const [domain, component, metadata] = getMetadata(error);
+  return encode({{
+    domainCode: domain.code,
+    componentCode: component.code,
+    errorCode: metadata.code,
+  }});
+}}
+
+export function getIdentifierRepr(error: ZksyncError): string {{
+  return getMetadata(error)[2].identifier;
+}}
+"#,
+            domain_capacity = layout.domain_capacity(),
+            component_capacity = layout.component_capacity(),
+            error_capacity = layout.error_capacity(),
+            domain_multiplier = layout.domain_multiplier(),
+            component_multiplier = layout.component_multiplier(),
+            domains = serde_json::to_string_pretty(&self.metadata_table()?)?,
+        );
+
+        Ok(File {
+            content: Self::with_preamble(&content),
+            relative_path: PathBuf::from("src/identifier.ts"),
+        })
+    }
+}
@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+
+use zksync_error_model::inner::template::FormatSpec;
+use zksync_error_model::inner::template::MessageSegment;
+use zksync_error_model::inner::template::Placeholder;
+use zksync_error_model::inner::ErrorDescription;
+
+use crate::codegen::typescript::error::GenerationError;
+use crate::codegen::typescript::TypescriptBackend;
+use crate::codegen::File;
+
+/// Object literal matching the `FormatSpec` interface of `format.ts`.
+fn format_spec(spec: &FormatSpec) -> serde_json::Value {
+    let mut result = serde_json::Map::new();
+    let FormatSpec {
+        fill,
+        align,
+        sign,
+        alternate,
+        zero,
+        width,
+        precision,
+        kind,
+    } = spec;
+    if let Some(fill) = fill {
+        result.insert("fill".into(), fill.to_string().into());
+    }
+    if let Some(align) = align {
+        result.insert("align".into(), align.as_char().to_string().into());
+    }
+    if let Some(sign) = sign {
+        result.insert("sign".into(), sign.to_string().into());
+    }
+    if *alternate {
+        result.insert("alternate".into(), true.into());
+    }
+    if *zero {
+        result.insert("zero".into(), true.into());
+    }
+    if let Some(width) = width {
+        result.insert("width".into(), (*width).into());
+    }
+    if let Some(precision) = precision {
+        result.insert("precision".into(), (*precision).into());
+    }
+    if !kind.is_empty() {
+        result.insert("kind".into(), kind.as_str().into());
+    }
+    serde_json::Value::Object(result)
+}
+
+/// Expression rendering the message of `error`, whose fields are accessible
+/// through the narrowed variable `error`.
+fn message_expression(error: &ErrorDescription) -> String {
+    let mut parts = Vec::new();
+    let mut literal = format!("{} ", error.get_identifier());
+    for segment in &error.message.segments {
+        match segment {
+            MessageSegment::Literal(text) => literal.push_str(text),
+            MessageSegment::Placeholder(Placeholder { field, spec }) => {
+                if !literal.is_empty() {
+                    parts.push(TypescriptBackend::quoted(&std::mem::take(&mut literal)));
+                }
+                parts.push(match spec {
+                    None => format!("formatValue(error.fields.{field})"),
+                    Some(spec) => {
+                        format!("formatValue(error.fields.{field}, {})", format_spec(spec))
+                    }
+                });
+            }
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(TypescriptBackend::quoted(&literal));
+    }
+    parts.join(" + ")
+}
+
+impl TypescriptBackend {
+    pub fn generate_file_message(&mut self) -> Result<File, GenerationError> {
+        let mut domain_cases = String::new();
+        for domain in self.model.domains.values() {
+            let mut component_cases = String::new();
+            for component in domain.components.values() {
+                let mut error_cases = String::new();
+                for error in component.emitted_errors() {
+                    error_cases += &format!(
+                        "            case {}:\n              return {};\n",
+                        Self::quoted(&Self::error_type_name(error)?),
+                        message_expression(error),
+                    );
+                }
+                // Components without errors are absent from the union, and
+                // can not be matched.
+                if !error_cases.is_empty() {
+                    component_cases += &format!(
+                        "        case {}:\n          switch (error.name) {{\n{error_cases}          }}\n          break;\n",
+                        Self::quoted(&Self::component_type_name(&component.meta)?),
+                    );
+                }
+            }
+            if !component_cases.is_empty() {
+                domain_cases += &format!(
+                    "    case {}:\n      switch (error.component) {{\n{component_cases}      }}\n      break;\n",
+                    Self::quoted(&Self::domain_type_name(&domain.meta)?),
+                );
+            }
+        }
+
+        let content = format!(
+            r#"import type {{ ZksyncError }} from "./error";
+import {{ formatValue }} from "./format";
+
+/**
+ * Render the message of an error from its template, prefixed by the
+ * identifier of the error.
+ */
+export function getMessage(error: ZksyncError): string {{
+  switch (error.domain) {{
+{domain_cases}  }}
+  throw new Error(`Unknown error ${{JSON.stringify(error)}}`);
+}}
+"#
+        );
+
+        Ok(File {
+            content: Self::with_preamble(&content),
+            relative_path: PathBuf::from("src/message.ts"),
+        })
+    }
+}
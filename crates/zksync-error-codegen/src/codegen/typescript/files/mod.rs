@@ -0,0 +1,4 @@
+pub mod error;
+pub mod identifier;
+pub mod message;
+pub mod package;
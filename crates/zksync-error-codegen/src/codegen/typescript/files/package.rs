@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use crate::codegen::typescript::error::GenerationError;
+use crate::codegen::typescript::TypescriptBackend;
+use crate::codegen::typescript::TypescriptBackendConfig;
+use crate::codegen::File;
+
+impl TypescriptBackend {
+    pub fn generate_file_package(
+        &mut self,
+        config: &TypescriptBackendConfig,
+    ) -> Result<File, GenerationError> {
+        let package = serde_json::json!({
+            "name": config.package_name,
+            "version": config.package_version,
+            "description": "Errors observable in ZKsync components.",
+            "main": "dist/index.js",
+            "types": "dist/index.d.ts",
+            "files": ["dist"],
+            "scripts": {
+                "build": "tsc"
+            },
+            "devDependencies": {
+                "typescript": "^5.0.0"
+            }
+        });
+
+        Ok(File {
+            content: serde_json::to_string_pretty(&package)? + "\n",
+            relative_path: PathBuf::from("package.json"),
+        })
+    }
+}
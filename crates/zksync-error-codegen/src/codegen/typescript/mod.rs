@@ -0,0 +1,339 @@
+pub mod config;
+pub mod error;
+pub mod files;
+
+pub use config::Config as TypescriptBackendConfig;
+use error::GenerationError;
+use include_dir::include_dir;
+use include_dir::Dir;
+use zksync_error_model::error::ModelValidationError;
+use zksync_error_model::inner::ComponentMetadata;
+use zksync_error_model::inner::DomainMetadata;
+use zksync_error_model::inner::ErrorDescription;
+use zksync_error_model::inner::FullyQualifiedTargetLanguageType;
+use zksync_error_model::inner::Model;
+use zksync_error_model::inner::TypeExpression;
+
+use super::Backend;
+use super::File;
+
+/// Parts of the generated package that do not depend on the model.
+static TEMPLATES_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/code_templates/typescript");
+
+const PREAMBLE: &str = r#"//
+// AUTOGENERATED BASED ON A SET OF JSON FILES, DO NOT EDIT MANUALLY
+//
+"#;
+
+pub struct TypescriptBackend {
+    model: Model,
+}
+
+impl Backend<TypescriptBackendConfig> for TypescriptBackend {
+    type Error = GenerationError;
+
+    fn get_name() -> &'static str {
+        "typescript"
+    }
+
+    fn get_language_name() -> &'static str {
+        "typescript"
+    }
+
+    fn generate(&mut self, config: &TypescriptBackendConfig) -> Result<Vec<File>, Self::Error> {
+        Ok(vec![
+            self.generate_file_package(config)?,
+            File::copy_as_is(&TEMPLATES_DIR, "tsconfig.json"),
+            self.generate_file_error()?,
+            self.generate_file_identifier()?,
+            self.generate_file_message()?,
+            File::copy_as_is(&TEMPLATES_DIR, "src/format.ts"),
+            File::copy_as_is(&TEMPLATES_DIR, "src/serialized.ts"),
+            File::copy_as_is(&TEMPLATES_DIR, "src/index.ts"),
+        ])
+    }
+}
+
+impl TypescriptBackend {
+    pub fn new(model: &Model) -> Self {
+        Self {
+            model: model.clone(),
+        }
+    }
+
+    fn with_preamble(contents: &str) -> String {
+        format!("{PREAMBLE}\n{contents}")
+    }
+
+    /// String literal in TypeScript syntax.
+    fn quoted(s: &str) -> String {
+        serde_json::Value::from(s).to_string()
+    }
+
+    /// JSDoc comment, indented by `indent` spaces.
+    fn doc_comment(text: &str, indent: usize) -> String {
+        let text = text.trim();
+        if text.is_empty() {
+            return String::new();
+        }
+        let indent = " ".repeat(indent);
+        let lines: String = text
+            .replace("*/", "*\\/")
+            .lines()
+            .map(|line| format!("{indent} * {line}").trim_end().to_owned() + "\n")
+            .collect();
+        format!("{indent}/**\n{lines}{indent} */\n")
+    }
+
+    fn get_typescript_type(
+        &self,
+        name: &str,
+    ) -> Result<&FullyQualifiedTargetLanguageType, GenerationError> {
+        Ok(self.model.get_type(Self::get_language_name(), name)?)
+    }
+
+    /// Translate a field type into a TypeScript type. Absent optional values
+    /// are `null`, maps are records, and byte strings are hex strings.
+    fn typescript_type(&self, typ: &TypeExpression) -> Result<String, GenerationError> {
+        Ok(match typ {
+            TypeExpression::Named(name) => self.get_typescript_type(name)?.name.clone(),
+            TypeExpression::List(item) => match item.as_ref() {
+                TypeExpression::Optional(_) => format!("({})[]", self.typescript_type(item)?),
+                _ => format!("{}[]", self.typescript_type(item)?),
+            },
+            TypeExpression::Optional(inner) => format!("{} | null", self.typescript_type(inner)?),
+            TypeExpression::Map(key, value) => format!(
+                "Record<{}, {}>",
+                self.typescript_type(key)?,
+                self.typescript_type(value)?
+            ),
+            TypeExpression::ByteArray(_) | TypeExpression::Bytes => "string".to_owned(),
+        })
+    }
+
+    fn binding<'a>(
+        bindings: &'a std::collections::BTreeMap<String, String>,
+        language: &str,
+        name: &str,
+    ) -> Result<&'a str, GenerationError> {
+        bindings
+            .get(language)
+            .map(String::as_str)
+            .ok_or(ModelValidationError::UnmappedName(name.to_owned()).into())
+    }
+
+    fn domain_type_name(domain: &DomainMetadata) -> Result<String, GenerationError> {
+        Self::binding(&domain.bindings, Self::get_language_name(), &domain.name).map(sanitize)
+    }
+
+    fn domain_rust_name(domain: &DomainMetadata) -> Result<&str, GenerationError> {
+        Self::binding(&domain.bindings, "rust", &domain.name)
+    }
+
+    fn component_type_name(component: &ComponentMetadata) -> Result<String, GenerationError> {
+        Self::binding(
+            &component.bindings,
+            Self::get_language_name(),
+            &component.name,
+        )
+        .map(sanitize)
+    }
+
+    fn component_rust_name(component: &ComponentMetadata) -> Result<&str, GenerationError> {
+        Self::binding(&component.bindings, "rust", &component.name)
+    }
+
+    fn error_type_name(error: &ErrorDescription) -> Result<String, GenerationError> {
+        let name = error
+            .bindings
+            .get(Self::get_language_name())
+            .ok_or(ModelValidationError::UnmappedName(error.name.clone()))?;
+        Ok(sanitize(&name.name))
+    }
+
+    fn error_rust_name(error: &ErrorDescription) -> Result<&str, GenerationError> {
+        let name = error
+            .bindings
+            .get("rust")
+            .ok_or(ModelValidationError::UnmappedName(error.name.clone()))?;
+        Ok(&name.name)
+    }
+}
+
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '$' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::rust::RustBackend;
+    use crate::testing::model_of;
+    use crate::testing::ROOT;
+
+    use super::TypescriptBackend;
+
+    const ERRORS: &str = r#"
+[[domains.components.errors]]
+name = "Failure"
+code = 5
+message = "Bad {index:>4} in {path:?}."
+bindings.typescript = { name = "BatchFailure" }
+fields = [
+  { name = "index", type = "uint" },
+  { name = "path", type = "string" },
+]
+
+[[domains.components.errors]]
+name = "Halted"
+code = 1
+message = "Halted."
+"#;
+
+    /// Value of the constant `name`, declared as `{name} = {value};` in TypeScript
+    /// or `{name}: u32 = {value};` in Rust.
+    fn constant(content: &str, name: &str) -> u64 {
+        let (_, rest) = content
+            .split_once(&format!("{name} = "))
+            .or_else(|| content.split_once(&format!("{name}: u32 = ")))
+            .unwrap_or_else(|| panic!("No constant `{name}` in:\n{content}"));
+        rest[..rest.find(';').unwrap()].parse().unwrap()
+    }
+
+    /// The `DOMAINS` table of `identifier.ts`.
+    fn domains(identifier: &str) -> serde_json::Value {
+        let (_, rest) = identifier
+            .split_once("export const DOMAINS: readonly DomainMetadata[] = ")
+            .unwrap();
+        serde_json::from_str(&rest[..rest.find("];").unwrap() + 1]).unwrap()
+    }
+
+    #[test]
+    fn identifiers_are_encoded_as_in_rust() {
+        for layout in [
+            "",
+            "code_layout = { unit = \"bits\", domain = 4, component = 6, error = 10 }",
+        ] {
+            let model = model_of(&format!("{layout}\n{ROOT}\n{ERRORS}"));
+            let typescript = TypescriptBackend::new(&model)
+                .generate_file_identifier()
+                .unwrap()
+                .content;
+            let rust = RustBackend::new(&model)
+                .generate_file_identifier()
+                .unwrap()
+                .content;
+
+            let names = [
+                "DOMAIN_CAPACITY",
+                "COMPONENT_CAPACITY",
+                "ERROR_CAPACITY",
+                "DOMAIN_MULTIPLIER",
+                "COMPONENT_MULTIPLIER",
+            ];
+            for name in names {
+                assert_eq!(constant(&typescript, name), constant(&rust, name), "{name}");
+            }
+            let [domain_capacity, component_capacity, error_capacity, domain_multiplier, component_multiplier] =
+                names.map(|name| constant(&typescript, name));
+
+            for expected in [
+                "code.domainCode * DOMAIN_MULTIPLIER +\n    code.componentCode * COMPONENT_MULTIPLIER +\n    code.errorCode",
+                "domainCode: Math.floor(raw / DOMAIN_MULTIPLIER) % DOMAIN_CAPACITY,",
+                "componentCode: Math.floor(raw / COMPONENT_MULTIPLIER) % COMPONENT_CAPACITY,",
+                "errorCode: raw % ERROR_CAPACITY,",
+            ] {
+                assert!(typescript.contains(expected), "`{expected}` in:\n{typescript}");
+            }
+
+            // The codes of the table, encoded and decoded as `encode` and
+            // `decode` do, agree with the layout used by the Rust code.
+            let layout = model.code_layout;
+            for domain in domains(&typescript).as_array().unwrap() {
+                for component in domain["components"].as_array().unwrap() {
+                    for error in component["errors"].as_array().unwrap() {
+                        let codes = [&domain["code"], &component["code"], &error["code"]]
+                            .map(|code| code.as_u64().unwrap());
+                        let [d, c, e] = codes;
+                        let raw = d * domain_multiplier + c * component_multiplier + e;
+                        assert_eq!(raw, u64::from(layout.encode(d as u32, c as u32, e as u32)));
+                        let decoded = [
+                            (raw / domain_multiplier) % domain_capacity,
+                            (raw / component_multiplier) % component_capacity,
+                            raw % error_capacity,
+                        ];
+                        assert_eq!(decoded, codes);
+                        assert_eq!(error["identifier"], format!("[core-seq-{e}]"));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn messages_are_rendered_from_templates() {
+        let model = model_of(&format!("{ROOT}\n{ERRORS}"));
+        let content = TypescriptBackend::new(&model)
+            .generate_file_message()
+            .unwrap()
+            .content;
+        for expected in [
+            "case \"BatchFailure\":\n              return \"[core-seq-5] Bad \" + formatValue(error.fields.index, {\"align\":\">\",\"width\":4}) + \" in \" + formatValue(error.fields.path, {\"kind\":\"?\"}) + \".\";",
+            "case \"Halted\":\n              return \"[core-seq-1] Halted.\";",
+        ] {
+            assert!(content.contains(expected), "`{expected}` in:\n{content}");
+        }
+    }
+
+    #[test]
+    fn errors_are_deserialized_by_their_rust_names() {
+        let model = model_of(&format!("{ROOT}\n{ERRORS}"));
+        let identifier = TypescriptBackend::new(&model)
+            .generate_file_identifier()
+            .unwrap()
+            .content;
+        let definitions = RustBackend::new(&model)
+            .generate_file_error_definitions(&crate::codegen::rust::config::Config {
+                use_anyhow: false,
+            })
+            .unwrap()
+            .content;
+
+        // `deserialize` unwraps `{ "Core": { "Sequencer": { "Failure": { ... } } } }`,
+        // the nested enums of the Rust code, by the Rust names of the table.
+        let domains = domains(&identifier);
+        let domain = &domains[0];
+        let component = &domain["components"][0];
+        assert_eq!(
+            (&domain["name"], &domain["rustName"]),
+            (&"Core".into(), &"Core".into())
+        );
+        assert_eq!(component["rustName"], "Sequencer");
+        assert!(
+            definitions.contains("pub enum Sequencer {"),
+            "{definitions}"
+        );
+
+        let errors = component["errors"].as_array().unwrap();
+        let failure = errors.iter().find(|e| e["code"] == 5).unwrap();
+        assert_eq!(failure["name"], "BatchFailure");
+        assert_eq!(failure["rustName"], "Failure");
+        assert_eq!(failure["fields"], serde_json::json!(["index", "path"]));
+        assert!(
+            definitions.contains("Failure { index: u32, path: String } = 5"),
+            "{definitions}"
+        );
+
+        // Errors without fields are unit variants, serialized as their names.
+        let halted = errors.iter().find(|e| e["code"] == 1).unwrap();
+        assert_eq!(halted["fields"], serde_json::json!([]));
+        assert!(definitions.contains("Halted = 1u32"), "{definitions}");
+    }
+}
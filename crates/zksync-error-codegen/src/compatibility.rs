@@ -0,0 +1,102 @@
+//!
+//! Comparison of two versions of the error model.
+//!
+
+use std::path::Path;
+
+use zksync_error_model::compatibility::compare;
+use zksync_error_model::compatibility::CompatibilityReport;
+use zksync_error_model::inner::Model;
+use zksync_error_model::merger::MergeMode;
+
+use crate::arguments::CompatibilityArguments;
+use crate::arguments::ModelSource;
+use crate::error::ProgramError;
+use crate::loader::builder::build_model;
+use crate::loader::builder::ModelBuildingOptions;
+use crate::loader::cargo::CargoConfig;
+use crate::loader::fetcher::HttpFetcher;
+use crate::loader::link::Link;
+use crate::loader::network::NetworkConfig;
+use crate::loader::revision::RevisionCheckout;
+use crate::loader::schemes::SchemeRegistry;
+
+/// Take the links relative to the current working directory relative to
+/// `base` instead.
+fn rebase(link: Link, base: &Path) -> Link {
+    match link {
+        Link::FileLink { path, sha256 } if Path::new(&path).is_relative() => Link::FileLink {
+            path: base.join(path).to_string_lossy().into_owned(),
+            sha256,
+        },
+        other => other,
+    }
+}
+
+fn load(
+    source: &ModelSource,
+    network: &NetworkConfig,
+    cargo: &CargoConfig,
+    merge_mode: MergeMode,
+) -> Result<Model, ProgramError> {
+    let ModelSource {
+        root_link,
+        input_links,
+        revision,
+    } = source;
+
+    let checkout = match revision {
+        Some(revision) => {
+            log::info!("Reading the definitions at revision `{revision}`");
+            Some(RevisionCheckout::new(revision)?)
+        }
+        None => None,
+    };
+    // Files and cargo packages of the revision are found relative to the
+    // directory of the checkout that stands for the current one.
+    let base = checkout.as_ref().map(|c| c.working_directory.as_path());
+    let parse = |link: &str| -> Result<Link, ProgramError> {
+        let link = Link::parse(link)?;
+        Ok(match base {
+            Some(base) => rebase(link, base),
+            None => link,
+        })
+    };
+    let cargo = CargoConfig {
+        directory: base
+            .map(Path::to_path_buf)
+            .or_else(|| cargo.directory.clone()),
+        ..cargo.clone()
+    };
+
+    log::info!("Reading config from \"{root_link}\"");
+    let additions: Result<Vec<_>, _> = input_links.iter().map(|link| parse(link)).collect();
+    let options = ModelBuildingOptions {
+        additions: additions?,
+        network: network.clone(),
+        cargo,
+        merge_mode,
+        ..Default::default()
+    };
+    Ok(build_model(
+        &parse(root_link)?,
+        &options,
+        &SchemeRegistry::with_builtins(HttpFetcher::default()),
+    )?)
+}
+
+/// Build both versions of the model and list the differences between them.
+pub fn check_compatibility(
+    arguments: &CompatibilityArguments,
+) -> Result<CompatibilityReport, ProgramError> {
+    let CompatibilityArguments {
+        network,
+        cargo,
+        merge_mode,
+        baseline,
+        current,
+    } = arguments;
+    let baseline = load(baseline, network, cargo, *merge_mode)?;
+    let current = load(current, network, cargo, *merge_mode)?;
+    Ok(compare(&baseline, &current))
+}
@@ -1,67 +1,116 @@
 //!
-//! Layout of the JSON file that holds a fragment of error hierarchy.
+//! Layout of the JSON or TOML file that holds a fragment of error hierarchy.
 //!
 
 #![allow(non_snake_case)]
 
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
 use serde::Deserialize;
 
-#[derive(Clone, Debug, Deserialize)]
+/// JSON pointer to a definition in its file. It is not part of the file; the
+/// loader sets it after parsing, see [`crate::loader::pointer`].
+pub type Pointer = String;
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct Root {
+    /// Links to other files whose types and domains are merged into this one.
+    #[serde(default)]
+    pub include: Vec<String>,
     #[serde(default)]
     pub types: Vec<Type>,
+    #[serde(default)]
     pub domains: Vec<Domain>,
+    #[serde(default)]
+    pub code_layout: Option<CodeLayout>,
+    /// Levels of the lint rules, e.g. `missing-doc = "deny"`.
+    #[serde(default)]
+    pub lints: BTreeMap<String, LintLevel>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeLayoutUnit {
+    Digits,
+    Bits,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+/// Widths of the domain, component and error parts of the numeric error code.
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct CodeLayout {
+    pub unit: CodeLayoutUnit,
+    pub domain: u32,
+    pub component: u32,
+    pub error: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct Type {
     pub name: String,
     pub description: String,
     pub bindings: TypeMappings,
+    #[serde(skip)]
+    pub pointer: Pointer,
 }
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema)]
 pub struct ErrorNameMapping {
     pub rust: Option<ErrorType>,
     pub typescript: Option<ErrorType>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct TypeMappings {
     pub rust: Option<FullyQualifiedType>,
     #[serde(default)]
     pub typescript: Option<FullyQualifiedType>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct ErrorType {
     pub name: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct FullyQualifiedType {
     pub name: String,
     pub path: String,
 }
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema)]
 pub struct NameBindings {
     pub rust: Option<String>,
     pub typescript: Option<String>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct Domain {
     pub domain_name: String,
     pub domain_code: u32,
     pub identifier_encoding: Option<String>,
     pub description: Option<String>,
+    #[serde(default)]
     pub components: Vec<Component>,
     #[serde(default)]
     pub bindings: NameBindings,
+    /// Links to files with the domain of the same name, whose components are
+    /// merged into this domain.
+    #[serde(default)]
+    pub takeFrom: Vec<String>,
+    #[serde(skip)]
+    pub pointer: Pointer,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct Component {
     pub component_name: String,
     pub component_code: u32,
@@ -76,12 +125,21 @@ pub struct Component {
 
     #[serde(default)]
     pub errors: Vec<Error>,
+    /// Ranges of codes that no error of this component may use.
+    #[serde(default)]
+    pub reserved: Vec<CodeRange>,
+    #[serde(skip)]
+    pub pointer: Pointer,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct Error {
     pub name: String,
-    pub code: u32,
+    /// Errors without a code get the code recorded for them in the registry,
+    /// or the next free code of their component.
+    #[serde(default)]
+    #[schemars(with = "u32")]
+    pub code: Option<u32>,
     pub message: String,
     #[serde(default)]
     pub fields: Vec<Field>,
@@ -90,15 +148,50 @@ pub struct Error {
     pub bindings: ErrorNameMapping,
     #[serde(default)]
     pub doc: Option<ErrorDocumentation>,
+    #[serde(default)]
+    pub deprecated: Option<Deprecation>,
+    /// Retired errors are not generated anymore; they are kept so that their
+    /// codes are never reused.
+    #[serde(default)]
+    pub retired: bool,
+    #[serde(skip)]
+    pub pointer: Pointer,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema)]
+pub struct Deprecation {
+    #[serde(default)]
+    pub message: String,
+    /// Identifier of the error to use instead, e.g. `[core-seq-2]`; brackets
+    /// are optional.
+    #[serde(default)]
+    pub replacement: Option<String>,
+}
+
+/// Inclusive range of error codes.
+#[derive(Clone, Copy, Debug, Deserialize, JsonSchema)]
+pub struct CodeRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct Field {
     pub name: String,
+    /// Declared type, or a type built with `list<T>`, `optional<T>`,
+    /// `map<K, V>`, `bytes<N>` or `bytes`.
     pub r#type: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Optional fields may be absent from the errors.
+    #[serde(default)]
+    pub optional: bool,
+    /// Value of the field in the serialized errors lacking it.
+    #[serde(default)]
+    pub default: Option<serde_json::Value>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct ErrorDocumentation {
     pub description: String,
     pub summary: Option<String>,
@@ -106,14 +199,14 @@ pub struct ErrorDocumentation {
     pub likely_causes: Vec<LikelyCause>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum LikelyCause {
     Simple(String),
     Structured(StructuredLikelyCause),
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct StructuredLikelyCause {
     pub cause: String,
     pub fixes: Vec<String>,
@@ -125,22 +218,105 @@ pub struct StructuredLikelyCause {
     pub references: Vec<String>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct VersionedOwner {
     pub name: String,
     #[serde(default)]
     pub version: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
-#[serde(untagged)]
+/// Documentation and bindings added to errors defined in other files.
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct Overlay {
+    pub overlay: Vec<ErrorOverlay>,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct ErrorOverlay {
+    /// Identifier of the patched error, e.g. `[core-seq-1]`; brackets are
+    /// optional.
+    pub error: String,
+    /// Bindings in the languages the error is not bound in yet.
+    #[serde(default)]
+    pub bindings: BTreeMap<String, ErrorType>,
+    #[serde(default)]
+    pub doc: Option<OverlayDocumentation>,
+}
+
+/// Documentation added to an error. The description and summary may only be
+/// provided if the error has none yet; likely causes are appended.
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema)]
+pub struct OverlayDocumentation {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub likely_causes: Vec<LikelyCause>,
+}
+
+/// Contents of a file. The loader recognizes the kind of the object by its
+/// top-level keys before parsing it.
+#[derive(Clone, Debug)]
 pub enum Collection {
-    Root(Root),
     Domain(Domain),
     Component(Component),
+    Overlay(Overlay),
+    Root(Root),
     Errors(Vec<Error>),
 }
 
+/// Flat layout of a master error database: domains, components and errors are
+/// listed side by side and refer to their parents by name. The loader rebuilds
+/// the [`Root`] tree from these references.
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct FlatRoot {
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub types: Vec<Type>,
+    pub domains: Vec<FlatDomain>,
+    #[serde(default)]
+    pub components: Vec<FlatComponent>,
+    #[serde(default)]
+    pub errors: Vec<FlatError>,
+    #[serde(default)]
+    pub code_layout: Option<CodeLayout>,
+    #[serde(default)]
+    pub lints: BTreeMap<String, LintLevel>,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct FlatDomain {
+    pub domain_name: String,
+    pub domain_code: u32,
+    pub identifier_encoding: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub bindings: NameBindings,
+    #[serde(default)]
+    pub takeFrom: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct FlatComponent {
+    pub domain: String,
+    #[serde(flatten)]
+    pub component: Component,
+}
+
+/// An error in the flat layout. If both `domain` and `component` are omitted,
+/// the error belongs to the same component as the error preceding it.
+#[derive(Clone, Debug, Deserialize, JsonSchema)]
+pub struct FlatError {
+    #[serde(default)]
+    pub domain: Option<String>,
+    #[serde(default)]
+    pub component: Option<String>,
+    #[serde(flatten)]
+    pub error: Error,
+}
+
 impl Root {
     pub fn get_component(&self, domain: &str, component: &str) -> Option<&Component> {
         let domain = self.domains.iter().find(|d| d.domain_name == domain)?;
@@ -162,6 +338,13 @@ impl Domain {
 }
 
 impl Collection {
+    pub fn get_domain(&self, domain_name: &str) -> Option<&Domain> {
+        match self {
+            Collection::Root(root) => root.domains.iter().find(|d| d.domain_name == domain_name),
+            Collection::Domain(domain) if domain.domain_name == domain_name => Some(domain),
+            _ => None,
+        }
+    }
     pub fn get_component(&self, domain: &str, component_name: &str) -> Option<&Component> {
         match self {
             Collection::Root(root) => root.get_component(domain, component_name),
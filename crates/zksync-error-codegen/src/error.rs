@@ -1,8 +1,10 @@
 use crate::codegen::mdbook::error::GenerationError as MarkdownGenerationError;
 use crate::codegen::rust::error::GenerationError as RustGenerationError;
+use crate::codegen::typescript::error::GenerationError as TypescriptGenerationError;
 use crate::loader::builder::error::ModelBuildingError;
-use crate::loader::error::{LinkError, LoadError};
+use crate::loader::error::{LinkError, LoadError, RevisionError};
 use zksync_error_model::error::ModelValidationError;
+use zksync_error_model::lint::LintReport;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ProgramError {
@@ -15,6 +17,8 @@ pub enum ProgramError {
     #[error(transparent)]
     RustGenerationError(#[from] RustGenerationError),
     #[error(transparent)]
+    TypescriptGenerationError(#[from] TypescriptGenerationError),
+    #[error(transparent)]
     MarkdownGenerationError(#[from] MarkdownGenerationError),
     #[error(transparent)]
     IOError(#[from] std::io::Error),
@@ -22,4 +26,8 @@ pub enum ProgramError {
     LoadError(#[from] LoadError),
     #[error(transparent)]
     LinkError(#[from] LinkError),
+    #[error(transparent)]
+    RevisionError(#[from] RevisionError),
+    #[error("The definitions violate denied lint rules.\n{0}")]
+    LintDenied(LintReport),
 }
@@ -1,8 +1,14 @@
 pub mod arguments;
 pub mod codegen;
+pub mod compatibility;
 pub mod description;
 pub mod error;
+pub mod lint;
 pub mod loader;
+pub mod schema;
+
+#[cfg(test)]
+pub(crate) mod testing;
 
 use std::io::Write as _;
 use std::path::Path;
@@ -12,21 +18,38 @@ use arguments::Backend;
 use arguments::GenerationArguments;
 use error::ProgramError;
 use loader::builder::build_model;
+use loader::builder::ModelBuildingOptions;
+use loader::cargo::CargoConfig;
+use loader::fetcher::HttpFetcher;
 use loader::link::Link;
+use loader::network::NetworkConfig;
+use loader::registry::RegistryConfig;
+use loader::schemes::SchemeRegistry;
+use zksync_error_model::lint::LintLevels;
+use zksync_error_model::merger::MergeMode;
 
 use crate::codegen::file::File;
 use crate::codegen::mdbook::config::MDBookBackendConfig;
 use crate::codegen::mdbook::MDBookBackend;
 use crate::codegen::rust::RustBackend;
 use crate::codegen::rust::RustBackendConfig;
+use crate::codegen::typescript::TypescriptBackend;
+use crate::codegen::typescript::TypescriptBackendConfig;
 use crate::codegen::Backend as _;
 
 pub fn default_load_and_generate(root_link: &str, input_links: Vec<&str>) {
     if let Err(e) = load_and_generate(GenerationArguments {
-        verbose: true,
         root_link: root_link.to_owned(),
         outputs: vec![("../zksync_error".into(), Backend::Rust, vec![])],
         input_links: input_links.into_iter().map(Into::into).collect(),
+        network: NetworkConfig::default(),
+        registry: RegistryConfig {
+            update: true,
+            ..Default::default()
+        },
+        cargo: CargoConfig::default(),
+        merge_mode: MergeMode::default(),
+        lints: LintLevels::default(),
     }) {
         eprintln!("{e:#?}")
     };
@@ -34,23 +57,47 @@ pub fn default_load_and_generate(root_link: &str, input_links: Vec<&str>) {
 
 pub fn load_and_generate(arguments: GenerationArguments) -> Result<(), ProgramError> {
     let GenerationArguments {
-        verbose,
         root_link,
         outputs,
         input_links,
+        network,
+        registry,
+        cargo,
+        merge_mode,
+        lints,
     } = &arguments;
-    if *verbose {
-        eprintln!("Reading config from \"{root_link}\"");
-    }
+    log::info!("Reading config from \"{root_link}\"");
 
     let additions: Result<Vec<_>, _> = input_links.iter().map(Link::parse).collect();
-    let model = build_model(&Link::parse(root_link)?, &additions?, *verbose)?;
+    let options = ModelBuildingOptions {
+        additions: additions?,
+        languages: outputs
+            .iter()
+            .filter_map(|(_, backend, _)| bindings_language(backend))
+            .map(str::to_owned)
+            .collect(),
+        network: network.clone(),
+        registry: registry.clone(),
+        cargo: cargo.clone(),
+        merge_mode: *merge_mode,
+    };
+    let model = build_model(
+        &Link::parse(root_link)?,
+        &options,
+        &SchemeRegistry::with_builtins(HttpFetcher::default()),
+    )?;
+
+    let report = lint::lint_model(&model, lints);
+    if report.is_denied() {
+        return Err(ProgramError::LintDenied(report));
+    }
+    for finding in &report.findings {
+        log::warn!("{finding}");
+    }
 
     for (output_directory, backend_type, backend_arguments) in outputs {
         let backend_arguments = vector_map::VecMap::from_iter(backend_arguments.iter().cloned());
-        if *verbose {
-            eprintln!("Selected backend: {backend_type:?}, \nGenerating files...");
-        }
+        log::info!("Selected backend: {backend_type:?}, \nGenerating files...");
 
         let result = match backend_type {
             arguments::Backend::Rust => {
@@ -64,28 +111,53 @@ pub fn load_and_generate(arguments: GenerationArguments) -> Result<(), ProgramEr
                     .unwrap(),
                 })?
             }
+            arguments::Backend::Typescript => {
+                let mut backend = TypescriptBackend::new(&model);
+                let defaults = TypescriptBackendConfig::default();
+                backend.generate(&TypescriptBackendConfig {
+                    package_name: backend_arguments
+                        .get(&String::from("package_name"))
+                        .cloned()
+                        .unwrap_or(defaults.package_name),
+                    package_version: backend_arguments
+                        .get(&String::from("package_version"))
+                        .cloned()
+                        .unwrap_or(defaults.package_version),
+                })?
+            }
             arguments::Backend::Mdbook => {
                 let mut backend = MDBookBackend::new(&model);
                 backend.generate(&MDBookBackendConfig)?
             }
         };
 
-        if *verbose {
-            eprintln!("Generation successful. Files: ");
-            for file in &result {
-                eprintln!("- {}", file.relative_path.to_str().unwrap());
-            }
-            eprintln!("Writing files to disk...");
+        log::info!("Generation successful. Files: ");
+        for file in &result {
+            log::info!("- {}", file.relative_path.display());
         }
+        log::info!("Writing files to disk...");
 
         create_files_in_result_directory(output_directory, result)?;
-        if *verbose {
-            eprintln!("Writing successful.");
-        }
+        log::info!("Writing successful.");
     }
     Ok(())
 }
 
+/// Write the JSON Schemas of the definition files to `output_directory`.
+pub fn write_schemas(output_directory: &PathBuf) -> Result<(), ProgramError> {
+    create_files_in_result_directory(output_directory, schema::schema_files()?)?;
+    Ok(())
+}
+
+/// Language whose type bindings are required by the backend, if any.
+fn bindings_language(backend: &Backend) -> Option<&'static str> {
+    match backend {
+        Backend::Rust => Some(RustBackend::get_language_name()),
+        Backend::Typescript => Some(TypescriptBackend::get_language_name()),
+        Backend::Mdbook => None,
+    }
+}
+
 fn create_files_in_result_directory(result_dir: &PathBuf, files: Vec<File>) -> std::io::Result<()> {
     let result_dir = Path::new(result_dir);
 
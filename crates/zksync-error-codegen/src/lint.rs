@@ -0,0 +1,50 @@
+//!
+//! Lint pass over the error model.
+//!
+
+use zksync_error_model::inner::Model;
+use zksync_error_model::lint::lint;
+use zksync_error_model::lint::LintLevels;
+use zksync_error_model::lint::LintReport;
+
+use crate::arguments::LintArguments;
+use crate::error::ProgramError;
+use crate::loader::builder::build_model;
+use crate::loader::builder::ModelBuildingOptions;
+use crate::loader::fetcher::HttpFetcher;
+use crate::loader::link::Link;
+use crate::loader::schemes::SchemeRegistry;
+
+/// Lint the model with the levels set in its files, overridden by `overrides`.
+pub fn lint_model(model: &Model, overrides: &LintLevels) -> LintReport {
+    let mut levels = model.lints.clone();
+    levels.override_with(overrides);
+    lint(model, &levels)
+}
+
+/// Build the model and report the findings of the lint rules.
+pub fn run_lints(arguments: &LintArguments) -> Result<LintReport, ProgramError> {
+    let LintArguments {
+        root_link,
+        input_links,
+        network,
+        cargo,
+        merge_mode,
+        lints,
+    } = arguments;
+    log::info!("Reading config from \"{root_link}\"");
+    let additions: Result<Vec<_>, _> = input_links.iter().map(Link::parse).collect();
+    let options = ModelBuildingOptions {
+        additions: additions?,
+        network: network.clone(),
+        cargo: cargo.clone(),
+        merge_mode: *merge_mode,
+        ..Default::default()
+    };
+    let model = build_model(
+        &Link::parse(root_link)?,
+        &options,
+        &SchemeRegistry::with_builtins(HttpFetcher::default()),
+    )?;
+    Ok(lint_model(&model, lints))
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use zksync_error_model::inner::CodeRange;
+use zksync_error_model::inner::ComponentMetadata;
+use zksync_error_model::inner::ErrorCode;
+
+use crate::loader::registry::Registry;
+
+/// Codes of the errors written without one. An error recorded in the registry
+/// keeps its code; a new error gets the code following every code known in its
+/// component, outside of the reserved ranges. The code 0 belongs to the
+/// generic error.
+pub(super) struct CodeAllocator {
+    reserved: Vec<CodeRange>,
+    recorded: BTreeMap<String, ErrorCode>,
+    taken: BTreeSet<ErrorCode>,
+}
+
+impl CodeAllocator {
+    pub(super) fn new(component: &ComponentMetadata, registry: &Registry) -> Self {
+        let recorded: BTreeMap<_, _> = registry
+            .recorded_codes(&component.domain.identifier, &component.identifier)
+            .map(|(code, name)| (name.to_owned(), code))
+            .collect();
+        let mut taken: BTreeSet<_> = recorded.values().copied().collect();
+        taken.insert(0);
+        Self {
+            reserved: component.reserved.clone(),
+            recorded,
+            taken,
+        }
+    }
+
+    pub(super) fn reserve(&mut self, ranges: &[CodeRange]) {
+        self.reserved.extend(ranges.iter().copied());
+    }
+
+    pub(super) fn take(&mut self, code: ErrorCode) {
+        self.taken.insert(code);
+    }
+
+    /// Code of the error `name`, or `None` if no code below `capacity` follows
+    /// the known ones.
+    pub(super) fn allocate(&mut self, name: &str, capacity: u64) -> Option<ErrorCode> {
+        if let Some(code) = self.recorded.get(name) {
+            return Some(*code);
+        }
+        let mut code = self
+            .taken
+            .last()
+            .map_or(Some(0), |last| last.checked_add(1))?;
+        while let Some(range) = self.reserved.iter().find(|range| range.contains(code)) {
+            code = range.end.checked_add(1)?;
+        }
+        if u64::from(code) >= capacity {
+            return None;
+        }
+        self.taken.insert(code);
+        Some(code)
+    }
+}
+
+/// Code allocators of the components of a domain. A component may be defined
+/// in several files, through `takeFrom` links and imported domains; all of
+/// them share the allocator of the component, which has taken the explicit
+/// codes found in every definition before any code is allocated.
+#[derive(Default)]
+pub(super) struct DomainCodes {
+    explicit: BTreeMap<String, BTreeSet<ErrorCode>>,
+    allocators: BTreeMap<String, CodeAllocator>,
+}
+
+impl DomainCodes {
+    pub(super) fn new(explicit: BTreeMap<String, BTreeSet<ErrorCode>>) -> Self {
+        Self {
+            explicit,
+            allocators: BTreeMap::new(),
+        }
+    }
+
+    /// Allocator of `component`, reserving its ranges.
+    pub(super) fn allocator(
+        &mut self,
+        component: &ComponentMetadata,
+        registry: &Registry,
+    ) -> &mut CodeAllocator {
+        let explicit = &self.explicit;
+        let allocator = self
+            .allocators
+            .entry(component.name.clone())
+            .or_insert_with(|| {
+                let mut allocator = CodeAllocator::new(component, registry);
+                for code in explicit.get(&component.name).into_iter().flatten() {
+                    allocator.take(*code);
+                }
+                allocator
+            });
+        allocator.reserve(&component.reserved);
+        allocator
+    }
+}
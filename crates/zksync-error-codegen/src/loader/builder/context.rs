@@ -1,21 +1,172 @@
 #![allow(unused)]
 
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::rc::Rc;
 
+use zksync_error_model::inner::CodeLayout;
 use zksync_error_model::inner::ComponentMetadata;
 use zksync_error_model::inner::DomainMetadata;
+use zksync_error_model::inner::Origin;
+use zksync_error_model::merger::MergeMode;
 
+use crate::description::Collection;
+use crate::loader::cargo::CargoWorkspace;
+use crate::loader::error::LoadError;
+use crate::loader::fetcher::Fetcher;
 use crate::loader::link::Link;
+use crate::loader::load_resolved;
+use crate::loader::network::RemoteFiles;
+use crate::loader::registry::Registry;
+use crate::loader::resolution::ResolvedLink;
+use crate::loader::CollectionFile;
 
-pub struct ModelTranslationContext {
-    pub origin: Link,
+use super::error::ModelBuildingError;
+
+/// Chain of files followed to reach the file being translated: the root or
+/// additional file passed by the user, then the targets of the nested
+/// `takeFrom` links.
+#[derive(Clone, Debug)]
+pub struct IncludeStack {
+    files: Vec<ResolvedLink>,
 }
+
+impl IncludeStack {
+    pub fn new(origin: ResolvedLink) -> Self {
+        Self {
+            files: vec![origin],
+        }
+    }
+
+    /// The file being translated. Relative links found in it are resolved
+    /// against its location.
+    pub fn origin(&self) -> &ResolvedLink {
+        self.files.last().expect("Include stack is never empty")
+    }
+
+    /// The file with the link to the file being translated; the origin itself
+    /// if it was passed by the user.
+    pub fn includer(&self) -> &ResolvedLink {
+        self.files.iter().rev().nth(1).unwrap_or(self.origin())
+    }
+
+    pub fn files(&self) -> &[ResolvedLink] {
+        &self.files
+    }
+
+    pub fn contains(&self, file: &ResolvedLink, fetcher: &dyn Fetcher) -> bool {
+        let key = Self::key(file, fetcher);
+        self.files.iter().any(|f| Self::key(f, fetcher) == key)
+    }
+
+    /// Stack with `file` on top. Fails if `file` is already on the stack,
+    /// because following it would never terminate.
+    pub fn including(
+        &self,
+        file: ResolvedLink,
+        fetcher: &dyn Fetcher,
+    ) -> Result<Self, ModelBuildingError> {
+        let is_cycle = self.contains(&file, fetcher);
+        let mut files = self.files.clone();
+        files.push(file);
+        if is_cycle {
+            return Err(ModelBuildingError::IncludeCycle {
+                include_stack: Self { files },
+            });
+        }
+        Ok(Self { files })
+    }
+
+    /// Local files are compared by their canonical paths given by `fetcher`,
+    /// so that different spellings of the same path are recognized.
+    fn key(file: &ResolvedLink, fetcher: &dyn Fetcher) -> String {
+        match file {
+            ResolvedLink::DescriptionFile(CollectionFile {
+                absolute_path: path,
+                ..
+            })
+            | ResolvedLink::LocalPath(path) => fetcher
+                .canonicalize_local(path)
+                .to_string_lossy()
+                .into_owned(),
+            ResolvedLink::Url(_) | ResolvedLink::Custom { .. } => file.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for IncludeStack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let files: Vec<_> = self.files.iter().map(|l| format!("`{l}`")).collect();
+        f.write_str(&files.join(" -> "))
+    }
+}
+
+pub struct ModelTranslationContext<'a> {
+    pub include_stack: IncludeStack,
+    /// The root definitions; origins of local files are relative to them.
+    pub root: &'a ResolvedLink,
+    pub remote: &'a RemoteFiles,
+    pub registry: &'a Registry,
+    pub workspace: &'a CargoWorkspace,
+    pub fetcher: &'a dyn Fetcher,
+    pub merge_mode: MergeMode,
+    /// Layout of the codes, bounding the codes given to errors without one.
+    pub code_layout: CodeLayout,
+    /// Files loaded so far, by their locations and pinned digests, so that
+    /// each file is fetched and parsed once.
+    pub loaded: &'a RefCell<BTreeMap<(String, Option<String>), Collection>>,
+}
+impl<'a> ModelTranslationContext<'a> {
+    /// Context for translating the file on top of `include_stack`.
+    pub fn including(&self, include_stack: IncludeStack) -> Self {
+        Self {
+            include_stack,
+            root: self.root,
+            remote: self.remote,
+            registry: self.registry,
+            workspace: self.workspace,
+            fetcher: self.fetcher,
+            merge_mode: self.merge_mode,
+            code_layout: self.code_layout,
+            loaded: self.loaded,
+        }
+    }
+
+    /// Name of the file being translated in the origins.
+    pub fn source_name(&self) -> String {
+        self.include_stack.origin().source_name(self.root)
+    }
+
+    /// Origin of the definition at `pointer` in the file being translated.
+    pub fn origin(&self, pointer: &str) -> Option<Origin> {
+        Some(Origin {
+            source: self.source_name(),
+            pointer: pointer.to_owned(),
+        })
+    }
+
+    /// Load the file on top of the include stack, which `link` points to.
+    pub fn load(&self, link: &Link) -> Result<Collection, LoadError> {
+        let key = (
+            IncludeStack::key(self.include_stack.origin(), self.fetcher),
+            link.sha256().map(str::to_owned),
+        );
+        if let Some(collection) = self.loaded.borrow().get(&key) {
+            return Ok(collection.clone());
+        }
+        let collection =
+            load_resolved(link, self.include_stack.origin(), self.remote, self.fetcher)?;
+        self.loaded.borrow_mut().insert(key, collection.clone());
+        Ok(collection)
+    }
+}
+
 pub(super) struct TypeTranslationContext<'a> {
     pub type_name: &'a str,
-    pub parent: &'a ModelTranslationContext,
+    pub parent: &'a ModelTranslationContext<'a>,
 }
 pub(super) struct DomainTranslationContext<'a> {
-    pub parent: &'a ModelTranslationContext,
+    pub parent: &'a ModelTranslationContext<'a>,
 }
 
 pub(super) struct ComponentTranslationContext<'a> {
@@ -27,6 +178,24 @@ impl ComponentTranslationContext<'_> {
     pub(super) fn get_domain(&self) -> String {
         self.domain.name.to_string()
     }
+    pub(super) fn get_include_stack(&self) -> &IncludeStack {
+        &self.parent.parent.include_stack
+    }
+    pub(super) fn get_remote(&self) -> &RemoteFiles {
+        self.parent.parent.remote
+    }
+    pub(super) fn get_registry(&self) -> &Registry {
+        self.parent.parent.registry
+    }
+    pub(super) fn get_workspace(&self) -> &CargoWorkspace {
+        self.parent.parent.workspace
+    }
+    pub(super) fn get_fetcher(&self) -> &dyn Fetcher {
+        self.parent.parent.fetcher
+    }
+    pub(super) fn get_origin(&self, pointer: &str) -> Option<Origin> {
+        self.parent.parent.origin(pointer)
+    }
 }
 
 pub(super) struct ErrorTranslationContext<'a> {
@@ -42,3 +211,54 @@ impl ErrorTranslationContext<'_> {
         self.parent.get_domain()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::loader::builder::error::ModelBuildingError;
+    use crate::loader::fetcher::FilesystemFetcher;
+    use crate::loader::resolution::ResolvedLink;
+
+    use super::IncludeStack;
+
+    #[test]
+    fn relative_links_follow_the_including_file() {
+        let url = ResolvedLink::Url("https://example.com/errors/root.json".into());
+        assert_eq!(
+            url.join("../core/a.json", &FilesystemFetcher)
+                .unwrap()
+                .to_string(),
+            "https://example.com/core/a.json"
+        );
+        let path = ResolvedLink::LocalPath("errors/root.json".into());
+        assert_eq!(
+            path.join("core/a.json", &FilesystemFetcher)
+                .unwrap()
+                .to_string(),
+            PathBuf::from("errors/core/a.json").display().to_string()
+        );
+    }
+
+    #[test]
+    fn cycles_are_detected() {
+        let a = ResolvedLink::Url("https://example.com/a.json".into());
+        let stack = IncludeStack::new(ResolvedLink::LocalPath("root.json".into()))
+            .including(a.clone(), &FilesystemFetcher)
+            .unwrap()
+            .including(
+                ResolvedLink::Url("https://example.com/b.json".into()),
+                &FilesystemFetcher,
+            )
+            .unwrap();
+        assert_eq!(stack.origin().to_string(), "https://example.com/b.json");
+
+        match stack.including(a, &FilesystemFetcher) {
+            Err(ModelBuildingError::IncludeCycle { include_stack }) => assert_eq!(
+                include_stack.to_string(),
+                "`root.json` -> `https://example.com/a.json` -> `https://example.com/b.json` -> `https://example.com/a.json`"
+            ),
+            other => panic!("Expected a cycle, got {other:?}"),
+        }
+    }
+}
@@ -2,7 +2,14 @@ use crate::loader::{
     error::{LinkError, LoadError},
     link::Link,
 };
-use zksync_error_model::{error::ModelValidationError, merger::error::MergeError};
+
+use super::context::IncludeStack;
+use zksync_error_model::{
+    error::{ModelValidationErrors, TemplateError},
+    identifier::ErrorIdentifier,
+    lint::Rule,
+    merger::error::MergeError,
+};
 
 #[derive(Debug, thiserror::Error)]
 #[error("Missing component {component_name} in the domain {domain_name}")]
@@ -11,6 +18,42 @@ pub struct MissingComponent {
     pub component_name: String,
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error("Missing domain {domain_name}")]
+pub struct MissingDomain {
+    pub domain_name: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OverlayError {
+    #[error("Overlay refers to an undefined error {identifier}.")]
+    UnknownError { identifier: String },
+    #[error("Overlay binds the error {identifier} to `{new}` in {language}, but it is already bound to `{existing}`.")]
+    ConflictingBinding {
+        identifier: String,
+        language: String,
+        existing: String,
+        new: String,
+    },
+    #[error("Overlay replaces the {field} of the error {identifier}; overlays may only add documentation.")]
+    ConflictingDocumentation {
+        identifier: String,
+        field: &'static str,
+    },
+    #[error("Overlay adds an invalid likely cause to the error {identifier}: {inner}")]
+    InvalidLikelyCause {
+        identifier: String,
+        #[source]
+        inner: Box<ModelBuildingError>,
+    },
+    #[error("Overlay adds a conflicting likely cause to the error {identifier}: {inner}")]
+    ConflictingLikelyCause {
+        identifier: String,
+        #[source]
+        inner: Box<MergeError>,
+    },
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum TakeFromError {
     #[error("Error while building model following a `takeFrom` link: {0}")]
@@ -23,10 +66,10 @@ pub enum TakeFromError {
     MissingComponent(#[from] MissingComponent),
 
     #[error("Error while building model following a `takeFrom` link: {0}")]
-    ModelBuildingError(/* from */ Box<ModelBuildingError>), // Can't derive `From` implementation because of `Box`.
+    MissingDomain(#[from] MissingDomain),
 
-    #[error("Error while merging with the error base fetched from `takeFrom` link: {0}")]
-    MergeError(#[from] MergeError),
+    #[error("Error while building model following a `takeFrom` link: {0}")]
+    ModelBuildingError(/* from */ Box<ModelBuildingError>), // Can't derive `From` implementation because of `Box`.
 
     #[error("Error while building model following a `takeFrom` link: {0}")]
     LinkError(#[from] LinkError),
@@ -39,20 +82,48 @@ impl From<ModelBuildingError> for TakeFromError {
 }
 
 impl TakeFromError {
-    pub fn from_address(self, address: &str) -> ModelBuildingError {
-        ModelBuildingError::TakeFrom {
-            address: address.to_string(),
-            inner: self,
+    /// Attach the chain of links leading to the file where the error occurred.
+    /// Errors coming from files included further down the chain already carry
+    /// a longer chain, and are passed through as is.
+    pub fn in_file(self, include_stack: &IncludeStack) -> ModelBuildingError {
+        match self {
+            TakeFromError::ModelBuildingError(inner)
+                if matches!(
+                    *inner,
+                    ModelBuildingError::TakeFrom { .. }
+                        | ModelBuildingError::IncludeCycle { .. }
+                        | ModelBuildingError::ImportMergeError { .. }
+                ) =>
+            {
+                *inner
+            }
+            _ => ModelBuildingError::TakeFrom {
+                include_stack: include_stack.clone(),
+                inner: Box::new(self),
+            },
         }
     }
 }
 #[derive(Debug, thiserror::Error)]
 pub enum ModelBuildingError {
-    #[error("Failed to import a file {address}: {inner}")]
+    #[error("Failed to import a file {}, included through {include_stack}: {inner}", include_stack.origin())]
     TakeFrom {
-        address: String,
+        include_stack: IncludeStack,
         #[source]
-        inner: TakeFromError,
+        inner: Box<TakeFromError>,
+    },
+
+    #[error("Cyclic `takeFrom` links: {include_stack}.")]
+    IncludeCycle { include_stack: IncludeStack },
+
+    #[error(
+        "Error merging `{}`, included through {include_stack}, into `{}`: {merge_error}",
+        include_stack.origin(),
+        include_stack.includer()
+    )]
+    ImportMergeError {
+        include_stack: IncludeStack,
+        merge_error: Box<MergeError>,
     },
 
     #[error(
@@ -60,11 +131,29 @@ pub enum ModelBuildingError {
     )]
     MergeError {
         merge_error: MergeError,
-        main_model_origin: Link,
-        additional_model_origin: Link,
+        main_model_origin: Box<Link>,
+        additional_model_origin: Box<Link>,
+    },
+    #[error("Failed to apply overlay {origin}: {inner}")]
+    OverlayError {
+        origin: Box<Link>,
+        #[source]
+        inner: OverlayError,
+    },
+    #[error("Invalid message `{message}` of the error `{error}` {identifier}: {inner}")]
+    MessageTemplateError {
+        error: String,
+        identifier: Box<ErrorIdentifier>,
+        message: String,
+        #[source]
+        inner: TemplateError,
     },
+    #[error("No code is left for the error `{error}` of the component `{component}`; give it a code explicitly.")]
+    NoFreeErrorCode { component: String, error: String },
+    #[error("Unknown lint rule `{rule}` in `{origin}`; known rules are {}.", Rule::ALL.map(|r| format!("`{r}`")).join(", "))]
+    UnknownLintRule { rule: String, origin: String },
     #[error("Error validating combined model: {0}")]
-    ModelValidationError(#[from] ModelValidationError),
+    ModelValidationError(#[from] ModelValidationErrors),
     #[error(transparent)]
     LoadError(#[from] LoadError),
 }
@@ -1,39 +1,77 @@
+mod codes;
 pub mod context;
 pub mod error;
+mod overlay;
 
+use std::cell::RefCell;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
 use std::rc::Rc;
 
+use codes::CodeAllocator;
+use codes::DomainCodes;
 use context::ComponentTranslationContext;
 use context::DomainTranslationContext;
 use context::ErrorTranslationContext;
+use context::IncludeStack;
 use context::ModelTranslationContext;
 use context::TypeTranslationContext;
 use error::MissingComponent;
+use error::MissingDomain;
 use error::ModelBuildingError;
 use error::TakeFromError;
 use maplit::btreemap;
+use overlay::apply_overlay;
+use overlay::normalize_identifier;
 use zksync_error_model::validator::validate;
 
 use crate::description::Collection;
-use crate::loader::load;
+use crate::loader::cargo::CargoConfig;
+use crate::loader::cargo::CargoWorkspace;
+use crate::loader::fetcher::Fetcher;
+use crate::loader::load_resolved;
+use crate::loader::network::NetworkConfig;
+use crate::loader::network::RemoteFiles;
+use crate::loader::network::LOCKFILE_NAME;
+use crate::loader::registry::Registry;
+use crate::loader::registry::RegistryConfig;
+use crate::loader::registry::REGISTRY_NAME;
+use crate::loader::resolution::ResolvedLink;
+use crate::loader::resolve_link;
+use crate::loader::resolve_links;
+use crate::loader::CollectionFile;
 
+use zksync_error_model::error::TemplateError;
+use zksync_error_model::identifier::ErrorIdentifier;
+use zksync_error_model::inner::CodeLayout;
+use zksync_error_model::inner::CodeLayoutUnit;
+use zksync_error_model::inner::CodeRange;
 use zksync_error_model::inner::ComponentDescription;
 use zksync_error_model::inner::ComponentMetadata;
 use zksync_error_model::inner::DomainDescription;
 use zksync_error_model::inner::DomainMetadata;
+use zksync_error_model::inner::ErrorCode;
 use zksync_error_model::inner::ErrorDescription;
 use zksync_error_model::inner::ErrorDocumentation;
 use zksync_error_model::inner::ErrorName;
 use zksync_error_model::inner::FieldDescription;
 use zksync_error_model::inner::FullyQualifiedTargetLanguageType;
+use zksync_error_model::inner::Lifecycle;
 use zksync_error_model::inner::LikelyCause;
+use zksync_error_model::inner::MessageTemplate;
 use zksync_error_model::inner::Model;
 use zksync_error_model::inner::TargetLanguageType;
 use zksync_error_model::inner::TypeDescription;
+use zksync_error_model::inner::TypeExpression;
 use zksync_error_model::inner::TypeMetadata;
 use zksync_error_model::inner::VersionedOwner;
-use zksync_error_model::merger::Merge as _;
+use zksync_error_model::lint::Level;
+use zksync_error_model::lint::LintLevels;
+use zksync_error_model::merger::error::MergeError;
+use zksync_error_model::merger::Merge;
+use zksync_error_model::merger::MergeMode;
 
 use super::error::FileFormatError;
 use super::error::LoadError;
@@ -73,26 +111,29 @@ fn translate_type_mappings(
     ModelBuildingError,
 > {
     let mut result: BTreeMap<_, FullyQualifiedTargetLanguageType> = Default::default();
-    if let Some(crate::description::FullyQualifiedType { name, path }) = &value.rust {
-        result.insert(
-            "rust".into(),
-            FullyQualifiedTargetLanguageType {
-                name: name.clone(),
-                path: path.clone(),
-            },
-        );
+    for (language, binding) in [("rust", &value.rust), ("typescript", &value.typescript)] {
+        if let Some(crate::description::FullyQualifiedType { name, path }) = binding {
+            result.insert(
+                language.into(),
+                FullyQualifiedTargetLanguageType {
+                    name: name.clone(),
+                    path: path.clone(),
+                },
+            );
+        }
     }
     Ok(result)
 }
 
 fn translate_type(
     value: &crate::description::Type,
-    _ctx: &TypeTranslationContext,
+    ctx: &TypeTranslationContext,
 ) -> Result<TypeDescription, ModelBuildingError> {
     let crate::description::Type {
         name,
         description,
         bindings: codegen,
+        pointer,
     } = value;
     Ok(TypeDescription {
         name: name.clone(),
@@ -100,6 +141,7 @@ fn translate_type(
             description: description.clone(),
         },
         bindings: translate_type_mappings(codegen)?,
+        origin: ctx.parent.origin(pointer),
     })
 }
 
@@ -107,8 +149,22 @@ fn translate_model(
     model: &crate::description::Root,
     ctx: ModelTranslationContext,
 ) -> Result<Model, ModelBuildingError> {
-    let mut result = Model::default();
-    let crate::description::Root { types, domains } = model;
+    let crate::description::Root {
+        include,
+        types,
+        domains,
+        code_layout,
+        lints,
+    } = model;
+    let code_layout = code_layout
+        .as_ref()
+        .map_or(ctx.code_layout, translate_code_layout);
+    let ctx = ModelTranslationContext { code_layout, ..ctx };
+    let mut result = Model {
+        code_layout,
+        lints: translate_lints(lints, &ctx)?,
+        ..Default::default()
+    };
     for t in types {
         let ctx = TypeTranslationContext {
             type_name: &t.name,
@@ -127,16 +183,76 @@ fn translate_model(
             .insert(transformed_domain.meta.name.clone(), transformed_domain);
     }
 
+    for address in include {
+        let (link, include_stacks) = follow_link(address, &ctx)?;
+        for include_stack in include_stacks {
+            let part = translate_included(&link, ctx.including(include_stack.clone()))
+                .map_err(|e| TakeFromError::from(e).in_file(&include_stack))?;
+            merge_imported(&mut result, &part, &include_stack, ctx.merge_mode)?;
+        }
+    }
+
     Ok(result)
 }
 
+fn translate_lints(
+    lints: &BTreeMap<String, crate::description::LintLevel>,
+    ctx: &ModelTranslationContext,
+) -> Result<LintLevels, ModelBuildingError> {
+    let mut result = LintLevels::default();
+    for (rule, level) in lints {
+        let rule = rule
+            .parse()
+            .map_err(|_| ModelBuildingError::UnknownLintRule {
+                rule: rule.clone(),
+                origin: ctx.source_name(),
+            })?;
+        let level = match level {
+            crate::description::LintLevel::Allow => Level::Allow,
+            crate::description::LintLevel::Warn => Level::Warn,
+            crate::description::LintLevel::Deny => Level::Deny,
+        };
+        result.0.insert(rule, level);
+    }
+    Ok(result)
+}
+
+fn translate_code_layout(layout: &crate::description::CodeLayout) -> CodeLayout {
+    let crate::description::CodeLayout {
+        unit,
+        domain,
+        component,
+        error,
+    } = layout;
+    CodeLayout {
+        unit: match unit {
+            crate::description::CodeLayoutUnit::Digits => CodeLayoutUnit::Digits,
+            crate::description::CodeLayoutUnit::Bits => CodeLayoutUnit::Bits,
+        },
+        domain: *domain,
+        component: *component,
+        error: *error,
+    }
+}
+
 fn translate_field(
     value: &crate::description::Field,
 ) -> Result<FieldDescription, ModelBuildingError> {
-    let crate::description::Field { name, r#type } = value;
+    let crate::description::Field {
+        name,
+        r#type,
+        description,
+        optional,
+        default,
+    } = value;
+    // Invalid types are reported by the validator.
+    let r#type = TypeExpression::parse(r#type).map_or_else(|_| r#type.clone(), |t| t.to_string());
     Ok(FieldDescription {
         name: name.clone(),
-        r#type: r#type.clone(),
+        r#type,
+        description: description.clone().unwrap_or_default(),
+        optional: *optional,
+        default: default.clone(),
     })
 }
 
@@ -191,10 +307,10 @@ fn translate_error_documentation(
         likely_causes,
     } = &doc;
 
-    let likely_causes: Vec<_> = likely_causes
+    let likely_causes = likely_causes
         .iter()
-        .flat_map(translate_likely_cause)
-        .collect();
+        .map(translate_likely_cause)
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok(ErrorDocumentation {
         description: description.clone(),
@@ -203,20 +319,69 @@ fn translate_error_documentation(
     })
 }
 
+/// Parse the message of an error and ensure that its placeholders refer to the
+/// fields of this error. Fields never mentioned in the message are reported as
+/// warnings.
+fn translate_message(
+    message: &str,
+    fields: &[FieldDescription],
+    error_name: &str,
+    identifier: &ErrorIdentifier,
+) -> Result<MessageTemplate, ModelBuildingError> {
+    let to_building_error = |inner| ModelBuildingError::MessageTemplateError {
+        error: error_name.to_owned(),
+        identifier: Box::new(identifier.clone()),
+        message: message.to_owned(),
+        inner,
+    };
+    let template = MessageTemplate::parse(message).map_err(to_building_error)?;
+
+    if let Some(placeholder) = template
+        .placeholders()
+        .find(|p| !fields.iter().any(|f| f.name == p.field))
+    {
+        return Err(to_building_error(TemplateError::UnknownField(
+            placeholder.field.clone(),
+        )));
+    }
+    for field in fields {
+        if !template.mentions(&field.name) {
+            log::warn!(
+                "Field `{}` of the error `{error_name}` {identifier} is not mentioned in its message.",
+                field.name
+            );
+        }
+    }
+    Ok(template)
+}
+
 fn translate_error(
     error: &crate::description::Error,
+    code: u32,
     ctx: &ErrorTranslationContext,
 ) -> Result<ErrorDescription, ModelBuildingError> {
     let crate::description::Error {
         name,
-        code,
+        code: _,
         message,
         bindings,
         fields,
         doc,
+        deprecated,
+        retired,
+        pointer,
     } = error;
-    let transformed_fields: Result<_, _> = fields.iter().map(translate_field).collect();
+    let transformed_fields: Vec<_> = fields
+        .iter()
+        .map(translate_field)
+        .collect::<Result<_, _>>()?;
     let transformed_bindings = translate_type_bindings(bindings, &error.name)?;
+    let identifier = ErrorIdentifier {
+        domain: ctx.parent.domain.identifier.clone(),
+        component: ctx.component.identifier.clone(),
+        code,
+    };
+    let message = translate_message(message, &transformed_fields, name, &identifier)?;
 
     let documentation = if let Some(doc) = doc {
         Some(translate_error_documentation(doc)?)
@@ -225,29 +390,64 @@ fn translate_error(
     };
     Ok(ErrorDescription {
         name: name.clone(),
-        code: *code,
-        message: message.clone(),
-        fields: transformed_fields?,
+        code,
+        message,
+        fields: transformed_fields,
         documentation,
         bindings: transformed_bindings,
         domain: ctx.parent.domain.clone(),
         component: ctx.component.clone(),
+        lifecycle: translate_lifecycle(deprecated, *retired),
+        origin: ctx.parent.get_origin(pointer),
     })
 }
 
+/// A retired error needs no deprecation notice, as it is not generated anymore.
+fn translate_lifecycle(
+    deprecated: &Option<crate::description::Deprecation>,
+    retired: bool,
+) -> Lifecycle {
+    match deprecated {
+        _ if retired => Lifecycle::Retired,
+        Some(crate::description::Deprecation {
+            message,
+            replacement,
+        }) => Lifecycle::Deprecated {
+            message: message.clone(),
+            replacement: replacement.as_deref().map(normalize_identifier),
+        },
+        None => Lifecycle::Active,
+    }
+}
+
 enum FetchComponentResult {
     Errors(Vec<ErrorDescription>),
     Component(ComponentDescription),
 }
 
-fn fetch_named_component<'a>(
-    link: &str,
+/// Load the file `link` points to, which is on top of `include_stack`, and
+/// translate the errors of the component described by
+/// `present_component_metadata` from it. The file is translated in its own
+/// context.
+fn fetch_named_component(
+    link: &Link,
     present_component_metadata: &Rc<ComponentMetadata>,
-    ctx: &'a ComponentTranslationContext<'a>,
+    ctx: &ComponentTranslationContext,
+    include_stack: &IncludeStack,
+    codes: &mut DomainCodes,
 ) -> Result<FetchComponentResult, TakeFromError> {
-    let error_base = load(&Link::parse(link)?)?;
+    let model_ctx = ctx.parent.parent.including(include_stack.clone());
+    let error_base = model_ctx.load(link)?;
+    let domain_ctx = DomainTranslationContext { parent: &model_ctx };
+    let ctx = ComponentTranslationContext {
+        domain: ctx.domain.clone(),
+        parent: &domain_ctx,
+    };
     match error_base {
-        Collection::Root(_) | Collection::Domain(_) | Collection::Component(_) => {
+        Collection::Root(_)
+        | Collection::Domain(_)
+        | Collection::Component(_)
+        | Collection::Overlay(_) => {
             let component = error_base
                 .get_component(&ctx.get_domain(), &present_component_metadata.name)
                 .ok_or(MissingComponent {
@@ -255,36 +455,56 @@ fn fetch_named_component<'a>(
                     component_name: present_component_metadata.name.to_owned(),
                 })?;
             Ok(FetchComponentResult::Component(translate_component(
-                component, ctx,
+                component, &ctx, codes,
             )?))
         }
         Collection::Errors(errors) => Ok(FetchComponentResult::Errors(translate_errors(
             &errors,
-            ctx,
+            &ctx,
             present_component_metadata,
+            codes.allocator(present_component_metadata, ctx.get_registry()),
         )?)),
     }
 }
 
+/// Errors without a code are given one by `codes`, after the codes of the
+/// other errors are taken.
 fn translate_errors<'a>(
     errors: &Vec<crate::description::Error>,
     ctx: &'a ComponentTranslationContext<'a>,
     component_meta: &Rc<ComponentMetadata>,
+    codes: &mut CodeAllocator,
 ) -> Result<Vec<ErrorDescription>, ModelBuildingError> {
     let mut transformed_errors = Vec::default();
 
+    for code in errors.iter().filter_map(|error| error.code) {
+        codes.take(code);
+    }
+    let capacity = ctx.parent.parent.code_layout.error_capacity();
     for error in errors {
         let ctx = ErrorTranslationContext {
             parent: ctx,
             component: component_meta.clone(),
         };
-        transformed_errors.push(translate_error(error, &ctx)?);
+        let code = match error.code {
+            Some(code) => code,
+            None => codes.allocate(&error.name, capacity).ok_or_else(|| {
+                ModelBuildingError::NoFreeErrorCode {
+                    component: component_meta.name.clone(),
+                    error: error.name.clone(),
+                }
+            })?,
+        };
+        transformed_errors.push(translate_error(error, code, &ctx)?);
     }
     Ok(transformed_errors)
 }
+/// All definitions of a component allocate the codes of their errors with the
+/// same allocator from `codes`.
 fn translate_component<'a>(
     component: &crate::description::Component,
     ctx: &'a ComponentTranslationContext<'a>,
+    codes: &mut DomainCodes,
 ) -> Result<ComponentDescription, ModelBuildingError> {
     let crate::description::Component {
         component_name,
@@ -294,6 +514,8 @@ fn translate_component<'a>(
         takeFrom,
         errors,
         bindings,
+        reserved,
+        pointer,
     } = component;
 
     let component_meta: Rc<ComponentMetadata> = Rc::new(ComponentMetadata {
@@ -306,26 +528,134 @@ fn translate_component<'a>(
         identifier: identifier_encoding.clone().unwrap_or_default(),
         description: description.clone().unwrap_or_default(),
         domain: ctx.domain.clone(),
+        reserved: reserved
+            .iter()
+            .map(|crate::description::CodeRange { start, end }| CodeRange {
+                start: *start,
+                end: *end,
+            })
+            .collect(),
+        origin: ctx.get_origin(pointer),
     });
 
-    let transformed_errors = translate_errors(errors, ctx, &component_meta)?;
+    let transformed_errors = translate_errors(
+        errors,
+        ctx,
+        &component_meta,
+        codes.allocator(&component_meta, ctx.get_registry()),
+    )?;
     let mut result = ComponentDescription {
         meta: component_meta.clone(),
         errors: transformed_errors,
     };
     for take_from_address in takeFrom {
-        match fetch_named_component(take_from_address, &component_meta, ctx)
-            .map_err(|e| e.from_address(take_from_address))?
-        {
-            FetchComponentResult::Errors(vec) => result.errors.extend(vec),
-            FetchComponentResult::Component(component_description) => {
-                result
-                    .merge(&component_description)
-                    .map_err(|e| TakeFromError::MergeError(e).from_address(take_from_address))?;
-            }
-        };
+        let (link, include_stacks) = follow_link(take_from_address, ctx.parent.parent)?;
+        for include_stack in include_stacks {
+            match fetch_named_component(&link, &component_meta, ctx, &include_stack, codes)
+                .map_err(|e| e.in_file(&include_stack))?
+            {
+                FetchComponentResult::Errors(vec) => result.errors.extend(vec),
+                FetchComponentResult::Component(component_description) => {
+                    merge_imported(
+                        &mut result,
+                        &component_description,
+                        &include_stack,
+                        ctx.parent.parent.merge_mode,
+                    )?;
+                }
+            };
+        }
+    }
+
+    Ok(result)
+}
+
+/// Merge `part`, imported through `include_stack`, into `main`. The conflicts
+/// tolerated in lenient `mode` are logged.
+fn merge_imported<T: Merge>(
+    main: &mut T,
+    part: &T,
+    include_stack: &IncludeStack,
+    mode: MergeMode,
+) -> Result<(), ModelBuildingError> {
+    let conflicts = main.merge_in_mode(part, mode).map_err(|merge_error| {
+        ModelBuildingError::ImportMergeError {
+            include_stack: include_stack.clone(),
+            merge_error: Box::new(merge_error),
+        }
+    })?;
+    for conflict in conflicts {
+        log::warn!(
+            "Merging `{}` into `{}`: {conflict}",
+            include_stack.origin(),
+            include_stack.includer()
+        );
+    }
+    Ok(())
+}
+
+/// Parse a link found in the file on top of the include stack of `ctx`, and
+/// find the files it points to. Returns the include stacks leading to each of
+/// them; patterns never match the files already on the stack.
+fn follow_link(
+    address: &str,
+    ctx: &ModelTranslationContext,
+) -> Result<(Link, Vec<IncludeStack>), ModelBuildingError> {
+    let include_stack = &ctx.include_stack;
+    let link = Link::parse(address).map_err(|e| TakeFromError::from(e).in_file(include_stack))?;
+    let locations = resolve_links(
+        &link,
+        Some(include_stack.origin()),
+        ctx.workspace,
+        ctx.fetcher,
+    )
+    .map_err(|e| TakeFromError::from(e).in_file(include_stack))?;
+
+    let mut include_stacks = vec![];
+    for location in locations {
+        // A pattern may match the including file itself.
+        if link.is_pattern() && include_stack.contains(&location, ctx.fetcher) {
+            continue;
+        }
+        include_stacks.push(include_stack.including(location, ctx.fetcher)?);
     }
+    Ok((link, include_stacks))
+}
 
+/// Domains named as `domain` in the files its `takeFrom` links point to, along
+/// with the include stacks leading to them.
+fn fetch_domains(
+    domain: &crate::description::Domain,
+    ctx: &ModelTranslationContext,
+) -> Result<Vec<(IncludeStack, crate::description::Domain)>, ModelBuildingError> {
+    let mut result = vec![];
+    for address in &domain.takeFrom {
+        let (link, include_stacks) = follow_link(address, ctx)?;
+        for include_stack in include_stacks {
+            let in_file = |e: TakeFromError| e.in_file(&include_stack);
+            let collection = ctx
+                .including(include_stack.clone())
+                .load(&link)
+                .map_err(|e| in_file(e.into()))?;
+            let imported = collection.get_domain(&domain.domain_name).ok_or_else(|| {
+                in_file(
+                    MissingDomain {
+                        domain_name: domain.domain_name.clone(),
+                    }
+                    .into(),
+                )
+            })?;
+            if imported.domain_code != domain.domain_code {
+                return Err(ModelBuildingError::ImportMergeError {
+                    include_stack,
+                    merge_error: Box::new(MergeError::ConflictingDomainDefinitions(
+                        domain.domain_name.clone(),
+                    )),
+                });
+            }
+            result.push((include_stack.clone(), imported.clone()));
+        }
+    }
     Ok(result)
 }
 
@@ -338,51 +668,218 @@ fn translate_domain<'a>(
         domain_code,
         identifier_encoding,
         description,
-        components,
         bindings,
+        pointer,
+        ..
     } = value;
-    let mut new_components: BTreeMap<_, _> = BTreeMap::default();
+    let imports = fetch_domains(value, ctx.parent)?;
+    // Attributes omitted here are taken from the imported domains.
+    let imported = || imports.iter().map(|(_, domain)| domain);
+    let identifier_encoding = identifier_encoding
+        .clone()
+        .or_else(|| imported().find_map(|d| d.identifier_encoding.clone()));
+    let description = description
+        .clone()
+        .or_else(|| imported().find_map(|d| d.description.clone()));
+    let rust = bindings
+        .rust
+        .clone()
+        .or_else(|| imported().find_map(|d| d.bindings.rust.clone()));
+    let typescript = bindings
+        .typescript
+        .clone()
+        .or_else(|| imported().find_map(|d| d.bindings.typescript.clone()));
+
     let metadata = Rc::new(DomainMetadata {
         name: domain_name.clone(),
         code: *domain_code,
-        identifier: identifier_encoding.clone().unwrap_or_default(),
-        description: description.clone().unwrap_or_default(),
+        identifier: identifier_encoding.unwrap_or_default(),
+        description: description.unwrap_or_default(),
         bindings: btreemap! {
-            "rust".into() => bindings.rust.clone().unwrap_or(domain_name.clone()),
-            "typescript".into() => bindings.typescript.clone().unwrap_or(domain_name.clone()),
+            "rust".into() => rust.unwrap_or(domain_name.clone()),
+            "typescript".into() => typescript.unwrap_or(domain_name.clone()),
         },
+        origin: ctx.parent.origin(pointer),
     });
-    for component in components {
+    let mut codes = DomainCodes::new(explicit_codes(value, &imports, ctx.parent)?);
+    translate_domain_components(value, imports, &metadata, ctx, &mut codes)
+}
+
+/// Codes written explicitly in the errors of the components of `domain` and
+/// of the `imports`, including the errors taken from other files, by the
+/// names of the components. The files are loaded once, through the context,
+/// and translated afterwards.
+fn explicit_codes(
+    domain: &crate::description::Domain,
+    imports: &[(IncludeStack, crate::description::Domain)],
+    ctx: &ModelTranslationContext,
+) -> Result<BTreeMap<String, BTreeSet<ErrorCode>>, ModelBuildingError> {
+    let mut result = BTreeMap::new();
+    collect_domain_codes(domain, imports, ctx, &mut result)?;
+    Ok(result)
+}
+
+fn collect_domain_codes(
+    domain: &crate::description::Domain,
+    imports: &[(IncludeStack, crate::description::Domain)],
+    ctx: &ModelTranslationContext,
+    result: &mut BTreeMap<String, BTreeSet<ErrorCode>>,
+) -> Result<(), ModelBuildingError> {
+    for component in &domain.components {
+        let codes = result.entry(component.component_name.clone()).or_default();
+        collect_component_codes(component, &domain.domain_name, ctx, codes)?;
+    }
+    for (include_stack, imported) in imports {
+        let ctx = ctx.including(include_stack.clone());
+        let nested_imports = fetch_domains(imported, &ctx)?;
+        collect_domain_codes(imported, &nested_imports, &ctx, result)
+            .map_err(|e| TakeFromError::from(e).in_file(include_stack))?;
+    }
+    Ok(())
+}
+
+fn collect_component_codes(
+    component: &crate::description::Component,
+    domain_name: &str,
+    ctx: &ModelTranslationContext,
+    result: &mut BTreeSet<ErrorCode>,
+) -> Result<(), ModelBuildingError> {
+    result.extend(component.errors.iter().filter_map(|error| error.code));
+    for address in &component.takeFrom {
+        let (link, include_stacks) = follow_link(address, ctx)?;
+        for include_stack in include_stacks {
+            let in_file = |e: TakeFromError| e.in_file(&include_stack);
+            let ctx = ctx.including(include_stack.clone());
+            match ctx.load(&link).map_err(|e| in_file(e.into()))? {
+                Collection::Errors(errors) => {
+                    result.extend(errors.iter().filter_map(|error| error.code))
+                }
+                collection => {
+                    let nested = collection
+                        .get_component(domain_name, &component.component_name)
+                        .ok_or_else(|| {
+                            in_file(
+                                MissingComponent {
+                                    domain_name: domain_name.to_owned(),
+                                    component_name: component.component_name.clone(),
+                                }
+                                .into(),
+                            )
+                        })?;
+                    collect_component_codes(nested, domain_name, &ctx, result)
+                        .map_err(|e| in_file(e.into()))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Translate the components of `value` and of the domains it imports, which
+/// all belong to the domain described by `metadata`.
+fn translate_domain_components<'a>(
+    value: &crate::description::Domain,
+    imports: Vec<(IncludeStack, crate::description::Domain)>,
+    metadata: &Rc<DomainMetadata>,
+    ctx: &'a DomainTranslationContext<'a>,
+    codes: &mut DomainCodes,
+) -> Result<DomainDescription, ModelBuildingError> {
+    let mut new_components: BTreeMap<_, _> = BTreeMap::default();
+    for component in &value.components {
         let ctx = ComponentTranslationContext {
             domain: metadata.clone(),
             parent: ctx,
         };
 
-        let translated_component = translate_component(component, &ctx)?;
+        let translated_component = translate_component(component, &ctx, codes)?;
         new_components.insert(translated_component.meta.name.clone(), translated_component);
     }
-    Ok(DomainDescription {
-        meta: metadata,
+    let mut result = DomainDescription {
+        meta: metadata.clone(),
         components: new_components,
-    })
+    };
+
+    for (include_stack, imported) in imports {
+        let model_ctx = ctx.parent.including(include_stack.clone());
+        let nested_imports = fetch_domains(&imported, &model_ctx)?;
+        let domain_ctx = DomainTranslationContext { parent: &model_ctx };
+        let part =
+            translate_domain_components(&imported, nested_imports, metadata, &domain_ctx, codes)
+                .map_err(|e| TakeFromError::from(e).in_file(&include_stack))?;
+        merge_imported(&mut result, &part, &include_stack, ctx.parent.merge_mode)?;
+    }
+    Ok(result)
 }
 
-fn load_root_model(root_link: &Link) -> Result<Model, LoadError> {
+/// Translate a file included at the root level: a whole model, or a single
+/// domain.
+fn translate_included(
+    link: &Link,
+    ctx: ModelTranslationContext,
+) -> Result<Model, ModelBuildingError> {
+    match ctx.load(link)? {
+        Collection::Root(root) => translate_model(&root, ctx),
+        Collection::Domain(domain) => {
+            let domain_ctx = DomainTranslationContext { parent: &ctx };
+            let domain = translate_domain(&domain, &domain_ctx)?;
+            Ok(Model {
+                domains: btreemap! { domain.meta.name.clone() => domain },
+                ..Default::default()
+            })
+        }
+        Collection::Overlay(_) => Err(LoadError::FileFormatError(
+            FileFormatError::ExpectedFullGotOverlay {
+                origin: link.clone(),
+            },
+        )
+        .into()),
+        Collection::Component(_) | Collection::Errors(_) => Err(LoadError::FileFormatError(
+            FileFormatError::ExpectedFullGotComponent {
+                origin: link.clone(),
+            },
+        )
+        .into()),
+    }
+}
+
+fn load_root_model(
+    root_link: &Link,
+    location: ResolvedLink,
+    ctx: &ModelTranslationContext,
+) -> Result<Model, ModelBuildingError> {
+    let collection = load_resolved(root_link, &location, ctx.remote, ctx.fetcher)?;
+    translate_root_collection(root_link, location, collection, ctx)
+}
+
+/// Translate a file passed by the user, found at `location`. Only the remote
+/// files, workspace, fetcher and merge mode of `ctx` are used.
+fn translate_root_collection(
+    root_link: &Link,
+    location: ResolvedLink,
+    collection: Collection,
+    ctx: &ModelTranslationContext,
+) -> Result<Model, ModelBuildingError> {
     let source = root_link.clone();
-    match load(root_link)? {
+    match collection {
         Collection::Domain(_) => Err(LoadError::FileFormatError(
             FileFormatError::ExpectedFullGotDomain { origin: source },
-        )),
+        )
+        .into()),
         Collection::Component(_) => Err(LoadError::FileFormatError(
             FileFormatError::ExpectedFullGotComponent { origin: source },
-        )),
+        )
+        .into()),
         Collection::Errors(_) => Err(LoadError::FileFormatError(
             FileFormatError::ExpectedFullGotComponent { origin: source },
-        )),
-        Collection::Root(root) => Ok(translate_model(
-            &root,
-            ModelTranslationContext { origin: source },
-        )?),
+        )
+        .into()),
+        Collection::Overlay(_) => Err(LoadError::FileFormatError(
+            FileFormatError::ExpectedFullGotOverlay { origin: source },
+        )
+        .into()),
+        Collection::Root(root) => {
+            translate_model(&root, ctx.including(IncludeStack::new(location)))
+        }
     }
 }
 
@@ -395,16 +892,22 @@ fn add_default_error(model: &mut Model) {
                     component: component.meta.clone(),
                     name: "GenericError".into(),
                     code: 0,
-                    message: "Generic error: {message}".into(),
+                    message: MessageTemplate::parse("Generic error: {message}")
+                        .expect("Internal error"),
                     fields: vec![FieldDescription {
                         name: "message".into(),
                         r#type: "string".into(),
+                        description: String::new(),
+                        optional: false,
+                        default: None,
                     }],
                     documentation: None,
                     bindings: btreemap! {
                         "rust".into() => TargetLanguageType { name: "GenericError".into()} ,
                         "typescript".into() => TargetLanguageType { name: "GenericError".into()} ,
                     },
+                    lifecycle: Lifecycle::Active,
+                    origin: None,
                 });
             }
         }
@@ -430,37 +933,607 @@ fn bind_error_types(model: &mut Model) {
                     description: component.meta.description.clone(),
                 },
                 bindings,
+                origin: None,
             };
             model.types.insert(component.meta.name.clone(), value);
         }
     }
 }
 
+/// Lockfile next to the root definitions, if they are stored in a local file.
+fn default_lockfile(root: &ResolvedLink) -> Option<PathBuf> {
+    match root {
+        ResolvedLink::DescriptionFile(CollectionFile {
+            absolute_path: path,
+            ..
+        })
+        | ResolvedLink::LocalPath(path) => {
+            Some(path.parent().unwrap_or(Path::new("")).join(LOCKFILE_NAME))
+        }
+        ResolvedLink::Url(_) | ResolvedLink::Custom { .. } => None,
+    }
+}
+
+/// Registry next to the root definitions, if they are stored in a local file.
+fn default_registry(root: &ResolvedLink) -> Option<PathBuf> {
+    default_lockfile(root).map(|lockfile| lockfile.with_file_name(REGISTRY_NAME))
+}
+
+/// Settings of [`build_model`].
+#[derive(Clone, Debug, Default)]
+pub struct ModelBuildingOptions {
+    /// Files merged into the root definitions.
+    pub additions: Vec<Link>,
+    /// Languages the types used in error fields should have bindings for.
+    pub languages: Vec<String>,
+    /// How the files in the network are locked and cached.
+    pub network: NetworkConfig,
+    /// Registry of the codes given to the errors without codes, updated if
+    /// it says so.
+    pub registry: RegistryConfig,
+    /// Workspace where cargo links are resolved.
+    pub cargo: CargoConfig,
+    /// How conflicting definitions are merged.
+    pub merge_mode: MergeMode,
+}
+
+/// Load the model from `root_link`, merge it with the additional files of
+/// `options` and validate it. Files are accessed through `fetcher`.
 pub fn build_model(
     root_link: &Link,
-    additions: &Vec<Link>,
-    diagnostic: bool,
+    options: &ModelBuildingOptions,
+    fetcher: &dyn Fetcher,
 ) -> Result<Model, ModelBuildingError> {
-    let mut root_model = load_root_model(root_link)?;
+    let ModelBuildingOptions {
+        additions,
+        languages,
+        network,
+        registry,
+        cargo,
+        merge_mode,
+    } = options;
+    let merge_mode = *merge_mode;
+    let workspace = CargoWorkspace::new(cargo.clone());
+    let root_location = resolve_link(root_link, None, &workspace, fetcher)?;
+    let remote =
+        RemoteFiles::new(network, default_lockfile(&root_location)).map_err(LoadError::from)?;
+    let mut registry_file =
+        Registry::new(registry, default_registry(&root_location)).map_err(LoadError::from)?;
+    let loaded = RefCell::default();
+    let ctx = ModelTranslationContext {
+        include_stack: IncludeStack::new(root_location.clone()),
+        root: &root_location,
+        remote: &remote,
+        registry: &registry_file,
+        workspace: &workspace,
+        fetcher,
+        merge_mode,
+        code_layout: CodeLayout::default(),
+        loaded: &loaded,
+    };
+    let mut root_model = load_root_model(root_link, root_location.clone(), &ctx)?;
+    // Additional files share the layout of the root definitions.
+    let ctx = ModelTranslationContext {
+        code_layout: root_model.code_layout,
+        ..ctx
+    };
 
+    let mut overlays = vec![];
     for input_link in additions {
-        let part = load_root_model(input_link)?;
-        root_model
-            .merge(&part)
-            .map_err(|error| ModelBuildingError::MergeError {
-                merge_error: error,
-                main_model_origin: root_link.clone(),
-                additional_model_origin: input_link.clone(),
-            })?
+        for location in resolve_links(input_link, None, &workspace, fetcher)? {
+            let collection = load_resolved(input_link, &location, &remote, fetcher)?;
+            if let Collection::Overlay(overlay) = collection {
+                overlays.push((input_link, overlay));
+                continue;
+            }
+            let part = translate_root_collection(input_link, location, collection, &ctx)?;
+            let conflicts = root_model
+                .merge_in_mode(&part, merge_mode)
+                .map_err(|error| ModelBuildingError::MergeError {
+                    merge_error: error,
+                    main_model_origin: Box::new(root_link.clone()),
+                    additional_model_origin: Box::new(input_link.clone()),
+                })?;
+            for conflict in conflicts {
+                log::warn!("Merging `{input_link}` into `{root_link}`: {conflict}");
+            }
+        }
+    }
+
+    remote.save().map_err(LoadError::from)?;
+    if let Some(lockfile) = remote.lockfile_path() {
+        log::info!("Lockfile: {}", lockfile.display());
     }
 
     add_default_error(&mut root_model);
-    bind_error_types(&mut root_model);
-    if diagnostic {
-        eprintln!("Model: {root_model:#?}");
-        eprintln!("Model validation...");
+    for (origin, overlay) in &overlays {
+        apply_overlay(&mut root_model, overlay).map_err(|inner| {
+            ModelBuildingError::OverlayError {
+                origin: Box::new((*origin).clone()),
+                inner,
+            }
+        })?;
     }
+    bind_error_types(&mut root_model);
+    log::debug!("Model: {root_model:#?}");
+    log::info!("Model validation...");
 
-    validate(&root_model)?;
+    let languages: Vec<_> = languages.iter().map(String::as_str).collect();
+    validate(&root_model, &languages)?;
+
+    if registry.update {
+        let changed = registry_file
+            .update(&root_model, registry.force)
+            .map_err(LoadError::from)?;
+        if changed {
+            registry_file.save().map_err(LoadError::from)?;
+        }
+        if let Some(path) = registry_file.path() {
+            log::info!("Registry: {}", path.display());
+        }
+    }
     Ok(root_model)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use crate::loader::error::FetchError;
+    use crate::loader::error::LoadError;
+    use crate::loader::error::RegistryError;
+    use crate::loader::error::RegistryViolation;
+    use crate::loader::fetcher::DirectoryEntry;
+    use crate::loader::fetcher::Fetcher;
+    use crate::loader::fetcher::InMemoryFetcher;
+    use crate::loader::link::Link;
+    use crate::loader::registry::RegistryConfig;
+
+    use zksync_error_model::inner::Origin;
+
+    use super::build_model;
+    use super::error::ModelBuildingError;
+    use super::error::OverlayError;
+    use super::ModelBuildingOptions;
+
+    /// Counts the reads of every local file.
+    struct CountingFetcher {
+        inner: InMemoryFetcher,
+        reads: RefCell<BTreeMap<PathBuf, usize>>,
+    }
+
+    impl Fetcher for CountingFetcher {
+        fn read_local(&self, path: &Path) -> Result<String, FetchError> {
+            *self.reads.borrow_mut().entry(path.to_owned()).or_default() += 1;
+            self.inner.read_local(path)
+        }
+
+        fn list_local(&self, path: &Path) -> Result<Option<Vec<DirectoryEntry>>, FetchError> {
+            self.inner.list_local(path)
+        }
+
+        fn canonicalize_local(&self, path: &Path) -> PathBuf {
+            self.inner.canonicalize_local(path)
+        }
+
+        fn fetch_remote(&self, url: &str) -> Result<String, FetchError> {
+            self.inner.fetch_remote(url)
+        }
+    }
+
+    const ROOT: &str = r#"
+[[types]]
+name = "uint"
+description = "Unsigned 32-bit integer"
+
+[types.bindings.rust]
+name = "u32"
+path = ""
+
+[[types]]
+name = "string"
+description = "Unicode string"
+
+[types.bindings.rust]
+name = "String"
+path = ""
+
+[[domains]]
+domain_name = "core"
+domain_code = 1
+identifier_encoding = "core"
+
+[[components]]
+component_name = "sequencer"
+domain = "core"
+component_code = 1
+identifier_encoding = "seq"
+takeFrom = ["errors/a.toml"]
+"#;
+
+    fn component(take_from: &str, error: &str, code: u32) -> String {
+        format!(
+            r#"
+component_name = "sequencer"
+component_code = 1
+takeFrom = [{take_from}]
+
+[[errors]]
+name = "{error}"
+code = {code}
+message = "{error}"
+"#
+        )
+    }
+
+    fn build(fetcher: &InMemoryFetcher) -> Result<Vec<String>, ModelBuildingError> {
+        let model = build_model(
+            &Link::parse("root.toml").unwrap(),
+            &ModelBuildingOptions::default(),
+            fetcher,
+        )?;
+        Ok(model.domains["core"].components["sequencer"]
+            .errors
+            .iter()
+            .map(|e| e.name.clone())
+            .collect())
+    }
+
+    #[test]
+    fn take_from_chains() {
+        let fetcher = InMemoryFetcher::default()
+            .with_file("root.toml", ROOT)
+            .with_file("errors/a.toml", component(r#""./b.toml""#, "A", 1))
+            .with_file("errors/b.toml", component("", "B", 2));
+        assert_eq!(build(&fetcher).unwrap(), ["A", "B", "GenericError"]);
+
+        let fetcher = fetcher.with_file("errors/b.toml", component(r#""a.toml""#, "B", 2));
+        match build(&fetcher) {
+            Err(ModelBuildingError::IncludeCycle { include_stack }) => assert_eq!(
+                include_stack.to_string(),
+                "`root.toml` -> `errors/a.toml` -> `errors/b.toml` -> `errors/a.toml`"
+            ),
+            other => panic!("Expected a cycle, got {other:?}"),
+        }
+
+        // Paths are compared in the canonical form given by the fetcher.
+        let fetcher = fetcher.with_file(
+            "errors/b.toml",
+            component(r#""../errors/./a.toml""#, "B", 2),
+        );
+        assert!(matches!(
+            build(&fetcher),
+            Err(ModelBuildingError::IncludeCycle { .. })
+        ));
+    }
+
+    #[test]
+    fn origins() {
+        let fetcher = InMemoryFetcher::default()
+            .with_file("root.toml", ROOT)
+            .with_file("errors/a.toml", component("", "A", 1));
+        let model = build_model(
+            &Link::parse("root.toml").unwrap(),
+            &ModelBuildingOptions::default(),
+            &fetcher,
+        )
+        .unwrap();
+        let origin = |origin: &Option<Origin>| origin.as_ref().map(ToString::to_string);
+        let domain = &model.domains["core"];
+        let component = &domain.components["sequencer"];
+        assert_eq!(origin(&domain.meta.origin).unwrap(), "root.toml#/domains/0");
+        assert_eq!(
+            origin(&component.meta.origin).unwrap(),
+            "root.toml#/components/0"
+        );
+        let errors: Vec<_> = component.errors.iter().map(|e| origin(&e.origin)).collect();
+        assert_eq!(errors, [Some("errors/a.toml#/errors/0".into()), None]);
+        assert_eq!(
+            origin(&model.types["uint"].origin).unwrap(),
+            "root.toml#/types/0"
+        );
+    }
+
+    #[test]
+    fn domain_imports_and_includes() {
+        let root = r#"
+include = ["shared/types.toml"]
+
+[[domains]]
+domain_name = "core"
+domain_code = 1
+takeFrom = ["shared/core.toml"]
+"#;
+        let types = ROOT.split("[[domains]]").next().unwrap();
+        let core = r#"
+domain_name = "core"
+domain_code = 1
+identifier_encoding = "core"
+
+[[components]]
+component_name = "sequencer"
+component_code = 1
+identifier_encoding = "seq"
+
+[[components.errors]]
+name = "A"
+code = 1
+message = "A"
+"#;
+        let fetcher = InMemoryFetcher::default()
+            .with_file("root.toml", root)
+            .with_file("shared/types.toml", types)
+            .with_file("shared/core.toml", core);
+        assert_eq!(build(&fetcher).unwrap(), ["A", "GenericError"]);
+
+        let fetcher = fetcher.with_file(
+            "shared/core.toml",
+            core.replace("domain_code = 1", "domain_code = 2"),
+        );
+        let error = build(&fetcher).unwrap_err();
+        assert!(matches!(error, ModelBuildingError::ImportMergeError { .. }));
+        assert!(error
+            .to_string()
+            .starts_with("Error merging `shared/core.toml`"));
+        assert!(error.to_string().contains("into `root.toml`"));
+    }
+
+    #[test]
+    fn overlays() {
+        let overlay = r#"
+[[overlay]]
+error = "core-seq-1"
+bindings.python = { name = "ErrorA" }
+doc = { summary = "Summary", likely_causes = ["Cause"] }
+"#;
+        let fetcher = InMemoryFetcher::default()
+            .with_file("root.toml", ROOT)
+            .with_file(
+                "errors/a.toml",
+                component("", "A", 1)
+                    .replace("takeFrom", "identifier_encoding = \"seq\"\ntakeFrom"),
+            )
+            .with_file("overlay.toml", overlay);
+        let build = |fetcher: &InMemoryFetcher| {
+            build_model(
+                &Link::parse("root.toml").unwrap(),
+                &ModelBuildingOptions {
+                    additions: vec![Link::parse("overlay.toml").unwrap()],
+                    ..Default::default()
+                },
+                fetcher,
+            )
+        };
+
+        let model = build(&fetcher).unwrap();
+        let error = &model.domains["core"].components["sequencer"].errors[0];
+        assert_eq!(error.bindings["python"].name, "ErrorA");
+        let documentation = error.documentation.as_ref().unwrap();
+        assert_eq!(documentation.summary.as_deref(), Some("Summary"));
+        assert_eq!(documentation.likely_causes[0].cause, "Cause");
+
+        // Likely causes with the same text are merged.
+        let causes = r#"
+likely_causes = [
+  "Cause",
+  { cause = "Cause", fixes = ["Fix"], report = "Report" },
+  { cause = "Cause", fixes = ["Fix", "Other fix"] },
+]
+"#;
+        let fetcher = fetcher.with_file(
+            "overlay.toml",
+            overlay.replace(
+                "doc = { summary = \"Summary\", likely_causes = [\"Cause\"] }",
+                &format!("[overlay.doc]{causes}"),
+            ),
+        );
+        let model = build(&fetcher).unwrap();
+        let error = &model.domains["core"].components["sequencer"].errors[0];
+        let likely_causes = &error.documentation.as_ref().unwrap().likely_causes;
+        assert_eq!(likely_causes.len(), 1);
+        assert_eq!(likely_causes[0].fixes, ["Fix", "Other fix"]);
+        assert_eq!(likely_causes[0].report, "Report");
+
+        let fetcher = fetcher.with_file(
+            "overlay.toml",
+            overlay.replace(
+                "doc = { summary = \"Summary\", likely_causes = [\"Cause\"] }",
+                &format!(
+                    "[overlay.doc]{}",
+                    causes.replace("\"Other fix\"]", "\"Other fix\"], report = \"Other\"")
+                ),
+            ),
+        );
+        assert!(matches!(
+            build(&fetcher),
+            Err(ModelBuildingError::OverlayError {
+                inner: OverlayError::ConflictingLikelyCause { .. },
+                ..
+            })
+        ));
+
+        let fetcher = fetcher.with_file("overlay.toml", overlay.replace("seq-1", "seq-2"));
+        assert!(matches!(
+            build(&fetcher),
+            Err(ModelBuildingError::OverlayError {
+                inner: OverlayError::UnknownError { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn explicit_codes_are_taken_before_allocation() {
+        let root = ROOT
+            .replacen(
+                "identifier_encoding = \"core\"",
+                "identifier_encoding = \"core\"\ntakeFrom = [\"shared/core.toml\"]",
+                1,
+            )
+            .replace("errors/a.toml", "errors/a.json")
+            + "\n[[components.errors]]\nname = \"Auto\"\nmessage = \"Auto\"\n";
+        let imported = r#"
+domain_name = "core"
+domain_code = 1
+
+[[components]]
+component_name = "sequencer"
+component_code = 1
+identifier_encoding = "seq"
+
+[[components.errors]]
+name = "Imported"
+code = 2
+message = "Imported"
+"#;
+        let fetcher = InMemoryFetcher::default()
+            .with_file("root.toml", root)
+            .with_file(
+                "errors/a.json",
+                r#"[{ "name": "Explicit", "code": 1, "message": "Explicit" }]"#,
+            )
+            .with_file("shared/core.toml", imported);
+        let fetcher = CountingFetcher {
+            inner: fetcher,
+            reads: RefCell::default(),
+        };
+        let model = build_model(
+            &Link::parse("root.toml").unwrap(),
+            &ModelBuildingOptions::default(),
+            &fetcher,
+        )
+        .unwrap();
+        // Codes are collected from the files loaded for the translation.
+        assert!(
+            fetcher.reads.borrow().values().all(|reads| *reads == 1),
+            "{:?}",
+            fetcher.reads
+        );
+        let codes: Vec<_> = model.domains["core"].components["sequencer"]
+            .errors
+            .iter()
+            .map(|e| (e.name.as_str(), e.code))
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                ("Auto", 3),
+                ("Explicit", 1),
+                ("Imported", 2),
+                ("GenericError", 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn codes_run_out() {
+        let auto = "\n[[errors]]\nname = \"Auto\"\nmessage = \"Auto\"\n";
+        let reserved = "reserved = [{ start = 5, end = 4294967295 }]\n";
+        for definitions in [
+            component("", "Last", u32::MAX) + auto,
+            component("", "Last", 999) + auto,
+            format!("{reserved}{}{auto}", component("", "Last", 4)),
+        ] {
+            let fetcher = InMemoryFetcher::default()
+                .with_file("root.toml", ROOT)
+                .with_file("errors/a.toml", definitions);
+            // The error is reported in the file defining `Auto`.
+            let error = build(&fetcher).unwrap_err().to_string();
+            assert!(
+                error.contains("No code is left for the error `Auto`"),
+                "{error}"
+            );
+        }
+    }
+
+    #[test]
+    fn codes_are_allocated_and_recorded() {
+        let path = std::env::temp_dir().join(format!("errors-{}.lock", std::process::id()));
+        let registry = RegistryConfig {
+            path: Some(path.clone()),
+            update: true,
+            force: false,
+        };
+        let errors = |names: &[&str]| {
+            let errors: String = names
+                .iter()
+                .map(|name| format!("[[errors]]\nname = \"{name}\"\nmessage = \"{name}\"\n"))
+                .collect();
+            format!("component_name = \"sequencer\"\ncomponent_code = 1\nreserved = [{{ start = 2, end = 3 }}]\n{errors}")
+        };
+        let build = |fetcher: &InMemoryFetcher, registry: &RegistryConfig| {
+            build_model(
+                &Link::parse("root.toml").unwrap(),
+                &ModelBuildingOptions {
+                    registry: registry.clone(),
+                    ..Default::default()
+                },
+                fetcher,
+            )
+            .map(|model| {
+                model.domains["core"].components["sequencer"]
+                    .errors
+                    .iter()
+                    .map(|e| (e.name.clone(), e.code))
+                    .collect::<Vec<_>>()
+            })
+        };
+        let codes = |pairs: &[(&str, u32)]| -> Vec<(String, u32)> {
+            pairs.iter().map(|(n, c)| (n.to_string(), *c)).collect()
+        };
+
+        let fetcher = InMemoryFetcher::default()
+            .with_file("root.toml", ROOT)
+            .with_file("errors/a.toml", errors(&["A", "B"]));
+        assert_eq!(
+            build(&fetcher, &registry).unwrap(),
+            codes(&[("A", 1), ("B", 4), ("GenericError", 0)])
+        );
+
+        // Fields that may be absent extend the recorded errors.
+        let field = |optional: bool| {
+            format!("fields = [{{ name = \"retries\", type = \"uint\", optional = {optional} }}]\n")
+        };
+        let extended = fetcher
+            .clone()
+            .with_file("errors/a.toml", errors(&["A", "B"]) + &field(true));
+        assert!(build(&extended, &registry).is_ok());
+        let reshaped = fetcher
+            .clone()
+            .with_file("errors/a.toml", errors(&["A", "B"]) + &field(false));
+        assert!(matches!(
+            build(&reshaped, &registry),
+            Err(ModelBuildingError::LoadError(LoadError::RegistryError(
+                RegistryError::Violations { violations, .. }
+            ))) if matches!(violations.as_slice(), [RegistryViolation::Reshaped { .. }])
+        ));
+
+        let fetcher = fetcher.with_file("errors/a.toml", errors(&["C", "B"]) + &field(true));
+        let result = build(&fetcher, &registry);
+        assert!(
+            matches!(
+                &result,
+                Err(ModelBuildingError::LoadError(LoadError::RegistryError(
+                    RegistryError::Violations { violations, .. }
+                ))) if matches!(
+                    violations.as_slice(),
+                    [RegistryViolation::Removed { identifier, .. }] if identifier == "[core-seq-1]"
+                )
+            ),
+            "{result:?}"
+        );
+
+        let forced = RegistryConfig {
+            force: true,
+            ..registry.clone()
+        };
+        assert_eq!(
+            build(&fetcher, &forced).unwrap(),
+            codes(&[("C", 5), ("B", 4), ("GenericError", 0)])
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+//!
+//! Overlays add documentation and bindings to the errors of the merged model,
+//! without changing its structure.
+//!
+
+use zksync_error_model::inner::ErrorDescription;
+use zksync_error_model::inner::ErrorDocumentation;
+use zksync_error_model::inner::Model;
+use zksync_error_model::inner::TargetLanguageType;
+use zksync_error_model::merger::merge_likely_causes;
+use zksync_error_model::merger::Conflicts;
+use zksync_error_model::merger::MergeMode;
+
+use crate::description::ErrorOverlay;
+use crate::description::Overlay;
+use crate::description::OverlayDocumentation;
+
+use super::error::OverlayError;
+use super::translate_likely_cause;
+
+pub(super) fn apply_overlay(model: &mut Model, overlay: &Overlay) -> Result<(), OverlayError> {
+    for error_overlay in &overlay.overlay {
+        let identifier = normalize_identifier(&error_overlay.error);
+        let error = model
+            .domains
+            .values_mut()
+            .flat_map(|domain| domain.components.values_mut())
+            .flat_map(|component| component.errors.iter_mut())
+            .find(|error| error.get_identifier().to_string() == identifier)
+            .ok_or_else(|| OverlayError::UnknownError {
+                identifier: identifier.clone(),
+            })?;
+        apply_error_overlay(error, error_overlay, &identifier)?;
+    }
+    Ok(())
+}
+
+/// Identifiers are accepted with or without the surrounding brackets.
+pub(super) fn normalize_identifier(identifier: &str) -> String {
+    let identifier = identifier.trim();
+    let bare = identifier
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(identifier);
+    format!("[{bare}]")
+}
+
+fn apply_error_overlay(
+    error: &mut ErrorDescription,
+    overlay: &ErrorOverlay,
+    identifier: &str,
+) -> Result<(), OverlayError> {
+    let ErrorOverlay {
+        error: _,
+        bindings,
+        doc,
+    } = overlay;
+
+    for (language, binding) in bindings {
+        match error.bindings.get(language) {
+            Some(existing) if existing.name != binding.name => {
+                return Err(OverlayError::ConflictingBinding {
+                    identifier: identifier.to_owned(),
+                    language: language.clone(),
+                    existing: existing.name.clone(),
+                    new: binding.name.clone(),
+                })
+            }
+            Some(_) => {}
+            None => {
+                error.bindings.insert(
+                    language.clone(),
+                    TargetLanguageType {
+                        name: binding.name.clone(),
+                    },
+                );
+            }
+        }
+    }
+
+    if let Some(OverlayDocumentation {
+        description,
+        summary,
+        likely_causes,
+    }) = doc
+    {
+        let documentation = error
+            .documentation
+            .get_or_insert_with(ErrorDocumentation::default);
+        let add = |field: &'static str, existing: &mut String, new: &Option<String>| match new {
+            Some(new) if existing.is_empty() => {
+                *existing = new.clone();
+                Ok(())
+            }
+            Some(new) if existing != new => Err(OverlayError::ConflictingDocumentation {
+                identifier: identifier.to_owned(),
+                field,
+            }),
+            _ => Ok(()),
+        };
+        add("description", &mut documentation.description, description)?;
+        add(
+            "summary",
+            documentation.summary.get_or_insert_with(String::new),
+            summary,
+        )?;
+        if documentation.summary.as_ref().is_some_and(String::is_empty) {
+            documentation.summary = None;
+        }
+        let likely_causes = likely_causes
+            .iter()
+            .map(translate_likely_cause)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|inner| OverlayError::InvalidLikelyCause {
+                identifier: identifier.to_owned(),
+                inner: Box::new(inner),
+            })?;
+        merge_likely_causes(
+            &format!("the error {identifier}"),
+            &mut documentation.likely_causes,
+            &likely_causes,
+            &mut Conflicts::new(MergeMode::Strict),
+        )
+        .map_err(|inner| OverlayError::ConflictingLikelyCause {
+            identifier: identifier.to_owned(),
+            inner: Box::new(inner),
+        })?;
+    }
+    Ok(())
+}
@@ -1,35 +1,225 @@
+//!
+//! Discovery of the files with error definitions provided by cargo packages.
+//!
+//! Packages list these files in their manifests, paths relative to the package
+//! root:
+//!
+//! ```toml
+//! [package.metadata.zksync_error_codegen]
+//! files = ["errors/", "extra.toml"]
+//! ```
+//!
+//! Directories provide all JSON and TOML files directly inside them. The
+//! workspace may list its own files in `[workspace.metadata.zksync_error_codegen]`,
+//! available through links with an empty package name: `cargo://@@errors.json`.
+//! The key `json_files` is accepted as an alias of `files`.
+//!
+
+use std::cell::OnceCell;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use cargo_metadata::Metadata;
 use cargo_metadata::MetadataCommand;
+use cargo_metadata::PackageId;
 
-use super::{resolution::ResolutionContext, CollectionFile};
+use super::error::CargoError;
+use super::fetcher::FilesystemFetcher;
+use super::resolution::CargoPackage;
+use super::resolution::ResolutionContext;
+use super::resolution::ResolvedLink;
 
 const METADATA_CATEGORY: &str = "zksync_error_codegen";
+const METADATA_KEYS: [&str; 2] = ["files", "json_files"];
+
+#[derive(Clone, Debug, Default)]
+pub struct CargoConfig {
+    /// Manifest of the workspace or package. By default, cargo looks for it
+    /// starting from the current working directory.
+    pub manifest_path: Option<PathBuf>,
+    /// Directory to run cargo in, the current working directory by default.
+    /// Relative manifest paths are taken relative to it.
+    pub directory: Option<PathBuf>,
+    /// Only consider this package and its dependencies.
+    pub package: Option<String>,
+}
+
+/// Packages of the cargo workspace, discovered when a cargo link is resolved
+/// for the first time.
+#[derive(Debug, Default)]
+pub struct CargoWorkspace {
+    config: CargoConfig,
+    context: OnceCell<ResolutionContext>,
+}
+
+impl CargoWorkspace {
+    pub fn new(config: CargoConfig) -> Self {
+        Self {
+            config,
+            context: OnceCell::new(),
+        }
+    }
+
+    pub fn context(&self) -> Result<&ResolutionContext, CargoError> {
+        if let Some(context) = self.context.get() {
+            return Ok(context);
+        }
+        let context = get_resolution_context(&self.config)?;
+        Ok(self.context.get_or_init(|| context))
+    }
+}
 
-pub fn get_resolution_context() -> ResolutionContext {
-    let metadata = MetadataCommand::new()
-        .exec()
-        .expect("Failed to fetch cargo metadata");
+pub fn get_resolution_context(config: &CargoConfig) -> Result<ResolutionContext, CargoError> {
+    let mut command = MetadataCommand::new();
+    if let Some(directory) = &config.directory {
+        command.current_dir(directory);
+    }
+    if let Some(manifest_path) = &config.manifest_path {
+        command.manifest_path(manifest_path);
+    }
+    let metadata = command.exec()?;
 
-    let mut context = ResolutionContext::default();
+    let included = match &config.package {
+        Some(package) => Some(dependency_closure(&metadata, package)?),
+        None => None,
+    };
 
+    let workspace_root: PathBuf = metadata.workspace_root.clone().into();
+    let mut packages = vec![CargoPackage {
+        name: String::new(),
+        version: None,
+        files: listed_files(&metadata.workspace_metadata, &workspace_root)?,
+        root: workspace_root,
+    }];
     for pkg in &metadata.packages {
-        if let Some(codegen_meta) = pkg.metadata.get(METADATA_CATEGORY) {
-            if let Some(json_files) = codegen_meta.get("json_files").and_then(|x| x.as_array()) {
-                for path_value in json_files {
-                    if let Some(rel_path) = path_value.as_str() {
-                        let package_root = pkg
-                            .manifest_path
-                            .parent() // removing Cargo.toml
-                            .unwrap();
-                        let absolute_path = package_root.join(rel_path).into();
-                        context.files.push(CollectionFile {
-                            package: pkg.name.to_owned(),
-                            absolute_path,
-                        });
-                    }
+        if included.as_ref().is_some_and(|ids| !ids.contains(&pkg.id)) {
+            continue;
+        }
+        let root: PathBuf = pkg
+            .manifest_path
+            .parent() // removing Cargo.toml
+            .expect("Manifest path always has a parent")
+            .into();
+        packages.push(CargoPackage {
+            name: pkg.name.to_owned(),
+            version: Some(pkg.version.clone()),
+            files: listed_files(&pkg.metadata, &root)?,
+            root,
+        });
+    }
+    packages.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| b.version.cmp(&a.version)));
+
+    Ok(ResolutionContext { packages })
+}
+
+/// Files listed in the `metadata` of a package or workspace, with the
+/// directories expanded.
+fn listed_files(metadata: &serde_json::Value, root: &Path) -> Result<Vec<PathBuf>, CargoError> {
+    let Some(codegen_meta) = metadata.get(METADATA_CATEGORY) else {
+        return Ok(vec![]);
+    };
+    let mut files = vec![];
+    for key in METADATA_KEYS {
+        let Some(paths) = codegen_meta.get(key).and_then(|x| x.as_array()) else {
+            continue;
+        };
+        for rel_path in paths.iter().filter_map(|path_value| path_value.as_str()) {
+            // Packages are always in the file system.
+            let location = ResolvedLink::LocalPath(root.join(rel_path));
+            for location in location.expand(&FilesystemFetcher)? {
+                if let ResolvedLink::LocalPath(path) = location {
+                    files.push(path);
                 }
             }
         }
     }
+    Ok(files)
+}
+
+/// Identifiers of the package named `package` and of all its dependencies.
+fn dependency_closure(
+    metadata: &Metadata,
+    package: &str,
+) -> Result<BTreeSet<PackageId>, CargoError> {
+    let root = metadata
+        .workspace_packages()
+        .into_iter()
+        .chain(&metadata.packages)
+        .find(|pkg| pkg.name == package)
+        .ok_or_else(|| CargoError::UnknownPackage {
+            package: package.to_owned(),
+        })?;
+
+    let mut closure = BTreeSet::new();
+    let mut queue = vec![root.id.clone()];
+    while let Some(id) = queue.pop() {
+        if !closure.insert(id.clone()) {
+            continue;
+        }
+        let node = metadata
+            .resolve
+            .as_ref()
+            .and_then(|resolve| resolve.nodes.iter().find(|node| node.id == id));
+        if let Some(node) = node {
+            queue.extend(node.dependencies.iter().cloned());
+        }
+    }
+    Ok(closure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_resolution_context;
+    use super::CargoConfig;
+    use crate::loader::link::Link;
 
-    context
+    #[test]
+    fn workspace_and_package_files() {
+        let directory =
+            std::env::temp_dir().join(format!("zksync-error-cargo-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(directory.join("errors")).unwrap();
+        std::fs::create_dir_all(directory.join("member/src")).unwrap();
+        let files = [
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"member\"]\n\n[workspace.metadata.zksync_error_codegen]\nfiles = [\"errors/\"]\n",
+            ),
+            (
+                "member/Cargo.toml",
+                "[package]\nname = \"member\"\nversion = \"1.2.3\"\n\n[package.metadata.zksync_error_codegen]\njson_files = [\"errors.json\"]\nfiles = [\"extra.toml\"]\n",
+            ),
+            ("member/src/lib.rs", ""),
+            ("errors/b.toml", ""),
+            ("errors/a.json", ""),
+        ];
+        for (path, contents) in files {
+            std::fs::write(directory.join(path), contents).unwrap();
+        }
+
+        let context = get_resolution_context(&CargoConfig {
+            manifest_path: Some(directory.join("Cargo.toml")),
+            directory: None,
+            package: Some("member".into()),
+        })
+        .unwrap();
+        let find = |link: &str| {
+            context
+                .find_file(&Link::parse(link).unwrap())
+                .map(|file| file.absolute_path)
+        };
+
+        assert_eq!(
+            find("cargo://@@b.toml"),
+            Some(directory.join("errors/b.toml"))
+        );
+        assert_eq!(
+            find("cargo://member@^1.2@@extra.toml"),
+            Some(directory.join("member/extra.toml"))
+        );
+        assert_eq!(find("cargo://member@^2@@extra.toml"), None);
+        assert_eq!(find("cargo://member@@missing.json"), None);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
 }
@@ -0,0 +1,101 @@
+//!
+//! Positions in the files with error definitions, shown to the user along with
+//! the offending line.
+//!
+
+/// Position in a file, displayed with the line it points to in the style of
+/// rustc:
+///
+/// ```text
+///  --> errors.json:3:18
+///   |
+/// 3 |   "domain_code": "one",
+///   |                  ^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Excerpt {
+    pub file: String,
+    /// One-based line number.
+    pub line: usize,
+    /// One-based column, in characters.
+    pub column: usize,
+    /// The line, without the line terminator.
+    pub text: String,
+}
+
+impl Excerpt {
+    /// Excerpt pointing at the byte `offset` of `contents`.
+    pub fn at_offset(file: &str, contents: &str, offset: usize) -> Self {
+        let mut offset = offset.min(contents.len());
+        while !contents.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line_start = contents[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = contents[offset..]
+            .find('\n')
+            .map_or(contents.len(), |i| offset + i);
+        Self {
+            file: file.to_owned(),
+            line: contents[..offset].matches('\n').count() + 1,
+            column: contents[line_start..offset].chars().count() + 1,
+            text: contents[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+        }
+    }
+
+    /// Excerpt pointing at the one-based `line` and `column` of `contents`,
+    /// with the column counted in bytes.
+    pub fn at_line(file: &str, contents: &str, line: usize, column: usize) -> Self {
+        let line_start: usize = contents
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum();
+        let line_length = contents[line_start..]
+            .find('\n')
+            .unwrap_or(contents.len() - line_start);
+        let column = column.saturating_sub(1).min(line_length);
+        Self::at_offset(file, contents, line_start + column)
+    }
+}
+
+impl std::fmt::Display for Excerpt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        // Tabs are kept so that the marker stays under the pointed character.
+        let indent: String = self
+            .text
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        writeln!(f, "{gutter}--> {}:{}:{}", self.file, self.line, self.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{number} | {}", self.text)?;
+        write!(f, "{gutter} | {indent}^")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Excerpt;
+
+    #[test]
+    fn excerpts() {
+        let contents = "{\n  \"domain_name\": \"core\",\n  \"domain_code\": \"one\"\n}\n";
+        let excerpt = Excerpt::at_line("core.json", contents, 3, 18);
+        assert_eq!(excerpt, Excerpt::at_offset("core.json", contents, 44));
+        assert_eq!(
+            excerpt.to_string(),
+            [
+                " --> core.json:3:18",
+                "  |",
+                "3 |   \"domain_code\": \"one\"",
+                "  |                  ^",
+            ]
+            .join("\n")
+        );
+    }
+}
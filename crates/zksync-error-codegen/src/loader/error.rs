@@ -1,10 +1,20 @@
 use super::builder::error::ModelBuildingError;
+use super::diagnostic::Excerpt;
 use super::link::Link;
+use super::registry::RegisteredError;
 use super::resolution::error::ResolutionError;
 
 #[derive(Debug, thiserror::Error)]
 pub enum LinkError {
     InvalidLinkFormat(String),
+    InvalidDigest(String),
+    InvalidVersionRequirement(String),
+    InvalidPattern(String),
+    PinnedPattern(String),
+    FailedListing {
+        directory: String,
+        inner: FetchError,
+    },
     FailedResolution(ResolutionError),
 }
 
@@ -13,6 +23,16 @@ impl std::fmt::Display for LinkError {
         match self {
             LinkError::InvalidLinkFormat(link) =>
                 f.write_fmt(format_args!("Link `{link}` has an invalid format. Expected `{}://<crate_name>{}<filename-with-extension>`.", Link::CARGO_FORMAT_PREFIX, Link::PACKAGE_SEPARATOR)),
+            LinkError::InvalidVersionRequirement(link) =>
+                f.write_fmt(format_args!("Link `{link}` has an invalid version requirement. Expected `{}://<crate_name>{}<version requirement>{}<filename-with-extension>`.", Link::CARGO_FORMAT_PREFIX, Link::VERSION_SEPARATOR, Link::PACKAGE_SEPARATOR)),
+            LinkError::InvalidDigest(link) =>
+                f.write_fmt(format_args!("Link `{link}` has an invalid digest. Expected `{}<64 hexadecimal digits>`.", Link::DIGEST_FRAGMENT)),
+            LinkError::InvalidPattern(pattern) =>
+                f.write_fmt(format_args!("Invalid file pattern `{pattern}`.")),
+            LinkError::PinnedPattern(link) =>
+                f.write_fmt(format_args!("Link `{link}` matches several files, and can not be pinned to a digest.")),
+            LinkError::FailedListing { directory, inner } =>
+                f.write_fmt(format_args!("Failed to list the files in `{directory}`: {inner}")),
             LinkError::FailedResolution(r) => r.fmt(f),
         }
     }
@@ -24,13 +44,40 @@ pub enum FileFormatError {
     ExpectedFullGotDomain { origin: Link },
     #[error("File `{origin}` contains just an error component description, but a master error database should describe at least one domain and one component.")]
     ExpectedFullGotComponent { origin: Link },
+    #[error("File `{origin}` contains an overlay, but a master error database should describe at least one domain and one component.")]
+    ExpectedFullGotOverlay { origin: Link },
     #[error("File `{origin}` contains just an array of errors, but a master error database should describe at least one domain and one component.")]
     ExpectedFullGotErrors { origin: Link },
-    #[error("Error parsing error description: {inner}.\n File contents: \n {contents}")]
+    #[error("{referenced_by} refers to an undefined domain `{domain}`.")]
+    UndefinedDomain {
+        domain: String,
+        referenced_by: String,
+    },
+    #[error("{referenced_by} refers to an undefined component `{component}`.")]
+    UndefinedComponent {
+        component: String,
+        referenced_by: String,
+    },
+    #[error("{referenced_by} refers to a component `{component}` defined in several domains: {domains:?}. Specify the domain explicitly.")]
+    AmbiguousComponent {
+        component: String,
+        referenced_by: String,
+        domains: Vec<String>,
+    },
+    #[error("Error `{error}` does not name its component, and there is no preceding error to take it from.")]
+    MissingComponentReference { error: String },
+    #[error("Failed to parse `{file}` as {expected}: {message}{}", excerpt.as_ref().map(|e| format!("\n{e}")).unwrap_or_default())]
     ParseError {
-        contents: String,
-        #[source]
-        inner: Box<dyn std::error::Error>,
+        file: String,
+        expected: String,
+        message: String,
+        excerpt: Option<Box<Excerpt>>,
+    },
+    #[error("File `{file}` does not match the schema of {expected}:{}", violations.iter().map(|v| format!("\n- {v}")).collect::<String>())]
+    SchemaViolation {
+        file: String,
+        expected: String,
+        violations: Vec<crate::schema::SchemaViolation>,
     },
 }
 
@@ -40,7 +87,13 @@ pub enum LoadError {
     IOError(#[from] std::io::Error),
 
     #[error(transparent)]
-    NetworkError(#[from] reqwest::Error),
+    FetchError(#[from] FetchError),
+
+    #[error(transparent)]
+    RemoteFileError(#[from] RemoteFileError),
+
+    #[error(transparent)]
+    RegistryError(#[from] RegistryError),
 
     #[error(transparent)]
     FileFormatError(#[from] FileFormatError),
@@ -51,6 +104,16 @@ pub enum LoadError {
     #[error(transparent)]
     ResolutionError(#[from] ResolutionError),
 
+    #[error(transparent)]
+    CargoError(#[from] CargoError),
+
+    #[error("Contents of `{link}` do not match its pinned digest: expected SHA-256 {expected}, got {actual}.")]
+    DigestMismatch {
+        link: Box<Link>,
+        expected: String,
+        actual: String,
+    },
+
     #[error("Missing file {0}")]
     MissingFileError(String),
 
@@ -63,3 +126,122 @@ impl From<ModelBuildingError> for LoadError {
         Self::ModelBuildingError(Box::new(v))
     }
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum CargoError {
+    #[error("Failed to fetch cargo metadata: {0}")]
+    MetadataError(#[from] cargo_metadata::Error),
+    #[error("Package `{package}` is not found in the cargo metadata.")]
+    UnknownPackage { package: String },
+    #[error(transparent)]
+    LinkError(#[from] LinkError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FetchError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    NetworkError(#[from] reqwest::Error),
+    #[error("File `{location}` was not found.")]
+    NotFound { location: String },
+    #[error("File `{location}` can not be accessed by this fetcher.")]
+    Unsupported { location: String },
+    #[error("Link scheme `{scheme}://` is not registered.")]
+    UnknownScheme { scheme: String },
+    #[error("Address `{address}` does not match the format `{scheme}://{expected}`.")]
+    InvalidAddress {
+        scheme: String,
+        address: String,
+        expected: String,
+    },
+    #[error("Environment variable `{variable}` is not set.")]
+    MissingEnvironmentVariable { variable: String },
+    #[error(transparent)]
+    RevisionError(#[from] RevisionError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RegistryError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("Registry `{}` is malformed: {inner}", path.display())]
+    InvalidRegistry {
+        path: std::path::PathBuf,
+        #[source]
+        inner: serde_json::Error,
+    },
+    #[error(
+        "Definitions contradict the registry `{}`; force the update to overwrite it. Found {} problem(s):{}",
+        path.display(),
+        violations.len(),
+        violations.iter().map(|v| format!("\n - {v}")).collect::<String>()
+    )]
+    Violations {
+        path: std::path::PathBuf,
+        violations: Vec<RegistryViolation>,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RegistryViolation {
+    #[error("Error {identifier} {recorded} was removed; retire it to keep its code reserved.")]
+    Removed {
+        identifier: String,
+        recorded: RegisteredError,
+    },
+    #[error("Error {identifier} is recorded as {recorded}, but is now defined as {actual}.")]
+    Reshaped {
+        identifier: String,
+        recorded: RegisteredError,
+        actual: RegisteredError,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteFileError {
+    #[error(transparent)]
+    FetchError(#[from] FetchError),
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error("Lockfile `{}` is malformed: {inner}", path.display())]
+    InvalidLockfile {
+        path: std::path::PathBuf,
+        #[source]
+        inner: serde_json::Error,
+    },
+    #[error(
+        "File `{url}` is not recorded in the lockfile, and can not be fetched in offline mode."
+    )]
+    NotLocked { url: String },
+    #[error("File `{url}` with SHA-256 {sha256} is missing from the cache, and can not be fetched in offline mode.")]
+    NotCached { url: String, sha256: String },
+    #[error(
+        "Contents of `{url}` do not match the lockfile: expected SHA-256 {expected}, got {actual}."
+    )]
+    HashMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RevisionError {
+    #[error("Failed to run `{command}`: {inner}")]
+    CommandFailed {
+        command: String,
+        #[source]
+        inner: std::io::Error,
+    },
+    #[error("`{command}` exited with {status}: {stderr}")]
+    CommandUnsuccessful {
+        command: String,
+        status: std::process::ExitStatus,
+        stderr: String,
+    },
+    #[error("Git {kind} `{value}` may not start with `-`.")]
+    OptionLike { kind: &'static str, value: String },
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+}
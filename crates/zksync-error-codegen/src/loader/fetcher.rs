@@ -0,0 +1,195 @@
+//!
+//! Access to the contents of the files with error definitions, local or in the
+//! network.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::error::FetchError;
+
+/// Entry of a local directory.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum DirectoryEntry {
+    File(PathBuf),
+    Directory(PathBuf),
+}
+
+/// Source of the contents of the files with error definitions. Links are
+/// resolved before fetching, so implementations only deal with local paths,
+/// URLs, and addresses in custom link schemes.
+pub trait Fetcher {
+    /// Contents of the local file at `path`.
+    fn read_local(&self, path: &Path) -> Result<String, FetchError>;
+
+    /// Entries directly inside the local directory at `path`, or `None` if
+    /// there is no such directory. Patterns in the links to local files are
+    /// expanded through it. Lists the file system by default.
+    fn list_local(&self, path: &Path) -> Result<Option<Vec<DirectoryEntry>>, FetchError> {
+        list_directory(path)
+    }
+
+    /// Canonical form of the local `path`, the same for all spellings of the
+    /// path to a file. Resolves the path in the file system by default.
+    fn canonicalize_local(&self, path: &Path) -> PathBuf {
+        std::fs::canonicalize(path).unwrap_or_else(|_| normalize(path))
+    }
+
+    /// Contents of the file at `url`.
+    fn fetch_remote(&self, url: &str) -> Result<String, FetchError>;
+
+    /// Contents of the file at `address` in a link with a custom `scheme`.
+    fn fetch_custom(&self, scheme: &str, _address: &str) -> Result<String, FetchError> {
+        Err(FetchError::UnknownScheme {
+            scheme: scheme.to_owned(),
+        })
+    }
+
+    /// Address of `path` relative to the file at `address` in a link with a
+    /// custom `scheme`, if this scheme supports relative links.
+    fn join_custom(&self, _scheme: &str, _address: &str, _path: &str) -> Option<String> {
+        None
+    }
+}
+
+/// `path` without `.` components and with `..` applied where possible.
+fn normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(result.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                result.pop();
+            }
+            component => result.push(component),
+        }
+    }
+    result
+}
+
+fn list_directory(path: &Path) -> Result<Option<Vec<DirectoryEntry>>, FetchError> {
+    // The empty path stands for the current directory.
+    let directory = if path.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        path
+    };
+    if !directory.is_dir() {
+        return Ok(None);
+    }
+    let mut entries = vec![];
+    for entry in std::fs::read_dir(directory)? {
+        let path = path.join(entry?.file_name());
+        entries.push(if path.is_dir() {
+            DirectoryEntry::Directory(path)
+        } else {
+            DirectoryEntry::File(path)
+        });
+    }
+    Ok(Some(entries))
+}
+
+/// Reads local files, refuses to access the network.
+#[derive(Clone, Debug, Default)]
+pub struct FilesystemFetcher;
+
+impl Fetcher for FilesystemFetcher {
+    fn read_local(&self, path: &Path) -> Result<String, FetchError> {
+        log::info!("Trying to read local file: {}", path.display());
+        Ok(std::fs::read_to_string(path)?)
+    }
+
+    fn fetch_remote(&self, url: &str) -> Result<String, FetchError> {
+        Err(FetchError::Unsupported {
+            location: url.to_owned(),
+        })
+    }
+}
+
+/// Reads local files and fetches remote files over HTTP. Provide a configured
+/// client through [`HttpFetcher::with_client`] to set timeouts or
+/// authorization headers.
+#[derive(Clone, Debug, Default)]
+pub struct HttpFetcher {
+    client: reqwest::blocking::Client,
+}
+
+impl HttpFetcher {
+    pub fn with_client(client: reqwest::blocking::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn read_local(&self, path: &Path) -> Result<String, FetchError> {
+        FilesystemFetcher.read_local(path)
+    }
+
+    fn fetch_remote(&self, url: &str) -> Result<String, FetchError> {
+        log::info!("Trying to fetch file from network: {url}");
+        let response = self.client.get(url).send()?.error_for_status()?;
+        Ok(response.text()?)
+    }
+}
+
+/// Serves files from memory, indexed by their paths or URLs.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryFetcher {
+    pub files: BTreeMap<String, String>,
+}
+
+impl InMemoryFetcher {
+    pub fn with_file(mut self, location: impl Into<String>, contents: impl Into<String>) -> Self {
+        self.files.insert(location.into(), contents.into());
+        self
+    }
+
+    fn get(&self, location: &str) -> Result<String, FetchError> {
+        self.files
+            .get(location)
+            .cloned()
+            .ok_or_else(|| FetchError::NotFound {
+                location: location.to_owned(),
+            })
+    }
+}
+
+impl Fetcher for InMemoryFetcher {
+    fn read_local(&self, path: &Path) -> Result<String, FetchError> {
+        self.get(&path.to_string_lossy())
+    }
+
+    /// Directories are the prefixes of the paths of the files, up to a `/`;
+    /// URLs are not listed.
+    fn list_local(&self, path: &Path) -> Result<Option<Vec<DirectoryEntry>>, FetchError> {
+        let directory = normalize(path);
+        let prefix = match directory.to_string_lossy().as_ref() {
+            "" => String::new(),
+            directory => format!("{directory}/"),
+        };
+        let entries: BTreeSet<_> = self
+            .files
+            .keys()
+            .filter(|location| !location.contains("://"))
+            .filter_map(|location| location.strip_prefix(&prefix))
+            .map(|rest| match rest.split_once('/') {
+                Some((directory, _)) => DirectoryEntry::Directory(path.join(directory)),
+                None => DirectoryEntry::File(path.join(rest)),
+            })
+            .collect();
+        Ok((!entries.is_empty()).then(|| entries.into_iter().collect()))
+    }
+
+    fn canonicalize_local(&self, path: &Path) -> PathBuf {
+        normalize(path)
+    }
+
+    fn fetch_remote(&self, url: &str) -> Result<String, FetchError> {
+        self.get(url)
+    }
+}
@@ -0,0 +1,241 @@
+//!
+//! Rebuilds the tree of a master error database from its flat layout, where
+//! components and errors refer to their parents by name.
+//!
+
+use crate::description::Domain;
+use crate::description::FlatComponent;
+use crate::description::FlatDomain;
+use crate::description::FlatError;
+use crate::description::FlatRoot;
+use crate::description::Root;
+
+use super::error::FileFormatError;
+use super::pointer::locate_component;
+use super::pointer::locate_types;
+
+/// Position of a component in the tree: indices of its domain and of the
+/// component inside this domain.
+type ComponentPosition = (usize, usize);
+
+fn find_component(
+    root: &Root,
+    domain: Option<&str>,
+    component: &str,
+    referenced_by: &str,
+) -> Result<ComponentPosition, FileFormatError> {
+    if let Some(domain) = domain {
+        if !root.domains.iter().any(|d| d.domain_name == domain) {
+            return Err(FileFormatError::UndefinedDomain {
+                domain: domain.to_owned(),
+                referenced_by: referenced_by.to_owned(),
+            });
+        }
+    }
+
+    let candidates: Vec<ComponentPosition> = root
+        .domains
+        .iter()
+        .enumerate()
+        .filter(|(_, d)| domain.is_none_or(|domain| d.domain_name == domain))
+        .flat_map(|(domain_index, d)| {
+            d.components
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.component_name == component)
+                .map(move |(component_index, _)| (domain_index, component_index))
+        })
+        .collect();
+
+    match candidates.as_slice() {
+        [] => Err(FileFormatError::UndefinedComponent {
+            component: component.to_owned(),
+            referenced_by: referenced_by.to_owned(),
+        }),
+        [position] => Ok(*position),
+        _ => Err(FileFormatError::AmbiguousComponent {
+            component: component.to_owned(),
+            referenced_by: referenced_by.to_owned(),
+            domains: candidates
+                .iter()
+                .map(|(domain_index, _)| root.domains[*domain_index].domain_name.clone())
+                .collect(),
+        }),
+    }
+}
+
+fn translate_domain(index: usize, domain: FlatDomain) -> Domain {
+    let FlatDomain {
+        domain_name,
+        domain_code,
+        identifier_encoding,
+        description,
+        bindings,
+        takeFrom,
+    } = domain;
+    Domain {
+        domain_name,
+        domain_code,
+        identifier_encoding,
+        description,
+        components: vec![],
+        bindings,
+        takeFrom,
+        pointer: format!("/domains/{index}"),
+    }
+}
+
+pub fn unflatten(flat: FlatRoot) -> Result<Root, FileFormatError> {
+    let FlatRoot {
+        include,
+        types,
+        domains,
+        components,
+        errors,
+        code_layout,
+        lints,
+    } = flat;
+
+    let mut result = Root {
+        include,
+        types,
+        domains: domains
+            .into_iter()
+            .enumerate()
+            .map(|(index, domain)| translate_domain(index, domain))
+            .collect(),
+        code_layout,
+        lints,
+    };
+    locate_types(&mut result.types);
+
+    for (
+        index,
+        FlatComponent {
+            domain,
+            mut component,
+        },
+    ) in components.into_iter().enumerate()
+    {
+        locate_component(&mut component, format!("/components/{index}"));
+        let parent = result
+            .domains
+            .iter_mut()
+            .find(|d| d.domain_name == domain)
+            .ok_or_else(|| FileFormatError::UndefinedDomain {
+                domain: domain.clone(),
+                referenced_by: format!("Component `{}`", component.component_name),
+            })?;
+        parent.components.push(component);
+    }
+
+    let mut previous: Option<ComponentPosition> = None;
+    for (
+        index,
+        FlatError {
+            domain,
+            component,
+            mut error,
+        },
+    ) in errors.into_iter().enumerate()
+    {
+        error.pointer = format!("/errors/{index}");
+        let referenced_by = format!("Error `{}`", error.name);
+        let (domain_index, component_index) = match (domain, component) {
+            (None, None) => previous.ok_or_else(|| FileFormatError::MissingComponentReference {
+                error: error.name.clone(),
+            })?,
+            (domain, Some(component)) => {
+                find_component(&result, domain.as_deref(), &component, &referenced_by)?
+            }
+            (Some(_), None) => {
+                return Err(FileFormatError::MissingComponentReference {
+                    error: error.name.clone(),
+                })
+            }
+        };
+        result.domains[domain_index].components[component_index]
+            .errors
+            .push(error);
+        previous = Some((domain_index, component_index));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::description::FlatRoot;
+    use crate::loader::error::FileFormatError;
+
+    use super::unflatten;
+
+    const FLAT: &str = r#"
+[[domains]]
+domain_name = "compiler"
+domain_code = 1
+
+[[domains]]
+domain_name = "core"
+domain_code = 2
+
+[[components]]
+component_name = "zksolc"
+domain = "compiler"
+component_code = 1
+
+[[components]]
+component_name = "sequencer"
+domain = "core"
+component_code = 1
+
+[[errors]]
+name = "Umbrella"
+code = 42
+domain = "compiler"
+component = "zksolc"
+message = "Any error!"
+
+[[errors]]
+name = "SolcNotFound"
+code = 1
+message = "I just can't find solc!"
+
+[[errors]]
+name = "Halted"
+code = 1
+component = "sequencer"
+message = "Halted."
+"#;
+
+    #[test]
+    fn rebuilds_hierarchy() {
+        let flat: FlatRoot = toml::from_str(FLAT).unwrap();
+        let root = unflatten(flat).unwrap();
+
+        let zksolc = root.get_component("compiler", "zksolc").unwrap();
+        let names: Vec<_> = zksolc.errors.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Umbrella", "SolcNotFound"]);
+
+        let sequencer = root.get_component("core", "sequencer").unwrap();
+        assert_eq!(sequencer.errors.len(), 1);
+    }
+
+    #[test]
+    fn reports_dangling_references() {
+        let flat: FlatRoot =
+            toml::from_str(&FLAT.replace("domain = \"core\"", "domain = \"cor\"")).unwrap();
+        assert!(matches!(
+            unflatten(flat),
+            Err(FileFormatError::UndefinedDomain { domain, .. }) if domain == "cor"
+        ));
+
+        let flat: FlatRoot =
+            toml::from_str(&FLAT.replace("component = \"sequencer\"", "component = \"sequenser\""))
+                .unwrap();
+        assert!(matches!(
+            unflatten(flat),
+            Err(FileFormatError::UndefinedComponent { component, .. }) if component == "sequenser"
+        ));
+    }
+}
@@ -0,0 +1,312 @@
+//!
+//! Serialization formats of the files holding fragments of error hierarchy.
+//!
+
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+use crate::description::Collection;
+use crate::description::FlatRoot;
+use crate::schema::validate;
+use crate::schema::SchemaViolation;
+
+use super::diagnostic::Excerpt;
+use super::error::FileFormatError;
+use super::flat::unflatten;
+use super::pointer::locate;
+use super::pointer::offset;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FileFormat {
+    Json,
+    Toml,
+}
+
+impl FileFormat {
+    /// Guess the format from the extension of a file name, path or URL.
+    pub fn from_extension(name: &str) -> Option<FileFormat> {
+        let name = name.split(['?', '#']).next().unwrap_or_default();
+        match Path::new(name).extension()?.to_str()? {
+            "json" => Some(FileFormat::Json),
+            "toml" => Some(FileFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// Guess the format from the contents of a file. A JSON description is
+    /// always an object or an array of objects, while a TOML file starts with
+    /// a key or a table header.
+    pub fn detect(contents: &str) -> FileFormat {
+        let trimmed = contents.trim_start();
+        if trimmed.starts_with('{') {
+            FileFormat::Json
+        } else if let Some(rest) = trimmed.strip_prefix('[') {
+            if rest.trim_start().starts_with(['{', ']']) {
+                FileFormat::Json
+            } else {
+                FileFormat::Toml
+            }
+        } else {
+            FileFormat::Toml
+        }
+    }
+}
+
+impl std::fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FileFormat::Json => "json",
+            FileFormat::Toml => "toml",
+        })
+    }
+}
+
+/// Kinds of objects described by a file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Shape {
+    Root,
+    FlatRoot,
+    Domain,
+    Component,
+    Overlay,
+    Errors,
+}
+
+impl Shape {
+    pub const ALL: [Shape; 6] = [
+        Shape::Root,
+        Shape::FlatRoot,
+        Shape::Domain,
+        Shape::Component,
+        Shape::Overlay,
+        Shape::Errors,
+    ];
+
+    /// Short name of the kind, used in the names of the schema files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shape::Root => "root",
+            Shape::FlatRoot => "flat-root",
+            Shape::Domain => "domain",
+            Shape::Component => "component",
+            Shape::Overlay => "overlay",
+            Shape::Errors => "errors",
+        }
+    }
+
+    /// Recognize the kind of the object by its top-level keys, so that the
+    /// errors in the file are reported against the layout of this kind.
+    fn sniff(is_array: bool, has_key: impl Fn(&str) -> bool) -> Shape {
+        if is_array {
+            Shape::Errors
+        } else if has_key("overlay") {
+            Shape::Overlay
+        } else if has_key("domain_name") {
+            Shape::Domain
+        } else if has_key("component_name") {
+            Shape::Component
+        } else if is_flat(has_key) {
+            Shape::FlatRoot
+        } else {
+            Shape::Root
+        }
+    }
+}
+
+impl std::fmt::Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Shape::Root => "a master error database",
+            Shape::FlatRoot => "a master error database in the flat layout",
+            Shape::Domain => "a domain",
+            Shape::Component => "a component",
+            Shape::Overlay => "an overlay",
+            Shape::Errors => "an array of errors",
+        })
+    }
+}
+
+/// A file uses the flat layout if it lists domains along with components or
+/// errors at the top level.
+fn is_flat(has_key: impl Fn(&str) -> bool) -> bool {
+    has_key("domains") && (has_key("components") || has_key("errors"))
+}
+
+fn json_error(
+    file: &str,
+    contents: &str,
+    expected: String,
+    error: serde_json::Error,
+) -> FileFormatError {
+    // The position is shown in the excerpt instead.
+    let position = format!(" at line {} column {}", error.line(), error.column());
+    let message = error.to_string();
+    FileFormatError::ParseError {
+        file: file.to_owned(),
+        expected,
+        message: message
+            .strip_suffix(&position)
+            .unwrap_or(&message)
+            .to_owned(),
+        excerpt: (error.line() > 0).then(|| {
+            Box::new(Excerpt::at_line(
+                file,
+                contents,
+                error.line(),
+                error.column(),
+            ))
+        }),
+    }
+}
+
+fn toml_error(
+    file: &str,
+    contents: &str,
+    expected: String,
+    error: toml::de::Error,
+) -> FileFormatError {
+    FileFormatError::ParseError {
+        file: file.to_owned(),
+        expected,
+        message: error.message().trim_end().to_owned(),
+        excerpt: error
+            .span()
+            .map(|span| Box::new(Excerpt::at_offset(file, contents, span.start))),
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(
+    contents: &str,
+    format: FileFormat,
+    file: &str,
+    shape: Shape,
+) -> Result<T, FileFormatError> {
+    match format {
+        FileFormat::Json => serde_json::from_str(contents)
+            .map_err(|error| json_error(file, contents, shape.to_string(), error)),
+        FileFormat::Toml => toml::from_str(contents)
+            .map_err(|error| toml_error(file, contents, shape.to_string(), error)),
+    }
+}
+
+fn check_schema(
+    value: &serde_json::Value,
+    contents: &str,
+    format: FileFormat,
+    file: &str,
+    shape: Shape,
+) -> Result<(), FileFormatError> {
+    validate(value, shape).map_err(|violations| FileFormatError::SchemaViolation {
+        file: file.to_owned(),
+        expected: shape.to_string(),
+        violations: violations
+            .into_iter()
+            .map(|violation| SchemaViolation {
+                excerpt: offset(contents, format, &violation.pointer)
+                    .map(|offset| Excerpt::at_offset(file, contents, offset)),
+                ..violation
+            })
+            .collect(),
+    })
+}
+
+/// Parse the contents of `file`, check them against the schema of the
+/// recognized kind and record the positions of the definitions in it.
+pub fn parse(
+    contents: &str,
+    format: FileFormat,
+    file: &str,
+) -> Result<Collection, FileFormatError> {
+    let shape = match format {
+        FileFormat::Json => {
+            let value: serde_json::Value = serde_json::from_str(contents)
+                .map_err(|error| json_error(file, contents, format.to_string(), error))?;
+            let shape = Shape::sniff(value.is_array(), |key| value.get(key).is_some());
+            check_schema(&value, contents, format, file, shape)?;
+            shape
+        }
+        FileFormat::Toml => {
+            let table: toml::Table = toml::from_str(contents)
+                .map_err(|error| toml_error(file, contents, format.to_string(), error))?;
+            let shape = Shape::sniff(false, |key| table.contains_key(key));
+            let value =
+                serde_json::to_value(&table).expect("TOML values are representable in JSON");
+            check_schema(&value, contents, format, file, shape)?;
+            shape
+        }
+    };
+    let mut collection = match shape {
+        Shape::FlatRoot => {
+            let flat: FlatRoot = deserialize(contents, format, file, shape)?;
+            return Ok(Collection::Root(unflatten(flat)?));
+        }
+        Shape::Root => Collection::Root(deserialize(contents, format, file, shape)?),
+        Shape::Domain => Collection::Domain(deserialize(contents, format, file, shape)?),
+        Shape::Component => Collection::Component(deserialize(contents, format, file, shape)?),
+        Shape::Overlay => Collection::Overlay(deserialize(contents, format, file, shape)?),
+        Shape::Errors => Collection::Errors(deserialize(contents, format, file, shape)?),
+    };
+    locate(&mut collection);
+    Ok(collection)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loader::error::FileFormatError;
+
+    use super::parse;
+    use super::FileFormat;
+
+    #[test]
+    fn errors_are_reported_against_the_recognized_shape() {
+        let contents = "component_name = \"seq\"\ncomponent_code = 1\n\n[[errors]]\nname = \"B\"\ncode = \"x\"\n";
+        match parse(contents, FileFormat::Toml, "seq.toml") {
+            Err(FileFormatError::SchemaViolation {
+                expected,
+                violations,
+                ..
+            }) => {
+                assert_eq!(expected, "a component");
+                let positions: Vec<_> = violations
+                    .iter()
+                    .map(|v| {
+                        let excerpt = v.excerpt.as_ref().unwrap();
+                        (v.pointer.as_str(), excerpt.line, excerpt.column)
+                    })
+                    .collect();
+                assert_eq!(positions, [("/errors/0/code", 6, 8), ("/errors/0", 4, 1)]);
+                assert_eq!(violations[0].message, "\"x\" is not of type \"integer\"");
+            }
+            other => panic!("Expected a schema violation, got {other:?}"),
+        }
+
+        let contents = "component_name = \"seq\"\ncomponent_code = 1\n\n[[errors]\n";
+        match parse(contents, FileFormat::Toml, "seq.toml") {
+            Err(FileFormatError::ParseError { excerpt, .. }) => {
+                let excerpt = excerpt.unwrap();
+                assert_eq!(excerpt.line, 4);
+            }
+            other => panic!("Expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn schema_violations_in_json_have_positions() {
+        let contents = r#"{
+  "component_name": "seq",
+  "component_code": 1,
+  "errors": [
+    { "name": "A", "code": 1, "message": "{ \"A\" }" },
+    { "name": "B", "code": "x", "message": "B" }
+  ]
+}"#;
+        match parse(contents, FileFormat::Json, "seq.json") {
+            Err(FileFormatError::SchemaViolation { violations, .. }) => {
+                let excerpt = violations[0].excerpt.as_ref().unwrap();
+                assert_eq!((excerpt.line, excerpt.column), (6, 28));
+            }
+            other => panic!("Expected a schema violation, got {other:?}"),
+        }
+    }
+}
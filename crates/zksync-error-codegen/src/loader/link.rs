@@ -1,12 +1,35 @@
-use std::path::PathBuf;
+use cargo_metadata::semver::VersionReq;
 
-use super::{error::LinkError, CollectionFile};
+use super::error::LinkError;
+use super::resolution::CargoPackage;
 
+/// Link to a file with error definitions. Links may be pinned to the SHA-256
+/// digest of the contents of the file with a fragment `#sha256=<hex digest>`.
 #[derive(Clone, Debug)]
 pub enum Link {
-    PackageLink { package: String, filename: String },
-    FileLink { path: String },
-    URL { url: String },
+    /// File provided by a cargo package, possibly of a version matching
+    /// `version`. An empty package name stands for the workspace.
+    PackageLink {
+        package: String,
+        version: Option<VersionReq>,
+        filename: String,
+        sha256: Option<String>,
+    },
+    FileLink {
+        path: String,
+        sha256: Option<String>,
+    },
+    URL {
+        url: String,
+        sha256: Option<String>,
+    },
+    /// Link with a scheme handled by a resolver registered in
+    /// [`crate::loader::schemes::SchemeRegistry`].
+    Custom {
+        scheme: String,
+        address: String,
+        sha256: Option<String>,
+    },
 }
 
 impl Link {
@@ -15,48 +38,112 @@ impl Link {
     pub const FILE_FORMAT_PREFIX: &str = "file";
     pub const NETWORK_FORMAT_PREFIXES: [&str; 2] = ["https", "http"];
     pub const PACKAGE_SEPARATOR: &str = "@@";
+    /// Separates the package name from the version requirement.
+    pub const VERSION_SEPARATOR: &str = "@";
+    /// Fragment pinning the digest of the contents of the file.
+    pub const DIGEST_FRAGMENT: &str = "#sha256=";
 
     pub fn parse(link: impl Into<String>) -> Result<Link, LinkError> {
-        let string: String = link.into();
+        let original: String = link.into();
+        let (string, sha256) = match original.rsplit_once(Link::DIGEST_FRAGMENT) {
+            Some((string, digest))
+                if digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit()) =>
+            {
+                (string.to_owned(), Some(digest.to_ascii_lowercase()))
+            }
+            Some(_) => return Err(LinkError::InvalidDigest(original)),
+            None => (original.clone(), None),
+        };
 
         match string.split_once("://") {
             Some((Link::CARGO_FORMAT_PREFIX, path)) => {
-                match path.split_once(Link::PACKAGE_SEPARATOR) {
-                    Some((package, filename)) => Ok(Link::PackageLink {
-                        package: package.to_owned(),
-                        filename: filename.to_owned(),
-                    }),
-                    None => Err(LinkError::InvalidLinkFormat(string)),
-                }
+                let Some((package, filename)) = path.split_once(Link::PACKAGE_SEPARATOR) else {
+                    return Err(LinkError::InvalidLinkFormat(original));
+                };
+                let (package, version) = match package.split_once(Link::VERSION_SEPARATOR) {
+                    Some((package, version)) => match VersionReq::parse(version) {
+                        Ok(version) => (package, Some(version)),
+                        Err(_) => return Err(LinkError::InvalidVersionRequirement(original)),
+                    },
+                    None => (package, None),
+                };
+                Ok(Link::PackageLink {
+                    package: package.to_owned(),
+                    version,
+                    filename: filename.to_owned(),
+                    sha256,
+                })
             }
             Some((Link::FILE_FORMAT_PREFIX, path)) => Ok(Link::FileLink {
                 path: path.to_owned(),
+                sha256,
             }),
             Some((prefix, _)) if Link::NETWORK_FORMAT_PREFIXES.contains(&prefix) => Ok(Link::URL {
                 url: string.to_string(),
+                sha256,
+            }),
+            None => Ok(Link::FileLink {
+                path: string,
+                sha256,
+            }),
+            Some((scheme, address)) if Link::is_scheme(scheme) => Ok(Link::Custom {
+                scheme: scheme.to_owned(),
+                address: address.to_owned(),
+                sha256,
             }),
-            None => Ok(Link::FileLink { path: string }),
-            Some(_) => Err(LinkError::InvalidLinkFormat(string)),
+            Some(_) => Err(LinkError::InvalidLinkFormat(original)),
+        }
+    }
+
+    /// Whether `path` matches several files: it contains glob metacharacters
+    /// or denotes a directory by a trailing slash.
+    pub fn is_pattern_path(path: &str) -> bool {
+        path.ends_with('/') || path.contains(['*', '?', '['])
+    }
+
+    /// Whether this link matches several local files.
+    pub fn is_pattern(&self) -> bool {
+        match self {
+            Link::PackageLink { filename: path, .. } | Link::FileLink { path, .. } => {
+                Link::is_pattern_path(path)
+            }
+            Link::URL { .. } | Link::Custom { .. } => false,
+        }
+    }
+
+    /// Scheme names follow RFC 3986: a letter followed by letters, digits,
+    /// `+`, `-` or `.`.
+    fn is_scheme(scheme: &str) -> bool {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    }
+
+    /// Expected SHA-256 digest of the contents of the file, if pinned.
+    pub fn sha256(&self) -> Option<&str> {
+        match self {
+            Link::PackageLink { sha256, .. }
+            | Link::FileLink { sha256, .. }
+            | Link::URL { sha256, .. }
+            | Link::Custom { sha256, .. } => sha256.as_deref(),
         }
     }
-    pub fn matches(link: &Link, file: &CollectionFile) -> bool {
-        if let Link::PackageLink { package, filename } = link {
-            let CollectionFile {
-                package: candidate_package,
-                absolute_path,
-            } = file;
-
-            if package != candidate_package {
-                return false;
-            };
-            let pathbuf = PathBuf::from(absolute_path);
-            let stripped_filename = pathbuf
-                .file_name()
-                .unwrap_or_else(|| panic!("Error accessing file `{absolute_path:?}`."));
-
-            stripped_filename.to_str().is_some_and(|s| s == filename)
-        } else {
-            false
+
+    /// Whether `candidate` is the package a cargo link refers to.
+    pub fn matches(link: &Link, candidate: &CargoPackage) -> bool {
+        match link {
+            Link::PackageLink {
+                package, version, ..
+            } => {
+                package == &candidate.name
+                    && match (version, &candidate.version) {
+                        (None, _) => true,
+                        (Some(requirement), Some(version)) => requirement.matches(version),
+                        (Some(_), None) => false,
+                    }
+            }
+            _ => false,
         }
     }
 }
@@ -64,13 +151,74 @@ impl Link {
 impl std::fmt::Display for Link {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Link::PackageLink { package, filename } => f.write_fmt(format_args!(
-                "{}://{package}{}{filename}",
-                Link::CARGO_FORMAT_PREFIX,
-                Link::PACKAGE_SEPARATOR
-            )),
-            Link::URL { url } => f.write_str(url),
-            Link::FileLink { path } => f.write_str(path),
+            Link::PackageLink {
+                package,
+                version,
+                filename,
+                ..
+            } => {
+                write!(f, "{}://{package}", Link::CARGO_FORMAT_PREFIX)?;
+                if let Some(version) = version {
+                    write!(f, "{}{version}", Link::VERSION_SEPARATOR)?;
+                }
+                write!(f, "{}{filename}", Link::PACKAGE_SEPARATOR)?
+            }
+            Link::URL { url, .. } => f.write_str(url)?,
+            Link::FileLink { path, .. } => f.write_str(path)?,
+            Link::Custom {
+                scheme, address, ..
+            } => write!(f, "{scheme}://{address}")?,
         }
+        match self.sha256() {
+            Some(sha256) => write!(f, "{}{sha256}", Link::DIGEST_FRAGMENT),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Link;
+    use crate::loader::error::LinkError;
+
+    #[test]
+    fn pinned_links() {
+        let digest = "AB".repeat(32);
+        let link = Link::parse(format!("https://example.com/a.json#sha256={digest}")).unwrap();
+        assert!(
+            matches!(&link, Link::URL { url, .. } if url == "https://example.com/a.json"),
+            "{link:?}"
+        );
+        assert_eq!(link.sha256(), Some("ab".repeat(32).as_str()));
+        assert_eq!(
+            link.to_string(),
+            format!("https://example.com/a.json#sha256={}", "ab".repeat(32))
+        );
+
+        assert_eq!(Link::parse("a.json").unwrap().sha256(), None);
+        assert!(matches!(
+            Link::parse("cargo://a@@a.json#sha256=abcd"),
+            Err(LinkError::InvalidDigest(_))
+        ));
+    }
+
+    #[test]
+    fn package_versions() {
+        let link = Link::parse("cargo://my-crate@^1.2@@errors.json").unwrap();
+        assert!(
+            matches!(&link, Link::PackageLink { package, version: Some(_), filename, .. }
+                if package == "my-crate" && filename == "errors.json"),
+            "{link:?}"
+        );
+        assert_eq!(link.to_string(), "cargo://my-crate@^1.2@@errors.json");
+
+        let link = Link::parse("cargo://@@errors/").unwrap();
+        assert!(
+            matches!(&link, Link::PackageLink { package, version: None, .. } if package.is_empty())
+        );
+        assert!(matches!(
+            Link::parse("cargo://my-crate@one@@errors.json"),
+            Err(LinkError::InvalidVersionRequirement(_))
+        ));
     }
 }
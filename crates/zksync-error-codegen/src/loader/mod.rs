@@ -1,9 +1,17 @@
-use cargo::get_resolution_context;
-use error::FileFormatError;
+use cargo::CargoWorkspace;
+use error::LinkError;
 use error::LoadError;
+use fetcher::Fetcher;
+use fetcher::HttpFetcher;
+use format::FileFormat;
 use link::Link;
+use network::NetworkConfig;
+use network::RemoteFiles;
 use resolution::resolve;
+use resolution::resolve_relative;
+use resolution::ResolutionContext;
 use resolution::ResolvedLink;
+use schemes::SchemeRegistry;
 
 use std::path::PathBuf;
 
@@ -11,9 +19,18 @@ use crate::description::Collection;
 
 pub mod builder;
 pub mod cargo;
+pub mod diagnostic;
 pub mod error;
+pub mod fetcher;
+pub mod flat;
+pub mod format;
 pub mod link;
+pub mod network;
+pub mod pointer;
+pub mod registry;
 pub mod resolution;
+pub mod revision;
+pub mod schemes;
 
 #[derive(Clone, Debug)]
 pub struct CollectionFile {
@@ -22,44 +39,101 @@ pub struct CollectionFile {
 }
 
 pub fn load(link: &Link) -> Result<Collection, LoadError> {
-    let context = get_resolution_context();
-    let contents = match resolve(link, &context)? {
+    let remote = RemoteFiles::new(&NetworkConfig::default(), None)?;
+    let fetcher = SchemeRegistry::with_builtins(HttpFetcher::default());
+    load_resolved(
+        link,
+        &resolve_link(link, None, &CargoWorkspace::default(), &fetcher)?,
+        &remote,
+        &fetcher,
+    )
+}
+
+/// Find the file `link` points to. Relative file paths in links found in the
+/// file at `base` are taken relative to it; otherwise, relative to the current
+/// working directory. Cargo links are resolved among the packages of
+/// `workspace`.
+pub fn resolve_link(
+    link: &Link,
+    base: Option<&ResolvedLink>,
+    workspace: &CargoWorkspace,
+    fetcher: &dyn Fetcher,
+) -> Result<ResolvedLink, LoadError> {
+    let no_packages = ResolutionContext::default();
+    let context = match link {
+        Link::PackageLink { .. } => workspace.context()?,
+        _ => &no_packages,
+    };
+    Ok(match base {
+        Some(base) => resolve_relative(link, context, base, fetcher)?,
+        None => resolve(link, context)?,
+    })
+}
+
+/// Find the files `link` points to, expanding patterns. See [`resolve_link`].
+pub fn resolve_links(
+    link: &Link,
+    base: Option<&ResolvedLink>,
+    workspace: &CargoWorkspace,
+    fetcher: &dyn Fetcher,
+) -> Result<Vec<ResolvedLink>, LoadError> {
+    let location = resolve_link(link, base, workspace, fetcher)?;
+    if !link.is_pattern() {
+        return Ok(vec![location]);
+    }
+    if link.sha256().is_some() {
+        return Err(LinkError::PinnedPattern(link.to_string()).into());
+    }
+    let locations = location.expand(fetcher)?;
+    if locations.is_empty() {
+        log::warn!("No files match the link `{link}`.");
+    }
+    Ok(locations)
+}
+
+/// Load the file `link` resolves to with `fetcher`, using `remote` for the
+/// files in the network. If `link` is pinned to a digest, the contents of the
+/// file should match it.
+pub fn load_resolved(
+    link: &Link,
+    location: &ResolvedLink,
+    remote: &RemoteFiles,
+    fetcher: &dyn Fetcher,
+) -> Result<Collection, LoadError> {
+    let contents = match location {
         ResolvedLink::DescriptionFile(description_file) => {
-            fetch::from_fs(&description_file.absolute_path)?
+            fetcher.read_local(&description_file.absolute_path)?
         }
-        ResolvedLink::LocalPath(path) => fetch::from_fs(&path)?,
-        ResolvedLink::Url(url) => fetch::from_network(&url)?,
+        ResolvedLink::LocalPath(path) => fetcher.read_local(path)?,
+        ResolvedLink::Url(url) => remote.fetch(url, fetcher)?,
+        ResolvedLink::Custom { scheme, address } => fetcher.fetch_custom(scheme, address)?,
     };
 
-    load_serialized(&contents)
+    if let Some(expected) = link.sha256() {
+        let actual = network::sha256(&contents);
+        if actual != expected {
+            return Err(LoadError::DigestMismatch {
+                link: Box::new(link.clone()),
+                expected: expected.to_owned(),
+                actual,
+            });
+        }
+    }
+
+    let file = location.to_string();
+    let format = FileFormat::from_extension(&file).unwrap_or_else(|| FileFormat::detect(&contents));
+    load_serialized_as(&contents, format, &file)
 }
 
 pub fn load_serialized(contents: &str) -> Result<Collection, LoadError> {
-    serde_json::from_str::<crate::description::Collection>(contents).map_err(|error| {
-        LoadError::FileFormatError(FileFormatError::ParseError {
-            contents: contents.to_owned(),
-            inner: Box::new(error),
-        })
-    })
+    load_serialized_as(contents, FileFormat::detect(contents), "<input>")
 }
 
-mod fetch {
-    use reqwest;
-    use std::fs;
-    use std::path::PathBuf;
-
-    pub fn from_fs(path: &PathBuf) -> std::io::Result<String> {
-        eprintln!(
-            "Trying to read local file: {}",
-            path.to_str().expect("Incorrect path")
-        );
-        fs::read_to_string(path)
-    }
-
-    pub fn from_network(url: &str) -> Result<String, reqwest::Error> {
-        eprintln!("Trying to fetch file from network: {url}");
-        let response = reqwest::blocking::get(url)?;
-        let content = response.text()?;
-        Ok(content)
-    }
+/// Parse `contents` of the file named `file` in diagnostics.
+pub fn load_serialized_as(
+    contents: &str,
+    format: FileFormat,
+    file: &str,
+) -> Result<Collection, LoadError> {
+    Ok(format::parse(contents, format, file)?)
 }
@@ -0,0 +1,280 @@
+//!
+//! Files fetched from the network. Their hashes are recorded in a lockfile, and
+//! their contents are kept in a local content-addressed cache, so that the
+//! builds are reproducible and may run offline.
+//!
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest as _;
+use sha2::Sha256;
+
+use super::error::RemoteFileError;
+use super::fetcher::Fetcher;
+
+/// Name of the lockfile placed next to the root definitions by default.
+pub const LOCKFILE_NAME: &str = "zksync-error-sources.lock";
+
+const LOCKFILE_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Default)]
+pub struct NetworkConfig {
+    /// Lockfile recording the hashes of the files fetched from the network.
+    /// Defaults to [`LOCKFILE_NAME`] next to the root definitions, if they are
+    /// stored locally.
+    pub lockfile: Option<PathBuf>,
+    /// Directory holding the fetched files. Defaults to
+    /// [`default_cache_directory`].
+    pub cache_directory: Option<PathBuf>,
+    /// Never access the network, serve the files only from the cache.
+    pub offline: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Lockfile {
+    version: u32,
+    files: BTreeMap<String, LockedFile>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct LockedFile {
+    sha256: String,
+}
+
+/// `$XDG_CACHE_HOME/zksync-error`, or `$HOME/.cache/zksync-error`.
+pub fn default_cache_directory() -> PathBuf {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir)
+        .join("zksync-error")
+}
+
+/// Hexadecimal SHA-256 digest of `contents`.
+pub fn sha256(contents: &str) -> String {
+    Sha256::digest(contents.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Access to the files fetched from the network, checked against the lockfile.
+pub struct RemoteFiles {
+    lockfile_path: Option<PathBuf>,
+    cache_directory: PathBuf,
+    offline: bool,
+    lock: RefCell<Lockfile>,
+    lock_changed: Cell<bool>,
+}
+
+impl RemoteFiles {
+    /// Read the lockfile, if it exists. `default_lockfile` is used if the
+    /// configuration does not specify one.
+    pub fn new(
+        config: &NetworkConfig,
+        default_lockfile: Option<PathBuf>,
+    ) -> Result<Self, RemoteFileError> {
+        let NetworkConfig {
+            lockfile,
+            cache_directory,
+            offline,
+        } = config;
+        let lockfile_path = lockfile.clone().or(default_lockfile);
+        let lock = match &lockfile_path {
+            Some(path) if path.exists() => {
+                let contents = std::fs::read_to_string(path)?;
+                serde_json::from_str(&contents).map_err(|inner| {
+                    RemoteFileError::InvalidLockfile {
+                        path: path.clone(),
+                        inner,
+                    }
+                })?
+            }
+            _ => Lockfile::default(),
+        };
+        Ok(Self {
+            lockfile_path,
+            cache_directory: cache_directory
+                .clone()
+                .unwrap_or_else(default_cache_directory),
+            offline: *offline,
+            lock: RefCell::new(lock),
+            lock_changed: Cell::new(false),
+        })
+    }
+
+    /// Contents of the file at `url`. Files recorded in the lockfile are
+    /// served from the cache if possible; the contents of the fetched files
+    /// should match the hashes in the lockfile. New files are recorded in the
+    /// lockfile. Files missing from the cache are fetched by `fetcher`.
+    pub fn fetch(&self, url: &str, fetcher: &dyn Fetcher) -> Result<String, RemoteFileError> {
+        let locked = self.lock.borrow().files.get(url).cloned();
+        if let Some(LockedFile { sha256 }) = &locked {
+            if let Some(contents) = self.read_cached(url, sha256)? {
+                return Ok(contents);
+            }
+        }
+        if self.offline {
+            return Err(match locked {
+                Some(LockedFile { sha256 }) => RemoteFileError::NotCached {
+                    url: url.to_owned(),
+                    sha256,
+                },
+                None => RemoteFileError::NotLocked {
+                    url: url.to_owned(),
+                },
+            });
+        }
+
+        let contents = fetcher.fetch_remote(url)?;
+        let actual = sha256(&contents);
+        match locked {
+            Some(LockedFile { sha256: expected }) if expected != actual => {
+                return Err(RemoteFileError::HashMismatch {
+                    url: url.to_owned(),
+                    expected,
+                    actual,
+                })
+            }
+            Some(_) => {}
+            None => {
+                self.lock.borrow_mut().files.insert(
+                    url.to_owned(),
+                    LockedFile {
+                        sha256: actual.clone(),
+                    },
+                );
+                self.lock_changed.set(true);
+            }
+        }
+        self.write_cached(&actual, &contents)?;
+        Ok(contents)
+    }
+
+    /// Write the lockfile if new files were recorded in it.
+    pub fn save(&self) -> Result<(), RemoteFileError> {
+        if let (true, Some(path)) = (self.lock_changed.get(), &self.lockfile_path) {
+            let contents = serde_json::to_string_pretty(&*self.lock.borrow())
+                .expect("Internal error: serializing lockfile");
+            std::fs::write(path, contents + "\n")?;
+            self.lock_changed.set(false);
+        }
+        Ok(())
+    }
+
+    pub fn lockfile_path(&self) -> Option<&Path> {
+        self.lockfile_path.as_deref()
+    }
+
+    fn read_cached(&self, url: &str, sha256: &str) -> Result<Option<String>, RemoteFileError> {
+        let path = self.cache_directory.join(sha256);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        let actual = self::sha256(&contents);
+        if actual != sha256 {
+            return Err(RemoteFileError::HashMismatch {
+                url: url.to_owned(),
+                expected: sha256.to_owned(),
+                actual,
+            });
+        }
+        Ok(Some(contents))
+    }
+
+    fn write_cached(&self, sha256: &str, contents: &str) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.cache_directory)?;
+        let temporary = self
+            .cache_directory
+            .join(format!("{sha256}.{}.tmp", std::process::id()));
+        std::fs::write(&temporary, contents)?;
+        std::fs::rename(temporary, self.cache_directory.join(sha256))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read as _;
+    use std::io::Write as _;
+    use std::net::TcpListener;
+
+    use super::NetworkConfig;
+    use super::RemoteFiles;
+    use crate::loader::error::RemoteFileError;
+    use crate::loader::fetcher::HttpFetcher;
+
+    /// Serve `body` to `requests` HTTP requests on a local port.
+    fn serve(body: &'static str, requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        format!("http://{address}/errors.json")
+    }
+
+    #[test]
+    fn lockfile_and_cache() {
+        let directory =
+            std::env::temp_dir().join(format!("zksync-error-network-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        let config = NetworkConfig {
+            lockfile: Some(directory.join("sources.lock")),
+            cache_directory: Some(directory.join("cache")),
+            offline: false,
+        };
+        let url = serve("[]", 1);
+
+        let remote = RemoteFiles::new(&config, None).unwrap();
+        assert_eq!(remote.fetch(&url, &HttpFetcher::default()).unwrap(), "[]");
+        std::fs::create_dir_all(&directory).unwrap();
+        remote.save().unwrap();
+
+        // The server is gone; the file is served from the cache.
+        let offline = NetworkConfig {
+            offline: true,
+            ..config.clone()
+        };
+        let remote = RemoteFiles::new(&offline, None).unwrap();
+        assert_eq!(remote.fetch(&url, &HttpFetcher::default()).unwrap(), "[]");
+        assert!(matches!(
+            remote.fetch("http://127.0.0.1:1/other.json", &HttpFetcher::default()),
+            Err(RemoteFileError::NotLocked { .. })
+        ));
+
+        for entry in std::fs::read_dir(directory.join("cache")).unwrap() {
+            std::fs::write(entry.unwrap().path(), "[ ]").unwrap();
+        }
+        assert!(matches!(
+            remote.fetch(&url, &HttpFetcher::default()),
+            Err(RemoteFileError::HashMismatch { .. })
+        ));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
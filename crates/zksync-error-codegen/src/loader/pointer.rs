@@ -0,0 +1,201 @@
+//!
+//! JSON pointers to the definitions in a file, recorded as parts of their
+//! origins. TOML files are addressed as if they were converted to JSON.
+//!
+
+use crate::description::Collection;
+use crate::description::Component;
+use crate::description::Domain;
+use crate::description::Error;
+use crate::description::Type;
+
+use super::format::FileFormat;
+
+/// Record the positions of the definitions in a file with the tree layout.
+pub fn locate(collection: &mut Collection) {
+    match collection {
+        Collection::Root(root) => {
+            locate_types(&mut root.types);
+            for (index, domain) in root.domains.iter_mut().enumerate() {
+                locate_domain(domain, format!("/domains/{index}"));
+            }
+        }
+        Collection::Domain(domain) => locate_domain(domain, String::new()),
+        Collection::Component(component) => locate_component(component, String::new()),
+        Collection::Errors(errors) => locate_errors(errors, ""),
+        Collection::Overlay(_) => {}
+    }
+}
+
+pub(super) fn locate_types(types: &mut [Type]) {
+    for (index, typ) in types.iter_mut().enumerate() {
+        typ.pointer = format!("/types/{index}");
+    }
+}
+
+pub(super) fn locate_domain(domain: &mut Domain, pointer: String) {
+    for (index, component) in domain.components.iter_mut().enumerate() {
+        locate_component(component, format!("{pointer}/components/{index}"));
+    }
+    domain.pointer = pointer;
+}
+
+pub(super) fn locate_component(component: &mut Component, pointer: String) {
+    locate_errors(&mut component.errors, &format!("{pointer}/errors"));
+    component.pointer = pointer;
+}
+
+pub(super) fn locate_errors(errors: &mut [Error], prefix: &str) {
+    for (index, error) in errors.iter_mut().enumerate() {
+        error.pointer = format!("{prefix}/{index}");
+    }
+}
+
+/// Reference tokens of a JSON pointer, unescaped.
+fn tokens(pointer: &str) -> impl Iterator<Item = String> + '_ {
+    pointer
+        .split('/')
+        .skip(1)
+        .filter(|token| !token.is_empty())
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+}
+
+/// Byte offset of the value at `pointer` in `contents`, which hold a valid
+/// file in `format`. If the value itself has no position, as do the tables
+/// defined implicitly in TOML, the offset of the closest enclosing value is
+/// returned.
+pub fn offset(contents: &str, format: FileFormat, pointer: &str) -> Option<usize> {
+    match format {
+        FileFormat::Json => {
+            let mut scanner = JsonScanner {
+                contents: contents.as_bytes(),
+                position: 0,
+            };
+            scanner.skip_whitespace();
+            for token in tokens(pointer) {
+                scanner.enter(&token)?;
+            }
+            Some(scanner.position)
+        }
+        FileFormat::Toml => {
+            let document = toml_edit::ImDocument::parse(contents).ok()?;
+            let mut item = document.as_item();
+            let mut offset = None;
+            for token in tokens(pointer) {
+                item = match token.parse::<usize>() {
+                    Ok(index) if item.is_array() || item.is_array_of_tables() => item.get(index)?,
+                    _ => item.get(&token)?,
+                };
+                offset = item.span().map(|span| span.start).or(offset);
+            }
+            offset
+        }
+    }
+}
+
+/// Walks a valid JSON document down to the value at a pointer.
+struct JsonScanner<'a> {
+    contents: &'a [u8],
+    position: usize,
+}
+
+impl JsonScanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.contents.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    /// Skip `byte` and the whitespace following it.
+    fn skip(&mut self, byte: u8) -> Option<()> {
+        (self.peek()? == byte).then(|| {
+            self.position += 1;
+            self.skip_whitespace();
+        })
+    }
+
+    /// Skip a string and return its value.
+    fn string(&mut self) -> Option<String> {
+        let start = self.position;
+        self.position += 1;
+        loop {
+            match self.peek()? {
+                b'\\' => self.position += 2,
+                b'"' => break,
+                _ => self.position += 1,
+            }
+        }
+        self.position += 1;
+        let value = std::str::from_utf8(&self.contents[start..self.position]).ok()?;
+        let value = serde_json::from_str(value).ok();
+        self.skip_whitespace();
+        value
+    }
+
+    /// Skip the value starting at the current position.
+    fn value(&mut self) -> Option<()> {
+        match self.peek()? {
+            b'"' => {
+                self.string()?;
+            }
+            open @ (b'{' | b'[') => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.skip(open)?;
+                while self.peek()? != close {
+                    if open == b'{' {
+                        self.string()?;
+                        self.skip(b':')?;
+                    }
+                    self.value()?;
+                    if self.peek()? == b',' {
+                        self.skip(b',')?;
+                    }
+                }
+                self.skip(close)?;
+            }
+            _ => {
+                while self
+                    .peek()
+                    .is_some_and(|c| !matches!(c, b',' | b'}' | b']') && !c.is_ascii_whitespace())
+                {
+                    self.position += 1;
+                }
+                self.skip_whitespace();
+            }
+        }
+        Some(())
+    }
+
+    /// Move from the object or array at the current position to its member
+    /// named `token`.
+    fn enter(&mut self, token: &str) -> Option<()> {
+        match self.peek()? {
+            b'{' => {
+                self.skip(b'{')?;
+                loop {
+                    let key = self.string()?;
+                    self.skip(b':')?;
+                    if key == token {
+                        return Some(());
+                    }
+                    self.value()?;
+                    self.skip(b',')?;
+                }
+            }
+            b'[' => {
+                let index: usize = token.parse().ok()?;
+                self.skip(b'[')?;
+                for _ in 0..index {
+                    self.value()?;
+                    self.skip(b',')?;
+                }
+                (self.peek()? != b']').then_some(())
+            }
+            _ => None,
+        }
+    }
+}
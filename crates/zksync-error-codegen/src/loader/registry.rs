@@ -0,0 +1,205 @@
+//!
+//! Registry of the error identifiers ever generated. Every identifier is
+//! recorded with the name and the fields of its error, so that a code is never
+//! silently reused for an error of a different shape, and the errors written
+//! without a code keep the codes allocated to them.
+//!
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+use zksync_error_model::inner::ErrorCode;
+use zksync_error_model::inner::ErrorDescription;
+use zksync_error_model::inner::Model;
+
+use super::error::RegistryError;
+use super::error::RegistryViolation;
+
+/// Name of the registry placed next to the root definitions by default.
+pub const REGISTRY_NAME: &str = "errors.lock";
+
+const REGISTRY_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Default)]
+pub struct RegistryConfig {
+    /// File holding the registry. Defaults to [`REGISTRY_NAME`] next to the
+    /// root definitions, if they are stored locally.
+    pub path: Option<PathBuf>,
+    /// Check the model against the registry and record its new errors.
+    /// Otherwise the registry is only used to allocate the codes.
+    pub update: bool,
+    /// Accept the removed and reshaped errors, overwriting their entries.
+    pub force: bool,
+}
+
+/// Name and fields of an error, as recorded in the registry.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RegisteredError {
+    pub name: String,
+    /// Fields in the `name: type` form, or `name?: type` if optional.
+    pub fields: Vec<String>,
+}
+
+impl From<&ErrorDescription> for RegisteredError {
+    fn from(error: &ErrorDescription) -> Self {
+        Self {
+            name: error.name.clone(),
+            fields: error
+                .fields
+                .iter()
+                .map(|field| {
+                    let optional = if field.optional { "?" } else { "" };
+                    format!("{}{optional}: {}", field.name, field.r#type)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl RegisteredError {
+    /// Whether `error` is compatible with the recorded error: it has the same
+    /// name and the recorded fields, and the serialized errors may lack each
+    /// of its other fields.
+    fn is_extended_by(&self, error: &ErrorDescription) -> bool {
+        let actual = RegisteredError::from(error);
+        actual.name == self.name
+            && self
+                .fields
+                .iter()
+                .all(|field| actual.fields.contains(field))
+            && error
+                .fields
+                .iter()
+                .zip(&actual.fields)
+                .all(|(field, entry)| self.fields.contains(entry) || field.may_be_absent())
+    }
+}
+
+impl std::fmt::Display for RegisteredError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.fields.is_empty() {
+            write!(f, "`{}`", self.name)
+        } else {
+            write!(f, "`{} {{ {} }}`", self.name, self.fields.join(", "))
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RegistryFile {
+    version: u32,
+    errors: BTreeMap<String, RegisteredError>,
+}
+
+pub struct Registry {
+    path: Option<PathBuf>,
+    errors: BTreeMap<String, RegisteredError>,
+}
+
+impl Registry {
+    /// Read the registry, if it exists. `default_path` is used if the
+    /// configuration does not specify one.
+    pub fn new(
+        config: &RegistryConfig,
+        default_path: Option<PathBuf>,
+    ) -> Result<Self, RegistryError> {
+        let path = config.path.clone().or(default_path);
+        let errors = match &path {
+            Some(path) if path.exists() => {
+                let contents = std::fs::read_to_string(path)?;
+                let file: RegistryFile = serde_json::from_str(&contents).map_err(|inner| {
+                    RegistryError::InvalidRegistry {
+                        path: path.clone(),
+                        inner,
+                    }
+                })?;
+                file.errors
+            }
+            _ => BTreeMap::new(),
+        };
+        Ok(Self { path, errors })
+    }
+
+    /// Codes recorded for the errors of the component with the given
+    /// identifier encodings, along with the names of the errors.
+    pub fn recorded_codes<'a>(
+        &'a self,
+        domain: &str,
+        component: &str,
+    ) -> impl Iterator<Item = (ErrorCode, &'a str)> + 'a {
+        let prefix = format!("[{domain}-{component}-");
+        self.errors.iter().filter_map(move |(identifier, error)| {
+            let code = identifier.strip_prefix(&prefix)?.strip_suffix(']')?;
+            Some((code.parse().ok()?, error.name.as_str()))
+        })
+    }
+
+    /// Check every recorded error against the model, then record the errors
+    /// of the model. Recorded errors that were removed from the model, or
+    /// changed their name or fields other than by adding fields that may be
+    /// absent, are rejected, unless `force` is set.
+    pub fn update(&mut self, model: &Model, force: bool) -> Result<bool, RegistryError> {
+        let current: BTreeMap<String, &ErrorDescription> = model
+            .errors()
+            .map(|error| (error.get_identifier().to_string(), error))
+            .collect();
+        let violations: Vec<_> = self
+            .errors
+            .iter()
+            .filter_map(|(identifier, recorded)| match current.get(identifier) {
+                None => Some(RegistryViolation::Removed {
+                    identifier: identifier.clone(),
+                    recorded: recorded.clone(),
+                }),
+                Some(actual) if !recorded.is_extended_by(actual) => {
+                    Some(RegistryViolation::Reshaped {
+                        identifier: identifier.clone(),
+                        recorded: recorded.clone(),
+                        actual: (*actual).into(),
+                    })
+                }
+                Some(_) => None,
+            })
+            .collect();
+        if !violations.is_empty() {
+            if !force {
+                return Err(RegistryError::Violations {
+                    path: self.path.clone().unwrap_or_default(),
+                    violations,
+                });
+            }
+            for violation in &violations {
+                log::warn!("Overwriting the registry: {violation}");
+            }
+            self.errors.clear();
+        }
+        let current: BTreeMap<String, RegisteredError> = current
+            .into_iter()
+            .map(|(identifier, error)| (identifier, error.into()))
+            .collect();
+        let changed = current.iter().any(|(k, v)| self.errors.get(k) != Some(v));
+        self.errors.extend(current);
+        Ok(changed)
+    }
+
+    /// Write the registry, if it has a location.
+    pub fn save(&self) -> Result<(), RegistryError> {
+        if let Some(path) = &self.path {
+            let file = RegistryFile {
+                version: REGISTRY_VERSION,
+                errors: self.errors.clone(),
+            };
+            let contents =
+                serde_json::to_string_pretty(&file).expect("Internal error: serializing registry");
+            std::fs::write(path, contents + "\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
@@ -6,9 +6,11 @@ use super::ResolutionContext;
 pub enum ResolutionError {
     #[error("Failed to resolve `{link}` in context {context:?}.")]
     CargoLinkResolutionError {
-        link: Link,
+        link: Box<Link>,
         context: ResolutionContext,
     },
     #[error("Failed to resolve `{link}`.")]
-    GenericLinkResolutionError { link: Link },
+    GenericLinkResolutionError { link: Box<Link> },
+    #[error("Relative link `{path}` can not be resolved against `{base}`.")]
+    UnsupportedRelativeLink { base: String, path: String },
 }
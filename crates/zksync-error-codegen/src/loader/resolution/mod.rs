@@ -1,45 +1,467 @@
 pub mod error;
 
+use std::path::Component;
+use std::path::Path;
 use std::path::PathBuf;
 
+use cargo_metadata::semver::Version;
 use error::ResolutionError;
 
 use super::error::LinkError;
+use super::fetcher::DirectoryEntry;
+use super::fetcher::Fetcher;
+use super::format::FileFormat;
 use super::link::Link;
 use super::CollectionFile;
 
-#[derive(Clone, Debug, Default)]
+/// Cargo package providing files with error definitions.
+#[derive(Clone, Debug)]
+pub struct CargoPackage {
+    /// Package name, empty for the workspace.
+    pub name: String,
+    /// Package version, absent for the workspace.
+    pub version: Option<Version>,
+    pub root: PathBuf,
+    pub files: Vec<PathBuf>,
+}
+
+impl CargoPackage {
+    /// Whether this package provides a file named `filename`, or found at
+    /// the path `filename` relative to the package root.
+    fn provides(&self, file: &Path, filename: &str) -> bool {
+        file.file_name().is_some_and(|name| name == filename) || file == self.root.join(filename)
+    }
+}
+
+#[derive(Clone, Default)]
 pub struct ResolutionContext {
-    pub files: Vec<CollectionFile>,
+    /// Packages ordered by name, the latest versions first.
+    pub packages: Vec<CargoPackage>,
+}
+
+// Packages without error definitions are omitted, there is one for every
+// dependency.
+impl std::fmt::Debug for ResolutionContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.packages
+                    .iter()
+                    .filter(|package| !package.files.is_empty())
+                    .map(|package| (&package.name, &package.files)),
+            )
+            .finish()
+    }
 }
 
 impl ResolutionContext {
-    pub fn find_package(&self, package: &str) -> Option<&CollectionFile> {
-        self.files.iter().find(|df| &df.package == package)
+    /// Latest version of the package `link` refers to.
+    pub fn find_package(&self, link: &Link) -> Option<&CargoPackage> {
+        self.packages
+            .iter()
+            .find(|package| Link::matches(link, package))
+    }
+
+    /// File `link` points to, in the latest version of the package providing
+    /// it.
+    pub fn find_file(&self, link: &Link) -> Option<CollectionFile> {
+        let Link::PackageLink { filename, .. } = link else {
+            return None;
+        };
+        self.packages
+            .iter()
+            .filter(|package| Link::matches(link, package))
+            .find_map(|package| {
+                package
+                    .files
+                    .iter()
+                    .find(|file| package.provides(file, filename))
+                    .map(|file| CollectionFile {
+                        package: package.name.clone(),
+                        absolute_path: file.clone(),
+                    })
+            })
     }
 }
 
+#[derive(Clone, Debug)]
 pub enum ResolvedLink {
     DescriptionFile(CollectionFile),
     LocalPath(PathBuf),
     Url(String),
+    Custom { scheme: String, address: String },
+}
+
+impl ResolvedLink {
+    /// Name of this file in the origins of the definitions read from it,
+    /// independent of the machine the model is built on. Files of cargo
+    /// packages are named by cargo links, local files by their paths relative
+    /// to the directory of the file `root`.
+    pub fn source_name(&self, root: &ResolvedLink) -> String {
+        match self {
+            ResolvedLink::DescriptionFile(CollectionFile {
+                package,
+                absolute_path,
+            }) => format!(
+                "{}://{package}{}{}",
+                Link::CARGO_FORMAT_PREFIX,
+                Link::PACKAGE_SEPARATOR,
+                absolute_path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+            ),
+            ResolvedLink::LocalPath(path) => {
+                let directory = match root {
+                    ResolvedLink::DescriptionFile(CollectionFile { absolute_path, .. })
+                    | ResolvedLink::LocalPath(absolute_path) => absolute_path.parent(),
+                    ResolvedLink::Url(_) | ResolvedLink::Custom { .. } => None,
+                };
+                directory
+                    .and_then(|directory| relative_path(path, directory))
+                    .unwrap_or_else(|| self.to_string())
+            }
+            ResolvedLink::Url(_) | ResolvedLink::Custom { .. } => self.to_string(),
+        }
+    }
+
+    /// Location of `path` relative to the directory of this file. Files of a
+    /// cargo package stay attributed to this package. Relative links in files
+    /// with custom schemes are resolved by `fetcher`.
+    pub fn join(&self, path: &str, fetcher: &dyn Fetcher) -> Result<ResolvedLink, LinkError> {
+        match self {
+            ResolvedLink::DescriptionFile(CollectionFile {
+                package,
+                absolute_path,
+            }) => Ok(ResolvedLink::DescriptionFile(CollectionFile {
+                package: package.clone(),
+                absolute_path: sibling(absolute_path, path),
+            })),
+            ResolvedLink::LocalPath(base) => Ok(ResolvedLink::LocalPath(sibling(base, path))),
+            ResolvedLink::Url(base) => reqwest::Url::parse(base)
+                .and_then(|base| base.join(path))
+                .map(|url| ResolvedLink::Url(url.into()))
+                .map_err(|_| {
+                    LinkError::FailedResolution(ResolutionError::GenericLinkResolutionError {
+                        link: Box::new(Link::FileLink {
+                            path: path.to_owned(),
+                            sha256: None,
+                        }),
+                    })
+                }),
+            ResolvedLink::Custom { scheme, address } => fetcher
+                .join_custom(scheme, address, path)
+                .map(|address| ResolvedLink::Custom {
+                    scheme: scheme.clone(),
+                    address,
+                })
+                .ok_or_else(|| {
+                    LinkError::FailedResolution(ResolutionError::UnsupportedRelativeLink {
+                        base: self.to_string(),
+                        path: path.to_owned(),
+                    })
+                }),
+        }
+    }
+
+    /// Local files matching this location, if it is a pattern, in sorted
+    /// order. A directory matches the files with error definitions directly
+    /// inside it. Other locations are returned as is. Directories are listed
+    /// by `fetcher`.
+    pub fn expand(&self, fetcher: &dyn Fetcher) -> Result<Vec<ResolvedLink>, LinkError> {
+        let (path, package) = match self {
+            ResolvedLink::DescriptionFile(CollectionFile {
+                package,
+                absolute_path,
+            }) => (absolute_path, Some(package)),
+            ResolvedLink::LocalPath(path) => (path, None),
+            ResolvedLink::Url(_) | ResolvedLink::Custom { .. } => return Ok(vec![self.clone()]),
+        };
+        let pattern = path.to_string_lossy();
+        let has_wildcards = pattern.contains(['*', '?', '[']);
+        let listing = if has_wildcards {
+            None
+        } else {
+            list(fetcher, path)?
+        };
+        // Trailing slashes do not survive path normalization.
+        let is_directory = pattern.ends_with('/') || listing.is_some();
+        if !is_directory && !has_wildcards {
+            return Ok(vec![self.clone()]);
+        }
+
+        let mut matches: Vec<PathBuf> = if is_directory {
+            listing
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|entry| match entry {
+                    DirectoryEntry::File(path)
+                        if FileFormat::from_extension(&path.to_string_lossy()).is_some() =>
+                    {
+                        Some(path)
+                    }
+                    _ => None,
+                })
+                .collect()
+        } else {
+            matching_files(&pattern, fetcher)?
+        };
+        matches.sort();
+
+        Ok(matches
+            .into_iter()
+            .map(|absolute_path| match package {
+                Some(package) => ResolvedLink::DescriptionFile(CollectionFile {
+                    package: package.clone(),
+                    absolute_path,
+                }),
+                None => ResolvedLink::LocalPath(absolute_path),
+            })
+            .collect())
+    }
+}
+
+fn list(fetcher: &dyn Fetcher, directory: &Path) -> Result<Option<Vec<DirectoryEntry>>, LinkError> {
+    fetcher
+        .list_local(directory)
+        .map_err(|inner| LinkError::FailedListing {
+            directory: directory.display().to_string(),
+            inner,
+        })
+}
+
+/// Local files matching the glob `pattern`. The files are looked for below
+/// the longest directory without wildcards, no deeper than the pattern goes.
+fn matching_files(pattern: &str, fetcher: &dyn Fetcher) -> Result<Vec<PathBuf>, LinkError> {
+    let matcher = glob::Pattern::new(pattern)
+        .map_err(|e| LinkError::InvalidPattern(format!("{pattern}: {e}")))?;
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..glob::MatchOptions::new()
+    };
+    let components: Vec<_> = Path::new(pattern).components().collect();
+    let literal = components
+        .iter()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+        .count();
+    let base: PathBuf = components[..literal].iter().collect();
+    let depth = if pattern.contains("**") {
+        None
+    } else {
+        Some(components.len() - literal)
+    };
+
+    let mut files = vec![];
+    walk(fetcher, &base, depth, &mut files)?;
+    files.retain(|file| matcher.matches_path_with(file, options));
+    Ok(files)
+}
+
+/// Collect the files in `directory` and in its subdirectories, down to
+/// `depth` levels if it is set.
+fn walk(
+    fetcher: &dyn Fetcher,
+    directory: &Path,
+    depth: Option<usize>,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), LinkError> {
+    if depth == Some(0) {
+        return Ok(());
+    }
+    for entry in list(fetcher, directory)?.unwrap_or_default() {
+        match entry {
+            DirectoryEntry::File(path) => files.push(path),
+            DirectoryEntry::Directory(path) => {
+                walk(fetcher, &path, depth.map(|depth| depth - 1), files)?
+            }
+        }
+    }
+    Ok(())
+}
+
+impl std::fmt::Display for ResolvedLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolvedLink::DescriptionFile(CollectionFile { absolute_path, .. })
+            | ResolvedLink::LocalPath(absolute_path) => absolute_path.display().fmt(f),
+            ResolvedLink::Url(url) => f.write_str(url),
+            ResolvedLink::Custom { scheme, address } => write!(f, "{scheme}://{address}"),
+        }
+    }
+}
+
+/// Path from `directory` to `path`, with `/` as separator. Both should be
+/// absolute or both relative, and `directory` should not climb up with `..`.
+fn relative_path(path: &Path, directory: &Path) -> Option<String> {
+    if path.is_absolute() != directory.is_absolute() {
+        return None;
+    }
+    let path: Vec<_> = path
+        .components()
+        .filter(|c| c != &Component::CurDir)
+        .collect();
+    let directory: Vec<_> = directory
+        .components()
+        .filter(|c| c != &Component::CurDir)
+        .collect();
+    let common = path
+        .iter()
+        .zip(&directory)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if directory[common..]
+        .iter()
+        .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        return None;
+    }
+    let parts: Vec<_> = std::iter::repeat_n("..".into(), directory.len() - common)
+        .chain(
+            path[common..]
+                .iter()
+                .map(|c| c.as_os_str().to_string_lossy()),
+        )
+        .collect();
+    Some(parts.join("/"))
+}
+
+fn sibling(file: &Path, path: &str) -> PathBuf {
+    file.parent()
+        .unwrap_or(Path::new(""))
+        .join(path)
+        .components()
+        .filter(|c| c != &Component::CurDir)
+        .collect()
 }
 
 pub fn resolve(query_link: &Link, context: &ResolutionContext) -> Result<ResolvedLink, LinkError> {
     match query_link {
-        link @ Link::PackageLink { .. } => {
-            if let Some(df) = context.files.iter().find(|file| Link::matches(link, file)) {
-                Ok(ResolvedLink::DescriptionFile(df.clone()))
+        link @ Link::PackageLink { filename, .. } => {
+            let file = if link.is_pattern() {
+                context.find_package(link).map(|package| CollectionFile {
+                    package: package.name.clone(),
+                    absolute_path: package.root.join(filename),
+                })
             } else {
-                Err(LinkError::FailedResolution(
-                    ResolutionError::CargoLinkResolutionError {
-                        link: link.clone(),
-                        context: context.clone(),
-                    },
-                ))
-            }
+                context.find_file(link)
+            };
+            file.map(ResolvedLink::DescriptionFile).ok_or_else(|| {
+                LinkError::FailedResolution(ResolutionError::CargoLinkResolutionError {
+                    link: Box::new(link.clone()),
+                    context: context.clone(),
+                })
+            })
+        }
+        Link::FileLink { path, .. } => Ok(ResolvedLink::LocalPath(path.into())),
+        Link::URL { url, .. } => Ok(ResolvedLink::Url(url.to_owned())),
+        Link::Custom {
+            scheme, address, ..
+        } => Ok(ResolvedLink::Custom {
+            scheme: scheme.clone(),
+            address: address.clone(),
+        }),
+    }
+}
+
+/// Resolve a link found in the file at `base`. Relative file paths are taken
+/// relative to the directory, URL or cargo package holding this file; other
+/// links are resolved as usual.
+pub fn resolve_relative(
+    query_link: &Link,
+    context: &ResolutionContext,
+    base: &ResolvedLink,
+    fetcher: &dyn Fetcher,
+) -> Result<ResolvedLink, LinkError> {
+    match query_link {
+        Link::FileLink { path, .. } if Path::new(path).is_relative() => base.join(path, fetcher),
+        _ => resolve(query_link, context),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use crate::loader::fetcher::Fetcher;
+    use crate::loader::fetcher::FilesystemFetcher;
+    use crate::loader::fetcher::InMemoryFetcher;
+
+    use super::ResolvedLink;
+
+    const FILES: [&str; 5] = ["b.json", "a.toml", "c.json", "notes.md", "nested/d.json"];
+
+    /// Expand the patterns relative to `directory`, served by `fetcher`.
+    fn check_expansion(directory: &Path, fetcher: &dyn Fetcher) {
+        let expand = |pattern: &str| -> Vec<PathBuf> {
+            ResolvedLink::LocalPath(directory.join(pattern))
+                .expand(fetcher)
+                .unwrap()
+                .into_iter()
+                .map(|location| match location {
+                    ResolvedLink::LocalPath(path) => path.strip_prefix(directory).unwrap().into(),
+                    _ => unreachable!(),
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            expand("*.json"),
+            [PathBuf::from("b.json"), PathBuf::from("c.json")]
+        );
+        assert_eq!(
+            expand("./"),
+            [
+                PathBuf::from("a.toml"),
+                PathBuf::from("b.json"),
+                PathBuf::from("c.json")
+            ]
+        );
+        assert_eq!(expand("**/d.json"), [PathBuf::from("nested/d.json")]);
+        assert_eq!(expand("*/*.json"), [PathBuf::from("nested/d.json")]);
+        assert!(expand("*.yaml").is_empty());
+    }
+
+    #[test]
+    fn patterns_expand_in_sorted_order() {
+        let directory =
+            std::env::temp_dir().join(format!("zksync-error-glob-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(directory.join("nested")).unwrap();
+        for file in FILES {
+            std::fs::write(directory.join(file), "").unwrap();
         }
-        Link::FileLink { path } => Ok(ResolvedLink::LocalPath(path.into())),
-        Link::URL { url } => Ok(ResolvedLink::Url(url.to_owned())),
+        check_expansion(&directory, &FilesystemFetcher);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn patterns_expand_through_the_fetcher() {
+        let fetcher = FILES
+            .iter()
+            .fold(InMemoryFetcher::default(), |fetcher, file| {
+                fetcher.with_file(format!("defs/{file}"), "")
+            })
+            .with_file("https://example.com/defs/e.json", "");
+        check_expansion(Path::new("defs"), &fetcher);
+    }
+
+    #[test]
+    fn source_names_are_relative_to_the_root() {
+        let root = ResolvedLink::LocalPath("/home/ci/project/errors/root.json".into());
+        let name = |path: &str| ResolvedLink::LocalPath(path.into()).source_name(&root);
+        assert_eq!(name("/home/ci/project/errors/root.json"), "root.json");
+        assert_eq!(name("/home/ci/project/errors/core/a.json"), "core/a.json");
+        assert_eq!(name("/home/ci/project/extra/b.toml"), "../extra/b.toml");
+
+        let root = ResolvedLink::LocalPath("./root.json".into());
+        assert_eq!(
+            ResolvedLink::LocalPath("core/../core/a.json".into()).source_name(&root),
+            "core/../core/a.json"
+        );
+        assert_eq!(
+            ResolvedLink::LocalPath("/etc/a.json".into()).source_name(&root),
+            "/etc/a.json"
+        );
     }
 }
@@ -0,0 +1,144 @@
+//!
+//! Access to the files of the repository as they were at a given git revision.
+//!
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use super::error::RevisionError;
+
+/// Copy of the repository tree at some revision, removed when dropped.
+pub struct RevisionCheckout {
+    root: PathBuf,
+    /// Directory of the copy that corresponds to the current working
+    /// directory.
+    pub working_directory: PathBuf,
+}
+
+fn describe(command: &Command) -> String {
+    let arguments: Vec<_> = command
+        .get_args()
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect();
+    format!(
+        "{} {}",
+        command.get_program().to_string_lossy(),
+        arguments.join(" ")
+    )
+}
+
+/// Standard output of `command`, which should succeed.
+fn run_raw(command: &mut Command) -> Result<String, RevisionError> {
+    let output = command
+        .output()
+        .map_err(|inner| RevisionError::CommandFailed {
+            command: describe(command),
+            inner,
+        })?;
+    if !output.status.success() {
+        return Err(RevisionError::CommandUnsuccessful {
+            command: describe(command),
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn run(command: &mut Command) -> Result<String, RevisionError> {
+    Ok(run_raw(command)?.trim().to_owned())
+}
+
+/// Reject the arguments that git would take for options. Revisions and paths
+/// may come from remote definition files.
+fn check_argument(kind: &'static str, value: &str) -> Result<(), RevisionError> {
+    if value.starts_with('-') {
+        Err(RevisionError::OptionLike {
+            kind,
+            value: value.to_owned(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Contents of `file` at `revision` of the git repository at `repository`.
+pub fn show_file(repository: &Path, revision: &str, file: &str) -> Result<String, RevisionError> {
+    check_argument("revision", revision)?;
+    check_argument("path", file)?;
+    run_raw(
+        Command::new("git")
+            .arg("-C")
+            .arg(repository)
+            .args(["show", "--end-of-options"])
+            .arg(format!("{revision}:{file}")),
+    )
+}
+
+impl RevisionCheckout {
+    /// Extract the tree of `revision` of the repository containing the current
+    /// working directory into a temporary directory.
+    pub fn new(revision: &str) -> Result<Self, RevisionError> {
+        check_argument("revision", revision)?;
+        let tree = format!("{revision}^{{tree}}");
+        run(Command::new("git").args([
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "--end-of-options",
+            &tree,
+        ]))?;
+        let prefix = run(Command::new("git").args(["rev-parse", "--show-prefix"]))?;
+
+        let name: String = revision
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let root = std::env::temp_dir().join(format!("zksync-error-{}-{name}", std::process::id()));
+        std::fs::create_dir_all(&root)?;
+        let checkout = Self {
+            working_directory: root.join(prefix),
+            root,
+        };
+
+        let mut archive = Command::new("git");
+        archive
+            .args(["archive", "--format=tar", "--end-of-options", &tree])
+            .stdout(Stdio::piped());
+        let mut archive_process =
+            archive
+                .spawn()
+                .map_err(|inner| RevisionError::CommandFailed {
+                    command: describe(&archive),
+                    inner,
+                })?;
+        let stdout = archive_process.stdout.take().expect("Internal error");
+        run(Command::new("tar")
+            .arg("-x")
+            .arg("-C")
+            .arg(&checkout.root)
+            .stdin(stdout))?;
+        let status = archive_process.wait()?;
+        if !status.success() {
+            return Err(RevisionError::CommandUnsuccessful {
+                command: describe(&archive),
+                status,
+                stderr: String::new(),
+            });
+        }
+
+        Ok(checkout)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl Drop for RevisionCheckout {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
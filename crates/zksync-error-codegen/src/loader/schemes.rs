@@ -0,0 +1,243 @@
+//!
+//! Custom link schemes. A link `<scheme>://<address>` with a scheme other than
+//! `cargo`, `file`, `http` and `https` is served by the resolver registered for
+//! this scheme in a [`SchemeRegistry`].
+//!
+
+use std::collections::BTreeMap;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::error::FetchError;
+use super::fetcher::DirectoryEntry;
+use super::fetcher::Fetcher;
+use super::revision::show_file;
+
+/// Turns addresses of a link scheme into the contents of the files.
+pub trait SchemeResolver {
+    /// Contents of the file at `address`.
+    fn fetch(&self, address: &str) -> Result<String, FetchError>;
+
+    /// Address of `path` relative to the file at `address`, if the scheme
+    /// supports relative links.
+    fn join(&self, _address: &str, _path: &str) -> Option<String> {
+        None
+    }
+}
+
+/// `env://VAR`: contents of the environment variable `VAR`.
+pub struct EnvScheme;
+
+impl EnvScheme {
+    pub const NAME: &str = "env";
+}
+
+impl SchemeResolver for EnvScheme {
+    fn fetch(&self, address: &str) -> Result<String, FetchError> {
+        std::env::var(address).map_err(|_| FetchError::MissingEnvironmentVariable {
+            variable: address.to_owned(),
+        })
+    }
+}
+
+/// `git://<repo-path>@<rev>:<file>`: the file `<file>` at the revision `<rev>`
+/// of the local git repository at `<repo-path>`. Relative links are resolved
+/// in the same repository at the same revision.
+pub struct GitScheme;
+
+impl GitScheme {
+    pub const NAME: &str = "git";
+
+    /// Split an address into the repository, the revision and the file. Git
+    /// revisions can not contain `:`, so the revision follows the last `@`
+    /// that comes before a `:`; the repository path itself may contain `:`,
+    /// as Windows paths do.
+    fn parse(address: &str) -> Result<(&str, &str, &str), FetchError> {
+        address
+            .rmatch_indices('@')
+            .find_map(|(at, _)| {
+                let (revision, file) = address[at + 1..].split_once(':')?;
+                Some((&address[..at], revision, file))
+            })
+            .filter(|(repository, revision, file)| {
+                !repository.is_empty() && !revision.is_empty() && !file.is_empty()
+            })
+            .ok_or_else(|| FetchError::InvalidAddress {
+                scheme: Self::NAME.to_owned(),
+                address: address.to_owned(),
+                expected: "<repo-path>@<rev>:<file>".to_owned(),
+            })
+    }
+}
+
+impl SchemeResolver for GitScheme {
+    fn fetch(&self, address: &str) -> Result<String, FetchError> {
+        let (repository, revision, file) = Self::parse(address)?;
+        log::info!("Trying to read file `{file}` at revision `{revision}` of `{repository}`");
+        Ok(show_file(Path::new(repository), revision, file)?)
+    }
+
+    fn join(&self, address: &str, path: &str) -> Option<String> {
+        let (repository, revision, file) = Self::parse(address).ok()?;
+        // Paths in git trees can not contain `.` or `..`.
+        let mut joined = PathBuf::new();
+        for component in Path::new(file).parent()?.join(path).components() {
+            match component {
+                Component::Normal(part) => joined.push(part),
+                Component::ParentDir => {
+                    if !joined.pop() {
+                        return None;
+                    }
+                }
+                Component::CurDir => {}
+                Component::RootDir | Component::Prefix(_) => return None,
+            }
+        }
+        Some(format!(
+            "{repository}@{revision}:{}",
+            joined.to_string_lossy()
+        ))
+    }
+}
+
+/// Fetcher serving links with registered custom schemes, and delegating the
+/// others to `inner`.
+pub struct SchemeRegistry<F: Fetcher> {
+    inner: F,
+    schemes: BTreeMap<String, Box<dyn SchemeResolver>>,
+}
+
+impl<F: Fetcher> SchemeRegistry<F> {
+    pub fn new(inner: F) -> Self {
+        Self {
+            inner,
+            schemes: BTreeMap::new(),
+        }
+    }
+
+    /// Registry with the schemes `env` and `git`.
+    pub fn with_builtins(inner: F) -> Self {
+        Self::new(inner)
+            .with_scheme(EnvScheme::NAME, EnvScheme)
+            .with_scheme(GitScheme::NAME, GitScheme)
+    }
+
+    /// Serve the links `<scheme>://...` with `resolver`, replacing the resolver
+    /// previously registered for `scheme`.
+    pub fn with_scheme(
+        mut self,
+        scheme: impl Into<String>,
+        resolver: impl SchemeResolver + 'static,
+    ) -> Self {
+        self.schemes.insert(scheme.into(), Box::new(resolver));
+        self
+    }
+}
+
+impl<F: Fetcher> Fetcher for SchemeRegistry<F> {
+    fn read_local(&self, path: &Path) -> Result<String, FetchError> {
+        self.inner.read_local(path)
+    }
+
+    fn list_local(&self, path: &Path) -> Result<Option<Vec<DirectoryEntry>>, FetchError> {
+        self.inner.list_local(path)
+    }
+
+    fn canonicalize_local(&self, path: &Path) -> PathBuf {
+        self.inner.canonicalize_local(path)
+    }
+
+    fn fetch_remote(&self, url: &str) -> Result<String, FetchError> {
+        self.inner.fetch_remote(url)
+    }
+
+    fn fetch_custom(&self, scheme: &str, address: &str) -> Result<String, FetchError> {
+        match self.schemes.get(scheme) {
+            Some(resolver) => resolver.fetch(address),
+            None => self.inner.fetch_custom(scheme, address),
+        }
+    }
+
+    fn join_custom(&self, scheme: &str, address: &str, path: &str) -> Option<String> {
+        match self.schemes.get(scheme) {
+            Some(resolver) => resolver.join(address, path),
+            None => self.inner.join_custom(scheme, address, path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::EnvScheme;
+    use super::GitScheme;
+    use super::SchemeResolver as _;
+    use crate::loader::error::FetchError;
+    use crate::loader::error::RevisionError;
+
+    #[test]
+    fn git_addresses() {
+        assert_eq!(
+            GitScheme.join("../errors@v1.0:defs/core/a.json", "../b.json"),
+            Some("../errors@v1.0:defs/b.json".to_owned())
+        );
+        assert_eq!(GitScheme.join("repo@main:a.json", "../b.json"), None);
+        assert!(GitScheme::parse("repo:a.json").is_err());
+        assert_eq!(
+            GitScheme::parse(r"C:\work\errors@main:defs/a@b.json").unwrap(),
+            (r"C:\work\errors", "main", "defs/a@b.json")
+        );
+    }
+
+    #[test]
+    fn git_scheme_reads_committed_files() {
+        let repository =
+            std::env::temp_dir().join(format!("zksync-error-git-scheme-{}", std::process::id()));
+        std::fs::create_dir_all(repository.join("defs")).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&repository)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?}");
+        };
+        git(&["init", "--quiet"]);
+        std::fs::write(repository.join("defs/a.json"), "committed").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "Definitions"]);
+        git(&["tag", "v1"]);
+        std::fs::write(repository.join("defs/a.json"), "changed").unwrap();
+
+        let address =
+            |revision: &str, file: &str| format!("{}@{revision}:{file}", repository.display());
+        let fetched = GitScheme.fetch(&address("v1", "defs/a.json"));
+        let option = GitScheme.fetch(&address("--output=/tmp/zksync-error-injected", "a.json"));
+        std::fs::remove_dir_all(&repository).unwrap();
+
+        assert_eq!(fetched.unwrap(), "committed");
+        assert!(matches!(
+            option,
+            Err(FetchError::RevisionError(RevisionError::OptionLike {
+                kind: "revision",
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn env_scheme_reads_variables() {
+        let variable = "ZKSYNC_ERROR_ENV_SCHEME_TEST";
+        std::env::set_var(variable, r#"{ "types": [] }"#);
+        assert_eq!(EnvScheme.fetch(variable).unwrap(), r#"{ "types": [] }"#);
+        std::env::remove_var(variable);
+        assert!(matches!(
+            EnvScheme.fetch(variable),
+            Err(FetchError::MissingEnvironmentVariable { variable: v }) if v == variable
+        ));
+    }
+}
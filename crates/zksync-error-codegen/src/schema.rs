@@ -0,0 +1,154 @@
+//!
+//! JSON Schemas of the files holding fragments of error hierarchy, derived
+//! from their layouts in [`crate::description`].
+//!
+
+use std::path::PathBuf;
+
+use schemars::gen::SchemaSettings;
+use schemars::schema::RootSchema;
+use schemars::JsonSchema;
+
+use crate::codegen::file::File;
+use crate::description::Component;
+use crate::description::Domain;
+use crate::description::Error;
+use crate::description::FlatRoot;
+use crate::description::Overlay;
+use crate::description::Root;
+use crate::loader::diagnostic::Excerpt;
+use crate::loader::format::Shape;
+
+fn schema_of<T: JsonSchema>() -> RootSchema {
+    SchemaSettings::draft07()
+        .into_generator()
+        .into_root_schema_for::<T>()
+}
+
+/// Schema of the files describing an object of the given kind.
+pub fn schema(shape: Shape) -> RootSchema {
+    match shape {
+        Shape::Root => schema_of::<Root>(),
+        Shape::FlatRoot => schema_of::<FlatRoot>(),
+        Shape::Domain => schema_of::<Domain>(),
+        Shape::Component => schema_of::<Component>(),
+        Shape::Overlay => schema_of::<Overlay>(),
+        Shape::Errors => schema_of::<Vec<Error>>(),
+    }
+}
+
+/// Schema accepting a file of any kind, for editors that can not tell the
+/// kinds apart by the file name.
+pub fn collection_schema() -> RootSchema {
+    let mut generator = SchemaSettings::draft07().into_generator();
+    let variants = vec![
+        generator.subschema_for::<Root>(),
+        generator.subschema_for::<FlatRoot>(),
+        generator.subschema_for::<Domain>(),
+        generator.subschema_for::<Component>(),
+        generator.subschema_for::<Overlay>(),
+        generator.subschema_for::<Vec<Error>>(),
+    ];
+    let mut root = generator.into_root_schema_for::<()>();
+    root.schema = schemars::schema::SchemaObject {
+        metadata: Some(Box::new(schemars::schema::Metadata {
+            title: Some("Collection".to_owned()),
+            description: Some(
+                "A fragment of error hierarchy: a master error database, a domain, a component, an overlay or an array of errors.".to_owned(),
+            ),
+            ..Default::default()
+        })),
+        subschemas: Some(Box::new(schemars::schema::SubschemaValidation {
+            any_of: Some(variants),
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+    root
+}
+
+/// Files `<kind>.schema.json` with the schema of every kind of file, and
+/// `collection.schema.json` accepting any of them.
+pub fn schema_files() -> Result<Vec<File>, serde_json::Error> {
+    let mut files = Vec::new();
+    for shape in Shape::ALL {
+        files.push(schema_file(shape.name(), &schema(shape))?);
+    }
+    files.push(schema_file("collection", &collection_schema())?);
+    Ok(files)
+}
+
+fn schema_file(name: &str, schema: &RootSchema) -> Result<File, serde_json::Error> {
+    Ok(File {
+        relative_path: PathBuf::from(format!("{name}.schema.json")),
+        content: serde_json::to_string_pretty(schema)?,
+    })
+}
+
+/// Value of a file that does not match the schema of its kind.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value; `/` for the whole file.
+    pub pointer: String,
+    pub message: String,
+    /// Position of the offending value, if it is known.
+    pub excerpt: Option<Excerpt>,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}", self.pointer, self.message)?;
+        if let Some(excerpt) = &self.excerpt {
+            write!(f, "\n{excerpt}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Check a parsed file against the schema of its kind. The positions of the
+/// violations are not known yet.
+pub fn validate(value: &serde_json::Value, shape: Shape) -> Result<(), Vec<SchemaViolation>> {
+    let schema = serde_json::to_value(schema(shape)).expect("Schemas are serializable");
+    let compiled = jsonschema::JSONSchema::compile(&schema).expect("Generated schemas are valid");
+    compiled.validate(value).map_err(|errors| {
+        errors
+            .map(|error| {
+                let path = error.instance_path.to_string();
+                SchemaViolation {
+                    pointer: if path.is_empty() {
+                        "/".to_owned()
+                    } else {
+                        path
+                    },
+                    message: error.to_string(),
+                    excerpt: None,
+                }
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loader::format::Shape;
+
+    use super::validate;
+
+    #[test]
+    fn violations_point_to_the_offending_values() {
+        let component = serde_json::json!({
+            "component_name": "seq",
+            "component_code": 1,
+            "errors": [ { "name": "A", "code": 1 } ]
+        });
+        let violations = validate(&component, Shape::Component).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert!(
+            violations[0]
+                .to_string()
+                .starts_with("`/errors/0`: \"message\" is a required property"),
+            "{violations:?}"
+        );
+        assert!(validate(&component, Shape::Errors).is_err());
+    }
+}
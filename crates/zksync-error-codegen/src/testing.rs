@@ -0,0 +1,52 @@
+//!
+//! Models built from small definition files, for the tests of the backends.
+//!
+
+use zksync_error_model::inner::Model;
+
+use crate::loader::builder::build_model;
+use crate::loader::builder::ModelBuildingOptions;
+use crate::loader::fetcher::InMemoryFetcher;
+use crate::loader::link::Link;
+
+/// Types `uint` and `string`, and the domain `Core` with the component
+/// `Sequencer` (identifiers `core` and `seq`). Errors are appended to it as
+/// `[[domains.components.errors]]` tables.
+pub const ROOT: &str = r#"
+[[types]]
+name = "uint"
+description = "Unsigned integer"
+bindings.rust = { name = "u32", path = "" }
+bindings.typescript = { name = "number", path = "" }
+
+[[types]]
+name = "string"
+description = "Unicode string"
+bindings.rust = { name = "String", path = "" }
+bindings.typescript = { name = "string", path = "" }
+
+[[domains]]
+domain_name = "Core"
+domain_code = 1
+identifier_encoding = "core"
+
+[[domains.components]]
+component_name = "Sequencer"
+component_code = 2
+identifier_encoding = "seq"
+"#;
+
+/// Model of [`ROOT`] with the given errors.
+pub fn model(errors: &str) -> Model {
+    model_of(&format!("{ROOT}\n{errors}"))
+}
+
+/// Model of the given root definitions.
+pub fn model_of(definitions: &str) -> Model {
+    let fetcher = InMemoryFetcher::default().with_file("root.toml", definitions);
+    let options = ModelBuildingOptions {
+        languages: vec!["rust".into(), "typescript".into()],
+        ..Default::default()
+    };
+    build_model(&Link::parse("root.toml").unwrap(), &options, &fetcher).unwrap()
+}
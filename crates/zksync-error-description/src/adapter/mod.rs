@@ -8,6 +8,36 @@ impl From<inner::TargetLanguageType> for model::TargetLanguageType {
     }
 }
 
+impl From<inner::Origin> for model::Origin {
+    fn from(val: inner::Origin) -> Self {
+        let inner::Origin { source, pointer } = val;
+        model::Origin { source, pointer }
+    }
+}
+
+impl From<inner::CodeRange> for model::CodeRange {
+    fn from(val: inner::CodeRange) -> Self {
+        let inner::CodeRange { start, end } = val;
+        model::CodeRange { start, end }
+    }
+}
+
+impl From<inner::Lifecycle> for model::Lifecycle {
+    fn from(val: inner::Lifecycle) -> Self {
+        match val {
+            inner::Lifecycle::Active => model::Lifecycle::Active,
+            inner::Lifecycle::Deprecated {
+                message,
+                replacement,
+            } => model::Lifecycle::Deprecated {
+                message,
+                replacement,
+            },
+            inner::Lifecycle::Retired => model::Lifecycle::Retired,
+        }
+    }
+}
+
 impl From<inner::TypeMetadata> for model::TypeMetadata {
     fn from(val: inner::TypeMetadata) -> Self {
         let inner::TypeMetadata { description } = val;
@@ -21,11 +51,13 @@ impl From<inner::TypeDescription> for model::TypeDescription {
             name,
             meta,
             bindings,
+            origin,
         } = val;
         model::TypeDescription {
             name,
             meta: meta.into(),
             bindings: bindings.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            origin: origin.map(Into::into),
         }
     }
 }
@@ -39,6 +71,7 @@ impl From<inner::DomainMetadata> for model::DomainMetadata {
             bindings,
             identifier,
             description,
+            origin,
         } = val;
         model::DomainMetadata {
             name,
@@ -47,6 +80,7 @@ impl From<inner::DomainMetadata> for model::DomainMetadata {
             bindings,
             identifier,
             description,
+            origin: origin.map(Into::into),
         }
     }
 }
@@ -77,6 +111,8 @@ impl From<inner::ComponentMetadata> for model::ComponentMetadata {
             bindings,
             identifier,
             description,
+            reserved,
+            origin,
         } = val;
         model::ComponentMetadata {
             name,
@@ -85,6 +121,8 @@ impl From<inner::ComponentMetadata> for model::ComponentMetadata {
             bindings,
             identifier,
             description,
+            reserved: reserved.into_iter().map(Into::into).collect(),
+            origin: origin.map(Into::into),
         }
     }
 }
@@ -101,6 +139,8 @@ impl From<inner::ErrorDescription> for model::ErrorDescription {
             fields,
             documentation,
             bindings,
+            lifecycle,
+            origin,
         } = val;
         model::ErrorDescription {
             domain,
@@ -112,14 +152,28 @@ impl From<inner::ErrorDescription> for model::ErrorDescription {
             fields: fields.into_iter().map(|f| f.into()).collect(),
             documentation: documentation.map(|d| d.into()),
             bindings: bindings.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            lifecycle: lifecycle.into(),
+            origin: origin.map(Into::into),
         }
     }
 }
 
 impl From<inner::FieldDescription> for model::FieldDescription {
     fn from(val: inner::FieldDescription) -> Self {
-        let inner::FieldDescription { name, r#type } = val;
-        model::FieldDescription { name, r#type }
+        let inner::FieldDescription {
+            name,
+            r#type,
+            description,
+            optional,
+            default,
+        } = val;
+        model::FieldDescription {
+            name,
+            r#type,
+            description,
+            optional,
+            default,
+        }
     }
 }
 
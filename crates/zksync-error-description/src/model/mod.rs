@@ -20,6 +20,21 @@ pub struct TargetLanguageType {
     pub path: String,
 }
 
+/// Where an element of the hierarchy is defined: the file, URL or file of a
+/// cargo package, and the JSON pointer to the element in this file.
+#[non_exhaustive]
+#[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Origin {
+    pub source: String,
+    pub pointer: String,
+}
+
+impl std::fmt::Display for Origin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}", self.source, self.pointer)
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TypeMetadata {
@@ -32,6 +47,8 @@ pub struct TypeDescription {
     pub name: TypeName,
     pub meta: TypeMetadata,
     pub bindings: BTreeMap<LanguageName, TargetLanguageType>,
+    #[serde(default)]
+    pub origin: Option<Origin>,
 }
 
 #[non_exhaustive]
@@ -43,6 +60,8 @@ pub struct DomainMetadata {
     pub bindings: BTreeMap<LanguageName, String>,
     pub identifier: String,
     pub description: String,
+    #[serde(default)]
+    pub origin: Option<Origin>,
 }
 #[non_exhaustive]
 #[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
@@ -62,6 +81,34 @@ pub struct ComponentMetadata {
     pub bindings: BTreeMap<LanguageName, String>,
     pub identifier: String,
     pub description: String,
+    /// Codes that no error of this component may use.
+    #[serde(default)]
+    pub reserved: Vec<CodeRange>,
+    #[serde(default)]
+    pub origin: Option<Origin>,
+}
+
+/// Inclusive range of error codes.
+#[non_exhaustive]
+#[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CodeRange {
+    pub start: ErrorCode,
+    pub end: ErrorCode,
+}
+
+/// Stage in the life of an error. Retired errors are absent from the generated
+/// code, and only keep their codes from being reused.
+#[non_exhaustive]
+#[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum Lifecycle {
+    #[default]
+    Active,
+    Deprecated {
+        message: String,
+        replacement: Option<String>,
+    },
+    Retired,
 }
 
 #[non_exhaustive]
@@ -76,6 +123,10 @@ pub struct ErrorDescription {
     pub fields: Vec<FieldDescription>,
     pub documentation: Option<ErrorDocumentation>,
     pub bindings: BTreeMap<LanguageName, TargetLanguageType>,
+    #[serde(default)]
+    pub lifecycle: Lifecycle,
+    #[serde(default)]
+    pub origin: Option<Origin>,
 }
 
 #[non_exhaustive]
@@ -83,6 +134,12 @@ pub struct ErrorDescription {
 pub struct FieldDescription {
     pub name: FieldName,
     pub r#type: TypeName,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default)]
+    pub default: Option<serde_json::Value>,
 }
 
 #[non_exhaustive]
@@ -131,10 +188,13 @@ impl ErrorHierarchy {
             model: self,
         }
     }
+}
+
+impl std::str::FromStr for ErrorHierarchy {
+    type Err = serde_json::Error;
 
-    pub fn from_str(serialized_model: &str) -> ErrorHierarchy {
-        let wrapped: WrappedErrorHierarchy =
-            serde_json::from_str(serialized_model).expect("Always valid");
-        wrapped.model
+    fn from_str(serialized_model: &str) -> Result<Self, Self::Err> {
+        let wrapped: WrappedErrorHierarchy = serde_json::from_str(serialized_model)?;
+        Ok(wrapped.model)
     }
 }
@@ -0,0 +1,705 @@
+//!
+//! Comparison of two versions of a model.
+//!
+//! A change is breaking if the code generated from the new version can not
+//! exchange serialized errors with the code generated from the old version, or
+//! if the code using the old generated code may stop compiling.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::inner::ComponentDescription;
+use crate::inner::DomainDescription;
+use crate::inner::ErrorDescription;
+use crate::inner::LanguageName;
+use crate::inner::Lifecycle;
+use crate::inner::Model;
+
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    CodeLayoutChanged {
+        old: String,
+        new: String,
+    },
+    TypeAdded {
+        r#type: String,
+    },
+    TypeRemoved {
+        r#type: String,
+    },
+    TypeBindingChanged {
+        r#type: String,
+        language: LanguageName,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    DomainAdded {
+        domain: String,
+    },
+    DomainRemoved {
+        domain: String,
+    },
+    DomainCodeChanged {
+        domain: String,
+        old: u32,
+        new: u32,
+    },
+    ComponentAdded {
+        component: String,
+    },
+    ComponentRemoved {
+        component: String,
+    },
+    ComponentCodeChanged {
+        component: String,
+        old: u32,
+        new: u32,
+    },
+    /// The identifier encoding of a domain or a component has changed.
+    IdentifierChanged {
+        element: String,
+        old: String,
+        new: String,
+    },
+    /// The name of a domain, a component or an error in a target language has
+    /// changed.
+    BindingChanged {
+        element: String,
+        language: LanguageName,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    ErrorAdded {
+        error: String,
+        code: u32,
+    },
+    ErrorRemoved {
+        error: String,
+        code: u32,
+    },
+    ErrorCodeChanged {
+        error: String,
+        old: u32,
+        new: u32,
+    },
+    ErrorDeprecated {
+        error: String,
+    },
+    /// The error is not generated anymore, though its code stays reserved.
+    ErrorRetired {
+        error: String,
+        code: u32,
+    },
+    /// The retired error is generated again.
+    ErrorRestored {
+        error: String,
+        code: u32,
+    },
+    /// The code of a removed error is now used by another error.
+    ErrorCodeReused {
+        component: String,
+        code: u32,
+        old_error: String,
+        new_error: String,
+    },
+    FieldAdded {
+        error: String,
+        field: String,
+        /// The field is optional or has a default value, so the errors
+        /// serialized without it are still accepted.
+        optional: bool,
+    },
+    FieldRemoved {
+        error: String,
+        field: String,
+    },
+    FieldTypeChanged {
+        error: String,
+        field: String,
+        old: String,
+        new: String,
+    },
+    /// The field became optional or required.
+    FieldOptionalityChanged {
+        error: String,
+        field: String,
+        optional: bool,
+    },
+    MessageChanged {
+        error: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl Change {
+    pub fn is_breaking(&self) -> bool {
+        !matches!(
+            self,
+            Change::TypeAdded { .. }
+                | Change::DomainAdded { .. }
+                | Change::ComponentAdded { .. }
+                | Change::ErrorAdded { .. }
+                | Change::ErrorRestored { .. }
+                | Change::MessageChanged { .. }
+                | Change::ErrorDeprecated { .. }
+                | Change::FieldAdded { optional: true, .. }
+                // Code generated for a language without bindings so far
+                // can not be affected.
+                | Change::TypeBindingChanged { old: None, .. }
+                | Change::BindingChanged { old: None, .. }
+        )
+    }
+}
+
+fn or_none(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("<none>")
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::CodeLayoutChanged { old, new } => {
+                write!(f, "Code layout changed from `{old}` to `{new}`.")
+            }
+            Change::TypeAdded { r#type } => write!(f, "Type `{type}` was added."),
+            Change::TypeRemoved { r#type } => write!(f, "Type `{type}` was removed."),
+            Change::TypeBindingChanged {
+                r#type,
+                language,
+                old,
+                new,
+            } => write!(
+                f,
+                "Type `{type}` is bound to `{}` instead of `{}` in `{language}`.",
+                or_none(new),
+                or_none(old)
+            ),
+            Change::DomainAdded { domain } => write!(f, "Domain `{domain}` was added."),
+            Change::DomainRemoved { domain } => write!(f, "Domain `{domain}` was removed."),
+            Change::DomainCodeChanged { domain, old, new } => {
+                write!(f, "Code of the domain `{domain}` changed from {old} to {new}.")
+            }
+            Change::ComponentAdded { component } => {
+                write!(f, "Component `{component}` was added.")
+            }
+            Change::ComponentRemoved { component } => {
+                write!(f, "Component `{component}` was removed.")
+            }
+            Change::ComponentCodeChanged {
+                component,
+                old,
+                new,
+            } => write!(
+                f,
+                "Code of the component `{component}` changed from {old} to {new}."
+            ),
+            Change::IdentifierChanged { element, old, new } => write!(
+                f,
+                "Identifier encoding of `{element}` changed from `{old}` to `{new}`."
+            ),
+            Change::BindingChanged {
+                element,
+                language,
+                old,
+                new,
+            } => write!(
+                f,
+                "`{element}` is named `{}` instead of `{}` in `{language}`.",
+                or_none(new),
+                or_none(old)
+            ),
+            Change::ErrorAdded { error, code } => {
+                write!(f, "Error `{error}` with the code {code} was added.")
+            }
+            Change::ErrorRemoved { error, code } => {
+                write!(f, "Error `{error}` with the code {code} was removed.")
+            }
+            Change::ErrorCodeChanged { error, old, new } => {
+                write!(f, "Code of the error `{error}` changed from {old} to {new}.")
+            }
+            Change::ErrorDeprecated { error } => write!(f, "Error `{error}` was deprecated."),
+            Change::ErrorRetired { error, code } => {
+                write!(f, "Error `{error}` with the code {code} was retired.")
+            }
+            Change::ErrorRestored { error, code } => write!(
+                f,
+                "Error `{error}` with the code {code} was restored after being retired."
+            ),
+            Change::ErrorCodeReused {
+                component,
+                code,
+                old_error,
+                new_error,
+            } => write!(
+                f,
+                "Code {code} of the component `{component}` belonged to `{old_error}` and is now used by `{new_error}`."
+            ),
+            Change::FieldAdded {
+                error,
+                field,
+                optional,
+            } => {
+                let kind = if *optional { "Optional field" } else { "Field" };
+                write!(f, "{kind} `{field}` was added to the error `{error}`.")
+            }
+            Change::FieldRemoved { error, field } => {
+                write!(f, "Field `{field}` was removed from the error `{error}`.")
+            }
+            Change::FieldTypeChanged {
+                error,
+                field,
+                old,
+                new,
+            } => write!(
+                f,
+                "Type of the field `{field}` of the error `{error}` changed from `{old}` to `{new}`."
+            ),
+            Change::FieldOptionalityChanged {
+                error,
+                field,
+                optional,
+            } => {
+                let state = if *optional { "optional" } else { "required" };
+                write!(f, "Field `{field}` of the error `{error}` became {state}.")
+            }
+            Change::MessageChanged { error, old, new } => write!(
+                f,
+                "Message of the error `{error}` changed from \"{old}\" to \"{new}\"."
+            ),
+        }
+    }
+}
+
+/// All differences between two versions of a model.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct CompatibilityReport {
+    pub changes: Vec<Change>,
+}
+
+#[derive(serde::Serialize)]
+struct ReportEntry<'a> {
+    breaking: bool,
+    description: String,
+    #[serde(flatten)]
+    change: &'a Change,
+}
+
+impl CompatibilityReport {
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(Change::is_breaking)
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let changes: Vec<_> = self
+            .changes
+            .iter()
+            .map(|change| ReportEntry {
+                breaking: change.is_breaking(),
+                description: change.to_string(),
+                change,
+            })
+            .collect();
+        serde_json::json!({
+            "breaking": self.is_breaking(),
+            "changes": changes,
+        })
+    }
+}
+
+impl std::fmt::Display for CompatibilityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.changes.is_empty() {
+            return f.write_str("No changes.");
+        }
+        let breaking = self.changes.iter().filter(|c| c.is_breaking()).count();
+        write!(
+            f,
+            "{breaking} breaking and {} non-breaking change(s):",
+            self.changes.len() - breaking
+        )?;
+        for change in &self.changes {
+            let severity = if change.is_breaking() {
+                "breaking"
+            } else {
+                "non-breaking"
+            };
+            write!(f, "\n - {severity}: {change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare the bindings of an element, reporting every language whose binding
+/// was changed, added or removed.
+fn compare_bindings<'a, T: 'a + ToString>(
+    element: &str,
+    old: impl IntoIterator<Item = (&'a LanguageName, T)>,
+    new: impl IntoIterator<Item = (&'a LanguageName, T)>,
+    result: &mut Vec<Change>,
+) {
+    let old: BTreeMap<_, _> = old.into_iter().map(|(l, b)| (l, b.to_string())).collect();
+    let new: BTreeMap<_, _> = new.into_iter().map(|(l, b)| (l, b.to_string())).collect();
+    let languages: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+    for language in languages {
+        let (old, new) = (old.get(language), new.get(language));
+        if old != new {
+            result.push(Change::BindingChanged {
+                element: element.to_owned(),
+                language: (*language).clone(),
+                old: old.cloned(),
+                new: new.cloned(),
+            });
+        }
+    }
+}
+
+fn compare_errors(
+    component_path: &str,
+    old: &ErrorDescription,
+    new: &ErrorDescription,
+    result: &mut Vec<Change>,
+) {
+    let error = format!("{component_path}/{}", new.name);
+
+    compare_bindings(
+        &error,
+        old.bindings.iter().map(|(l, b)| (l, &b.name)),
+        new.bindings.iter().map(|(l, b)| (l, &b.name)),
+        result,
+    );
+
+    for old_field in &old.fields {
+        match new.fields.iter().find(|f| f.name == old_field.name) {
+            None => result.push(Change::FieldRemoved {
+                error: error.clone(),
+                field: old_field.name.clone(),
+            }),
+            Some(new_field) => {
+                if new_field.r#type != old_field.r#type {
+                    result.push(Change::FieldTypeChanged {
+                        error: error.clone(),
+                        field: old_field.name.clone(),
+                        old: old_field.r#type.clone(),
+                        new: new_field.r#type.clone(),
+                    })
+                }
+                if new_field.optional != old_field.optional {
+                    result.push(Change::FieldOptionalityChanged {
+                        error: error.clone(),
+                        field: old_field.name.clone(),
+                        optional: new_field.optional,
+                    })
+                }
+            }
+        }
+    }
+    for new_field in &new.fields {
+        if !old.fields.iter().any(|f| f.name == new_field.name) {
+            result.push(Change::FieldAdded {
+                error: error.clone(),
+                field: new_field.name.clone(),
+                optional: new_field.may_be_absent(),
+            });
+        }
+    }
+
+    match (&old.lifecycle, &new.lifecycle) {
+        (Lifecycle::Retired, Lifecycle::Retired) => {}
+        (Lifecycle::Retired, _) => result.push(Change::ErrorRestored {
+            error: error.clone(),
+            code: new.code,
+        }),
+        (_, Lifecycle::Retired) => result.push(Change::ErrorRetired {
+            error: error.clone(),
+            code: new.code,
+        }),
+        (Lifecycle::Active, Lifecycle::Deprecated { .. }) => result.push(Change::ErrorDeprecated {
+            error: error.clone(),
+        }),
+        _ => {}
+    }
+
+    if old.message.raw != new.message.raw {
+        result.push(Change::MessageChanged {
+            error,
+            old: old.message.raw.clone(),
+            new: new.message.raw.clone(),
+        });
+    }
+}
+
+/// Errors are identified by their codes, as the codes are what the serialized
+/// errors carry.
+fn compare_components(
+    domain_name: &str,
+    old: &ComponentDescription,
+    new: &ComponentDescription,
+    result: &mut Vec<Change>,
+) {
+    let path = format!("{domain_name}/{}", new.meta.name);
+    if old.meta.code != new.meta.code {
+        result.push(Change::ComponentCodeChanged {
+            component: path.clone(),
+            old: old.meta.code,
+            new: new.meta.code,
+        });
+    }
+    if old.meta.identifier != new.meta.identifier {
+        result.push(Change::IdentifierChanged {
+            element: path.clone(),
+            old: old.meta.identifier.clone(),
+            new: new.meta.identifier.clone(),
+        });
+    }
+    compare_bindings(&path, &old.meta.bindings, &new.meta.bindings, result);
+
+    for old_error in &old.errors {
+        let same_code = new.errors.iter().find(|e| e.code == old_error.code);
+        let same_name = new.errors.iter().find(|e| e.name == old_error.name);
+        match (same_code, same_name) {
+            (Some(new_error), _) if new_error.name == old_error.name => {
+                compare_errors(&path, old_error, new_error, result)
+            }
+            (_, Some(new_error)) => {
+                result.push(Change::ErrorCodeChanged {
+                    error: format!("{path}/{}", old_error.name),
+                    old: old_error.code,
+                    new: new_error.code,
+                });
+                compare_errors(&path, old_error, new_error, result)
+            }
+            (Some(new_error), None) => result.push(Change::ErrorCodeReused {
+                component: path.clone(),
+                code: old_error.code,
+                old_error: old_error.name.clone(),
+                new_error: new_error.name.clone(),
+            }),
+            (None, None) => result.push(Change::ErrorRemoved {
+                error: format!("{path}/{}", old_error.name),
+                code: old_error.code,
+            }),
+        }
+    }
+
+    for new_error in &new.errors {
+        let known_code = old.errors.iter().any(|e| e.code == new_error.code);
+        let known_name = old.errors.iter().any(|e| e.name == new_error.name);
+        if !known_code && !known_name {
+            result.push(Change::ErrorAdded {
+                error: format!("{path}/{}", new_error.name),
+                code: new_error.code,
+            });
+        }
+    }
+}
+
+fn compare_domains(old: &DomainDescription, new: &DomainDescription, result: &mut Vec<Change>) {
+    let name = &new.meta.name;
+    if old.meta.code != new.meta.code {
+        result.push(Change::DomainCodeChanged {
+            domain: name.clone(),
+            old: old.meta.code,
+            new: new.meta.code,
+        });
+    }
+    if old.meta.identifier != new.meta.identifier {
+        result.push(Change::IdentifierChanged {
+            element: name.clone(),
+            old: old.meta.identifier.clone(),
+            new: new.meta.identifier.clone(),
+        });
+    }
+    compare_bindings(name, &old.meta.bindings, &new.meta.bindings, result);
+
+    for (component_name, old_component) in &old.components {
+        match new.components.get(component_name) {
+            Some(new_component) => compare_components(name, old_component, new_component, result),
+            None => result.push(Change::ComponentRemoved {
+                component: format!("{name}/{component_name}"),
+            }),
+        }
+    }
+    for component_name in new.components.keys() {
+        if !old.components.contains_key(component_name) {
+            result.push(Change::ComponentAdded {
+                component: format!("{name}/{component_name}"),
+            });
+        }
+    }
+}
+
+/// List the differences between the `old` and `new` versions of a model.
+/// Domains and components are matched by their names, errors by their codes.
+pub fn compare(old: &Model, new: &Model) -> CompatibilityReport {
+    let mut changes = Vec::new();
+
+    if old.code_layout != new.code_layout {
+        changes.push(Change::CodeLayoutChanged {
+            old: old.code_layout.to_string(),
+            new: new.code_layout.to_string(),
+        });
+    }
+
+    for (type_name, old_type) in &old.types {
+        let Some(new_type) = new.types.get(type_name) else {
+            changes.push(Change::TypeRemoved {
+                r#type: type_name.clone(),
+            });
+            continue;
+        };
+        let languages: BTreeSet<_> = old_type
+            .bindings
+            .keys()
+            .chain(new_type.bindings.keys())
+            .collect();
+        for language in languages {
+            let binding = |t: &crate::inner::TypeDescription| {
+                t.bindings.get(language).map(|b| match b.path.as_str() {
+                    "" => b.name.clone(),
+                    path => format!("{path}.{}", b.name),
+                })
+            };
+            let (old, new) = (binding(old_type), binding(new_type));
+            if old != new {
+                changes.push(Change::TypeBindingChanged {
+                    r#type: type_name.clone(),
+                    language: language.clone(),
+                    old,
+                    new,
+                });
+            }
+        }
+    }
+
+    for type_name in new.types.keys() {
+        if !old.types.contains_key(type_name) {
+            changes.push(Change::TypeAdded {
+                r#type: type_name.clone(),
+            });
+        }
+    }
+
+    for (domain_name, old_domain) in &old.domains {
+        match new.domains.get(domain_name) {
+            Some(new_domain) => compare_domains(old_domain, new_domain, &mut changes),
+            None => changes.push(Change::DomainRemoved {
+                domain: domain_name.clone(),
+            }),
+        }
+    }
+    for domain_name in new.domains.keys() {
+        if !old.domains.contains_key(domain_name) {
+            changes.push(Change::DomainAdded {
+                domain: domain_name.clone(),
+            });
+        }
+    }
+
+    CompatibilityReport { changes }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inner::FullyQualifiedTargetLanguageType;
+    use crate::inner::Lifecycle;
+    use crate::testing::errors_mut;
+    use crate::testing::model;
+
+    use super::compare;
+
+    #[test]
+    fn identical_models() {
+        let model = model(&[("A", 1, &[("index", "uint")])]);
+        assert!(compare(&model, &model).changes.is_empty());
+    }
+
+    #[test]
+    fn classifies_changes() {
+        let old = model(&[
+            ("A", 1, &[("index", "uint")]),
+            ("B", 2, &[]),
+            ("C", 3, &[]),
+            ("D", 4, &[]),
+        ]);
+        let new = model(&[
+            ("A", 1, &[("index", "string"), ("path", "string")]),
+            ("B", 5, &[]),
+            ("E", 3, &[]),
+            ("F", 6, &[]),
+        ]);
+        let report = compare(&old, &new);
+        assert!(report.is_breaking());
+
+        let changes: Vec<_> = report
+            .changes
+            .iter()
+            .map(|c| (c.to_string(), c.is_breaking()))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("Type of the field `index` of the error `core/sequencer/A` changed from `uint` to `string`.".into(), true),
+                ("Field `path` was added to the error `core/sequencer/A`.".into(), true),
+                ("Code of the error `core/sequencer/B` changed from 2 to 5.".into(), true),
+                ("Code 3 of the component `core/sequencer` belonged to `C` and is now used by `E`.".into(), true),
+                ("Error `core/sequencer/D` with the code 4 was removed.".into(), true),
+                ("Error `core/sequencer/F` with the code 6 was added.".into(), false),
+            ]
+        );
+        assert!(!compare(&model(&[]), &model(&[("F", 6, &[])])).is_breaking());
+
+        let mut optional = model(&[("A", 1, &[("index", "uint"), ("path", "string")])]);
+        for error in errors_mut(&mut optional) {
+            error.fields[1].optional = true;
+        }
+        let report = compare(&model(&[("A", 1, &[("index", "uint")])]), &optional);
+        assert_eq!(
+            report.changes[0].to_string(),
+            "Optional field `path` was added to the error `core/sequencer/A`."
+        );
+        assert!(!report.is_breaking());
+    }
+
+    #[test]
+    fn bindings_for_new_languages_are_compatible() {
+        let old = model(&[]);
+        let mut new = old.clone();
+        new.types.get_mut("uint").unwrap().bindings.insert(
+            "typescript".into(),
+            FullyQualifiedTargetLanguageType::from("number"),
+        );
+        let report = compare(&old, &new);
+        assert_eq!(report.changes.len(), 1);
+        assert!(!report.is_breaking());
+        assert!(compare(&new, &old).is_breaking());
+    }
+
+    #[test]
+    fn restored_errors_and_removed_types() {
+        let mut active = model(&[("A", 1, &[])]);
+        let mut retired = active.clone();
+        for error in errors_mut(&mut retired) {
+            error.lifecycle = Lifecycle::Retired;
+        }
+        let report = compare(&retired, &active);
+        assert_eq!(
+            report.changes[0].to_string(),
+            "Error `core/sequencer/A` with the code 1 was restored after being retired."
+        );
+        assert!(!report.is_breaking());
+
+        active.types.remove("uint");
+        let report = compare(&retired, &active);
+        assert_eq!(report.changes[0].to_string(), "Type `uint` was removed.");
+        assert!(report.is_breaking());
+
+        let mut without_types = model(&[]);
+        without_types.types.remove("uint");
+        let report = compare(&without_types, &model(&[]));
+        assert_eq!(report.changes[0].to_string(), "Type `uint` was added.");
+        assert!(!report.is_breaking());
+    }
+}
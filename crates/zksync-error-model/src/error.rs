@@ -1,5 +1,8 @@
 #![allow(unreachable_patterns)]
 
+use crate::identifier::ErrorIdentifier;
+use crate::inner::{CodeLayout, CodeRange, ComponentCode, DomainCode, ErrorCode};
+
 #[derive(Debug, thiserror::Error)]
 pub enum ModelValidationError {
     #[error("Unknown model type {0}. Ensure the \"types\" object of the error definitions file contains it.")]
@@ -8,4 +11,185 @@ pub enum ModelValidationError {
     UnmappedType(String),
     #[error("The name {0} has no mapping.")]
     UnmappedName(String),
+
+    #[error("Component `{component}` has several errors with the code {code}: {errors:?}.")]
+    DuplicateErrorCode {
+        component: String,
+        code: ErrorCode,
+        errors: Vec<String>,
+    },
+    #[error("Code {code} of the component `{component}` belongs to the retired error `{retired}`, and can not be reused by {errors:?}.")]
+    RetiredErrorCode {
+        component: String,
+        code: ErrorCode,
+        retired: String,
+        errors: Vec<String>,
+    },
+    #[error("Error `{error}` {identifier} uses the code {code}, which is reserved by its component in the range {range}.")]
+    ReservedErrorCode {
+        error: String,
+        identifier: Box<ErrorIdentifier>,
+        code: ErrorCode,
+        range: CodeRange,
+    },
+    #[error("Component `{component}` reserves an empty range of codes {range}.")]
+    InvalidReservedRange { component: String, range: CodeRange },
+    #[error("Error `{error}` {identifier} is deprecated in favor of `{replacement}`, which is not an error that is still generated.")]
+    UnknownReplacement {
+        error: String,
+        identifier: Box<ErrorIdentifier>,
+        replacement: String,
+    },
+    #[error("Component `{component}` has several errors named `{error}`.")]
+    DuplicateErrorName { component: String, error: String },
+    #[error("Errors {errors:?} of the component `{component}` are all bound to the name `{name}` in the language `{language}`.")]
+    DuplicateVariantName {
+        component: String,
+        language: String,
+        name: String,
+        errors: Vec<String>,
+    },
+    #[error("Domains {domains:?} share the code {code}.")]
+    DuplicateDomainCode {
+        code: DomainCode,
+        domains: Vec<String>,
+    },
+    #[error("Components {components:?} of the domain `{domain}` share the code {code}.")]
+    DuplicateComponentCode {
+        domain: String,
+        code: ComponentCode,
+        components: Vec<String>,
+    },
+    #[error("Domains {domains:?} share the identifier encoding `{identifier}`.")]
+    DuplicateDomainIdentifier {
+        identifier: String,
+        domains: Vec<String>,
+    },
+    #[error("Components {components:?} of the domain `{domain}` share the identifier encoding `{identifier}`.")]
+    DuplicateComponentIdentifier {
+        domain: String,
+        identifier: String,
+        components: Vec<String>,
+    },
+    #[error("Field `{field}` of the error `{error}` {identifier} uses the type `{r#type}`, which is not defined. Ensure the \"types\" object of the error definitions file contains it.")]
+    UnknownFieldType {
+        error: String,
+        identifier: Box<ErrorIdentifier>,
+        field: String,
+        r#type: String,
+    },
+    #[error("Field `{field}` of the error `{error}` {identifier} has the invalid type `{r#type}`: {inner}.")]
+    InvalidFieldType {
+        error: String,
+        identifier: Box<ErrorIdentifier>,
+        field: String,
+        r#type: String,
+        inner: Box<TypeError>,
+    },
+    #[error("Default value `{default}` of the field `{field}` of the error `{error}` {identifier} is not a value of the type `{r#type}`, or the values of this type can not be checked.")]
+    InvalidDefault {
+        error: String,
+        identifier: Box<ErrorIdentifier>,
+        field: String,
+        r#type: String,
+        default: String,
+    },
+    #[error("Invalid type `{r#type}`: {inner}.")]
+    InvalidType { r#type: String, inner: TypeError },
+    #[error("Type `{r#type}` is used by the error `{error}` {identifier} but has no binding for the language `{language}`.")]
+    MissingTypeBinding {
+        r#type: String,
+        language: String,
+        error: String,
+        identifier: Box<ErrorIdentifier>,
+    },
+    #[error("Field name `{field}` of the error `{error}` {identifier} is not a valid identifier.")]
+    InvalidFieldName {
+        error: String,
+        identifier: Box<ErrorIdentifier>,
+        field: String,
+    },
+    #[error("Error `{error}` {identifier} has several fields named `{field}`.")]
+    DuplicateFieldName {
+        error: String,
+        identifier: Box<ErrorIdentifier>,
+        field: String,
+    },
+    #[error("Code layout `{0}` is invalid: every level should be at least one unit wide, and the largest code should fit into 32 bits.")]
+    InvalidCodeLayout(CodeLayout),
+    #[error("Code {code} of the domain `{domain}` does not fit into the code layout `{layout}`.")]
+    DomainCodeOutOfRange {
+        domain: String,
+        code: DomainCode,
+        layout: CodeLayout,
+    },
+    #[error(
+        "Code {code} of the component `{component}` does not fit into the code layout `{layout}`."
+    )]
+    ComponentCodeOutOfRange {
+        component: String,
+        code: ComponentCode,
+        layout: CodeLayout,
+    },
+    #[error("Code {code} of the error `{error}` {identifier} does not fit into the code layout `{layout}`.")]
+    ErrorCodeOutOfRange {
+        error: String,
+        identifier: Box<ErrorIdentifier>,
+        code: ErrorCode,
+        layout: CodeLayout,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TemplateError {
+    #[error("unclosed placeholder starting at position {0}")]
+    UnclosedPlaceholder(usize),
+    #[error("unmatched `}}` at position {0}; use `}}}}` for a literal brace")]
+    UnmatchedClosingBrace(usize),
+    #[error("placeholder at position {0} does not name a field")]
+    EmptyPlaceholder(usize),
+    #[error("placeholder `{0}` is not a valid field name")]
+    InvalidPlaceholder(String),
+    #[error("invalid format specification `{spec}` for the field `{field}`")]
+    InvalidFormatSpec { field: String, spec: String },
+    #[error("placeholder `{0}` does not name a field of the error")]
+    UnknownField(String),
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TypeError {
+    #[error("unexpected `{0}` at position {1}")]
+    Unexpected(char, usize),
+    #[error("unexpected end of the type")]
+    UnexpectedEnd,
+    #[error("`{0}` at position {1} is not a valid type name")]
+    InvalidName(String, usize),
+    #[error(
+        "unknown type constructor `{0}`; expected one of `list`, `optional`, `map` or `bytes`"
+    )]
+    UnknownConstructor(String),
+    #[error("type constructor `{constructor}` expects {expected} argument(s), found {found}")]
+    WrongArity {
+        constructor: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("type constructor `{0}` got a size where a type is expected, or the other way around")]
+    WrongArgumentKind(String),
+    #[error("invalid size `{0}`")]
+    InvalidSize(String),
+}
+
+/// All problems found while validating a model.
+#[derive(Debug, thiserror::Error)]
+pub struct ModelValidationErrors(pub Vec<ModelValidationError>);
+
+impl std::fmt::Display for ModelValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("found {} problem(s):", self.0.len()))?;
+        for error in &self.0 {
+            f.write_fmt(format_args!("\n - {error}"))?;
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,152 @@
+//!
+//! Layout of the numeric error code: the domain, component and error codes are
+//! packed into a single `u32`, each level occupying a fixed number of decimal
+//! digits or bits.
+//!
+
+use super::{ComponentCode, DomainCode, ErrorCode};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize)]
+pub enum CodeLayoutUnit {
+    Digits,
+    Bits,
+}
+
+impl CodeLayoutUnit {
+    pub fn radix(&self) -> u64 {
+        match self {
+            CodeLayoutUnit::Digits => 10,
+            CodeLayoutUnit::Bits => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for CodeLayoutUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CodeLayoutUnit::Digits => "digits",
+            CodeLayoutUnit::Bits => "bits",
+        })
+    }
+}
+
+/// Widths of the domain, component and error codes, from the most significant
+/// to the least significant part of the numeric code.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize)]
+pub struct CodeLayout {
+    pub unit: CodeLayoutUnit,
+    pub domain: u32,
+    pub component: u32,
+    pub error: u32,
+}
+
+impl Default for CodeLayout {
+    /// One digit for the domain, one for the component and three for the error.
+    fn default() -> Self {
+        Self {
+            unit: CodeLayoutUnit::Digits,
+            domain: 1,
+            component: 1,
+            error: 3,
+        }
+    }
+}
+
+impl CodeLayout {
+    fn capacity(&self, width: u32) -> u64 {
+        self.unit.radix().saturating_pow(width)
+    }
+
+    /// Number of distinct domain codes.
+    pub fn domain_capacity(&self) -> u64 {
+        self.capacity(self.domain)
+    }
+    /// Number of distinct component codes in a domain.
+    pub fn component_capacity(&self) -> u64 {
+        self.capacity(self.component)
+    }
+    /// Number of distinct error codes in a component.
+    pub fn error_capacity(&self) -> u64 {
+        self.capacity(self.error)
+    }
+
+    pub fn component_multiplier(&self) -> u64 {
+        self.error_capacity()
+    }
+    pub fn domain_multiplier(&self) -> u64 {
+        self.error_capacity()
+            .saturating_mul(self.component_capacity())
+    }
+
+    /// Every level has a positive width, and the largest code fits into `u32`.
+    pub fn is_valid(&self) -> bool {
+        self.domain > 0
+            && self.component > 0
+            && self.error > 0
+            && self
+                .domain_multiplier()
+                .saturating_mul(self.domain_capacity())
+                <= u64::from(u32::MAX) + 1
+    }
+
+    pub fn encode(&self, domain: DomainCode, component: ComponentCode, error: ErrorCode) -> u32 {
+        (u64::from(domain) * self.domain_multiplier()
+            + u64::from(component) * self.component_multiplier()
+            + u64::from(error)) as u32
+    }
+
+    pub fn decode(&self, raw: u32) -> (DomainCode, ComponentCode, ErrorCode) {
+        let raw = u64::from(raw);
+        let error = raw % self.error_capacity();
+        let component = (raw / self.component_multiplier()) % self.component_capacity();
+        let domain = (raw / self.domain_multiplier()) % self.domain_capacity();
+        (domain as u32, component as u32, error as u32)
+    }
+}
+
+impl std::fmt::Display for CodeLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let CodeLayout {
+            unit,
+            domain,
+            component,
+            error,
+        } = self;
+        f.write_fmt(format_args!(
+            "{domain}/{component}/{error} {unit} for domain/component/error"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CodeLayout, CodeLayoutUnit};
+
+    #[test]
+    fn default_layout_matches_legacy_encoding() {
+        let layout = CodeLayout::default();
+        assert_eq!(layout.encode(2, 1, 42), 21042);
+        assert_eq!(layout.decode(21042), (2, 1, 42));
+    }
+
+    #[test]
+    fn bit_layout() {
+        let layout = CodeLayout {
+            unit: CodeLayoutUnit::Bits,
+            domain: 8,
+            component: 8,
+            error: 16,
+        };
+        assert!(layout.is_valid());
+        let raw = layout.encode(255, 12, 1500);
+        assert_eq!(raw, 0xFF0C_05DC);
+        assert_eq!(layout.decode(raw), (255, 12, 1500));
+
+        assert!(!CodeLayout {
+            error: 17,
+            ..layout
+        }
+        .is_valid());
+        assert!(!CodeLayout { error: 0, ..layout }.is_valid());
+    }
+}
@@ -1,6 +1,15 @@
+pub mod layout;
+pub mod template;
+pub mod type_expression;
+
 use std::{collections::BTreeMap, rc::Rc};
 
 use super::error::ModelValidationError;
+use super::lint::LintLevels;
+pub use layout::CodeLayout;
+pub use layout::CodeLayoutUnit;
+pub use template::MessageTemplate;
+pub use type_expression::TypeExpression;
 
 pub type LanguageName = String;
 pub type TypeName = String;
@@ -24,6 +33,21 @@ pub struct TargetLanguageType {
     pub name: String,
 }
 
+/// Where an element of the model is defined.
+#[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize)]
+pub struct Origin {
+    /// File, URL or file of a cargo package the element was read from.
+    pub source: String,
+    /// JSON pointer to the element in this file, e.g. `/domains/0/components/1`.
+    pub pointer: String,
+}
+
+impl std::fmt::Display for Origin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}", self.source, self.pointer)
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize)]
 pub struct TypeMetadata {
     pub description: String,
@@ -34,12 +58,16 @@ pub struct TypeDescription {
     pub name: TypeName,
     pub meta: TypeMetadata,
     pub bindings: BTreeMap<LanguageName, FullyQualifiedTargetLanguageType>,
+    /// Missing for the types generated by the loader.
+    pub origin: Option<Origin>,
 }
 
 #[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize)]
 pub struct Model {
     pub types: BTreeMap<TypeName, TypeDescription>,
     pub domains: BTreeMap<DomainName, DomainDescription>,
+    pub code_layout: CodeLayout,
+    pub lints: LintLevels,
 }
 
 impl Model {
@@ -47,7 +75,12 @@ impl Model {
         types: BTreeMap<TypeName, TypeDescription>,
         domains: BTreeMap<DomainName, DomainDescription>,
     ) -> Self {
-        Self { types, domains }
+        Self {
+            types,
+            domains,
+            code_layout: CodeLayout::default(),
+            lints: LintLevels::default(),
+        }
     }
 
     pub fn components(&self) -> impl Iterator<Item = &ComponentDescription> {
@@ -69,6 +102,7 @@ pub struct DomainMetadata {
     pub bindings: BTreeMap<LanguageName, String>,
     pub identifier: String,
     pub description: String,
+    pub origin: Option<Origin>,
 }
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize)]
 pub struct DomainDescription {
@@ -84,6 +118,9 @@ pub struct ComponentMetadata {
     pub bindings: BTreeMap<LanguageName, String>,
     pub identifier: String,
     pub description: String,
+    /// Codes that no error of this component may use.
+    pub reserved: Vec<CodeRange>,
+    pub origin: Option<Origin>,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize)]
@@ -98,10 +135,71 @@ pub struct ErrorDescription {
     pub component: Rc<ComponentMetadata>,
     pub name: ErrorName,
     pub code: ErrorCode,
-    pub message: ErrorMessageTemplate,
+    pub message: MessageTemplate,
     pub fields: Vec<FieldDescription>,
     pub documentation: Option<ErrorDocumentation>,
     pub bindings: BTreeMap<LanguageName, TargetLanguageType>,
+    pub lifecycle: Lifecycle,
+    /// Missing for the errors generated by the loader.
+    pub origin: Option<Origin>,
+}
+
+/// Stage in the life of an error.
+#[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum Lifecycle {
+    #[default]
+    Active,
+    /// The error is still generated, but should not be used in new code.
+    Deprecated {
+        message: String,
+        /// Identifier of the error to use instead, e.g. `[core-seq-2]`.
+        replacement: Option<String>,
+    },
+    /// The error is not generated anymore; it is only kept so that its code
+    /// is never reused.
+    Retired,
+}
+
+impl std::fmt::Display for Lifecycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lifecycle::Active => f.write_str("active"),
+            Lifecycle::Deprecated {
+                message,
+                replacement,
+            } => {
+                f.write_str("deprecated")?;
+                if !message.is_empty() {
+                    write!(f, ": {message}")?;
+                }
+                if let Some(replacement) = replacement {
+                    write!(f, " (use {replacement} instead)")?;
+                }
+                Ok(())
+            }
+            Lifecycle::Retired => f.write_str("retired"),
+        }
+    }
+}
+
+/// Inclusive range of error codes.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, serde::Serialize)]
+pub struct CodeRange {
+    pub start: ErrorCode,
+    pub end: ErrorCode,
+}
+
+impl CodeRange {
+    pub fn contains(&self, code: ErrorCode) -> bool {
+        (self.start..=self.end).contains(&code)
+    }
+}
+
+impl std::fmt::Display for CodeRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..={}", self.start, self.end)
+    }
 }
 
 impl From<TargetLanguageType> for FullyQualifiedTargetLanguageType {
@@ -123,6 +221,18 @@ impl From<&str> for FullyQualifiedTargetLanguageType {
 pub struct FieldDescription {
     pub name: FieldName,
     pub r#type: TypeName,
+    pub description: String,
+    /// Optional fields may be absent from the errors.
+    pub optional: bool,
+    /// Value of the field in the serialized errors lacking it.
+    pub default: Option<serde_json::Value>,
+}
+
+impl FieldDescription {
+    /// Serialized errors may lack the field.
+    pub fn may_be_absent(&self) -> bool {
+        self.optional || self.default.is_some()
+    }
 }
 
 #[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize)]
@@ -163,9 +273,31 @@ impl Model {
             .ok_or(ModelValidationError::UnmappedType(name.to_string()))?;
         Ok(mapped_type)
     }
+
+    /// Parse a field type and resolve the built-in types it refers to.
+    pub fn resolve_type(&self, r#type: &str) -> Result<TypeExpression, ModelValidationError> {
+        TypeExpression::parse(r#type)
+            .map(|expression| expression.resolve(&self.types))
+            .map_err(|inner| ModelValidationError::InvalidType {
+                r#type: r#type.to_owned(),
+                inner,
+            })
+    }
+}
+
+impl ErrorDescription {
+    pub fn is_retired(&self) -> bool {
+        self.lifecycle == Lifecycle::Retired
+    }
 }
 
 impl ComponentDescription {
+    /// Errors present in the generated code, that is, all errors except the
+    /// retired ones.
+    pub fn emitted_errors(&self) -> impl Iterator<Item = &ErrorDescription> {
+        self.errors.iter().filter(|error| !error.is_retired())
+    }
+
     pub fn mergeable_with(&self, other: &Self) -> bool {
         self.meta.name == other.meta.name && self.meta.code == other.meta.code
     }
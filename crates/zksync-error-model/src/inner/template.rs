@@ -0,0 +1,292 @@
+//!
+//! Error messages are templates where `{field}` or `{field:spec}` placeholders
+//! refer to the fields of the error, and `{{`, `}}` stand for literal braces.
+//! The format specifications follow the syntax of Rust's `format!`.
+//!
+
+use crate::error::TemplateError;
+use crate::validator::is_valid_identifier;
+
+use super::FieldName;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '<' => Some(Alignment::Left),
+            '^' => Some(Alignment::Center),
+            '>' => Some(Alignment::Right),
+            _ => None,
+        }
+    }
+    pub fn as_char(&self) -> char {
+        match self {
+            Alignment::Left => '<',
+            Alignment::Center => '^',
+            Alignment::Right => '>',
+        }
+    }
+}
+
+/// Format specification of a placeholder, e.g. `>4` in `{index:>4}`.
+#[derive(Debug, Default, Clone, Eq, PartialEq, serde::Serialize)]
+pub struct FormatSpec {
+    pub fill: Option<char>,
+    pub align: Option<Alignment>,
+    pub sign: Option<char>,
+    pub alternate: bool,
+    pub zero: bool,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    /// Formatting trait: empty for `Display`, `?` for `Debug`, `x` for
+    /// `LowerHex` and so on.
+    pub kind: String,
+}
+
+impl FormatSpec {
+    const KINDS: [&str; 10] = ["", "?", "x", "X", "o", "b", "e", "E", "x?", "X?"];
+
+    pub fn parse(spec: &str) -> Option<FormatSpec> {
+        let chars: Vec<char> = spec.chars().collect();
+        let mut result = FormatSpec::default();
+        let mut i = 0;
+
+        if let Some(align) = chars.get(1).copied().and_then(Alignment::from_char) {
+            result.fill = Some(chars[0]);
+            result.align = Some(align);
+            i = 2;
+        } else if let Some(align) = chars.first().copied().and_then(Alignment::from_char) {
+            result.align = Some(align);
+            i = 1;
+        }
+        if let Some(sign @ ('+' | '-')) = chars.get(i) {
+            result.sign = Some(*sign);
+            i += 1;
+        }
+        if chars.get(i) == Some(&'#') {
+            result.alternate = true;
+            i += 1;
+        }
+        if chars.get(i) == Some(&'0') {
+            result.zero = true;
+            i += 1;
+        }
+
+        let take_number = |i: &mut usize| -> Option<usize> {
+            let start = *i;
+            while chars.get(*i).is_some_and(char::is_ascii_digit) {
+                *i += 1;
+            }
+            String::from_iter(&chars[start..*i]).parse().ok()
+        };
+
+        result.width = take_number(&mut i);
+        if chars.get(i) == Some(&'.') {
+            i += 1;
+            result.precision = Some(take_number(&mut i)?);
+        }
+
+        let kind = String::from_iter(&chars[i..]);
+        if !Self::KINDS.contains(&kind.as_str()) {
+            return None;
+        }
+        result.kind = kind;
+        Some(result)
+    }
+}
+
+impl std::fmt::Display for FormatSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(fill) = self.fill {
+            f.write_fmt(format_args!("{fill}"))?;
+        }
+        if let Some(align) = self.align {
+            f.write_fmt(format_args!("{}", align.as_char()))?;
+        }
+        if let Some(sign) = self.sign {
+            f.write_fmt(format_args!("{sign}"))?;
+        }
+        if self.alternate {
+            f.write_str("#")?;
+        }
+        if self.zero {
+            f.write_str("0")?;
+        }
+        if let Some(width) = self.width {
+            f.write_fmt(format_args!("{width}"))?;
+        }
+        if let Some(precision) = self.precision {
+            f.write_fmt(format_args!(".{precision}"))?;
+        }
+        f.write_str(&self.kind)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize)]
+pub struct Placeholder {
+    pub field: FieldName,
+    pub spec: Option<FormatSpec>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize)]
+pub enum MessageSegment {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, serde::Serialize)]
+pub struct MessageTemplate {
+    pub raw: String,
+    pub segments: Vec<MessageSegment>,
+}
+
+impl MessageTemplate {
+    pub fn parse(raw: &str) -> Result<MessageTemplate, TemplateError> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = raw.char_indices().peekable();
+
+        while let Some((position, c)) = chars.next() {
+            match c {
+                '{' if chars.next_if(|(_, c)| *c == '{').is_some() => literal.push('{'),
+                '}' if chars.next_if(|(_, c)| *c == '}').is_some() => literal.push('}'),
+                '}' => return Err(TemplateError::UnmatchedClosingBrace(position)),
+                '{' => {
+                    let mut content = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, '{')) | None => {
+                                return Err(TemplateError::UnclosedPlaceholder(position))
+                            }
+                            Some((_, c)) => content.push(c),
+                        }
+                    }
+
+                    let (field, spec) = match content.split_once(':') {
+                        Some((field, spec)) => (field, Some(spec)),
+                        None => (content.as_str(), None),
+                    };
+                    if field.is_empty() {
+                        return Err(TemplateError::EmptyPlaceholder(position));
+                    }
+                    if !is_valid_identifier(field) {
+                        return Err(TemplateError::InvalidPlaceholder(field.to_owned()));
+                    }
+                    let spec = spec
+                        .map(|spec| {
+                            FormatSpec::parse(spec).ok_or_else(|| {
+                                TemplateError::InvalidFormatSpec {
+                                    field: field.to_owned(),
+                                    spec: spec.to_owned(),
+                                }
+                            })
+                        })
+                        .transpose()?;
+
+                    if !literal.is_empty() {
+                        segments.push(MessageSegment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(MessageSegment::Placeholder(Placeholder {
+                        field: field.to_owned(),
+                        spec,
+                    }));
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(MessageSegment::Literal(literal));
+        }
+
+        Ok(MessageTemplate {
+            raw: raw.to_owned(),
+            segments,
+        })
+    }
+
+    pub fn placeholders(&self) -> impl Iterator<Item = &Placeholder> {
+        self.segments.iter().filter_map(|segment| match segment {
+            MessageSegment::Placeholder(placeholder) => Some(placeholder),
+            MessageSegment::Literal(_) => None,
+        })
+    }
+
+    pub fn mentions(&self, field: &str) -> bool {
+        self.placeholders().any(|p| p.field == field)
+    }
+}
+
+impl std::fmt::Display for MessageTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::TemplateError;
+
+    use super::{Alignment, FormatSpec, MessageSegment, MessageTemplate, Placeholder};
+
+    #[test]
+    fn parses_segments() {
+        let template = MessageTemplate::parse("{{literal}} {path} at {index:>4}.").unwrap();
+        assert_eq!(
+            template.segments,
+            vec![
+                MessageSegment::Literal("{literal} ".into()),
+                MessageSegment::Placeholder(Placeholder {
+                    field: "path".into(),
+                    spec: None
+                }),
+                MessageSegment::Literal(" at ".into()),
+                MessageSegment::Placeholder(Placeholder {
+                    field: "index".into(),
+                    spec: Some(FormatSpec {
+                        align: Some(Alignment::Right),
+                        width: Some(4),
+                        ..Default::default()
+                    })
+                }),
+                MessageSegment::Literal(".".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn format_spec_roundtrip() {
+        for spec in ["", "?", "*^+#010.3x?", "<8", "08.2e"] {
+            assert_eq!(FormatSpec::parse(spec).unwrap().to_string(), spec);
+        }
+        for spec in ["q", ".x", "1$"] {
+            assert!(FormatSpec::parse(spec).is_none());
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        assert!(matches!(
+            MessageTemplate::parse("a {b"),
+            Err(TemplateError::UnclosedPlaceholder(2))
+        ));
+        assert!(matches!(
+            MessageTemplate::parse("a } b"),
+            Err(TemplateError::UnmatchedClosingBrace(2))
+        ));
+        assert!(matches!(
+            MessageTemplate::parse("a {} b"),
+            Err(TemplateError::EmptyPlaceholder(2))
+        ));
+        assert!(matches!(
+            MessageTemplate::parse("{0}"),
+            Err(TemplateError::InvalidPlaceholder(_))
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+//!
+//! Types of the error fields: the types declared in the model, and the
+//! built-in type constructors applied to them, e.g. `list<uint>` or
+//! `map<string, bytes>`. Every backend translates the constructors into the
+//! native types of its language.
+//!
+
+use std::collections::BTreeMap;
+
+use crate::error::TypeError;
+use crate::validator::is_valid_identifier;
+
+use super::TypeDescription;
+use super::TypeName;
+
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize)]
+pub enum TypeExpression {
+    /// Type declared in the model, bound to a type in every language.
+    Named(TypeName),
+    /// `list<T>`: sequence of values.
+    List(Box<TypeExpression>),
+    /// `optional<T>`: value that may be absent.
+    Optional(Box<TypeExpression>),
+    /// `map<K, V>`: mapping from keys to values, ordered by the keys.
+    Map(Box<TypeExpression>, Box<TypeExpression>),
+    /// `bytes<N>`: exactly `N` bytes, hex-encoded.
+    ByteArray(usize),
+    /// `bytes`: any number of bytes, hex-encoded.
+    Bytes,
+}
+
+enum Argument {
+    Type(TypeExpression),
+    Size(usize),
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.input[self.position..].chars().next()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TypeError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.position += c.len_utf8();
+                Ok(())
+            }
+            Some(c) => Err(TypeError::Unexpected(c, self.position)),
+            None => Err(TypeError::UnexpectedEnd),
+        }
+    }
+
+    fn word(&mut self) -> &str {
+        self.skip_whitespace();
+        let start = self.position;
+        let rest = &self.input[start..];
+        let length = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.position += length;
+        &self.input[start..self.position]
+    }
+
+    fn argument(&mut self) -> Result<Argument, TypeError> {
+        if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            let word = self.word().to_owned();
+            let size = word.parse().map_err(|_| TypeError::InvalidSize(word))?;
+            Ok(Argument::Size(size))
+        } else {
+            self.type_expression().map(Argument::Type)
+        }
+    }
+
+    fn type_expression(&mut self) -> Result<TypeExpression, TypeError> {
+        let position = self.position;
+        let name = self.word().to_owned();
+        if name.is_empty() {
+            return Err(match self.peek() {
+                Some(c) => TypeError::Unexpected(c, self.position),
+                None => TypeError::UnexpectedEnd,
+            });
+        }
+        if !is_valid_identifier(&name) {
+            return Err(TypeError::InvalidName(name, position));
+        }
+        if self.peek() != Some('<') {
+            return Ok(TypeExpression::Named(name));
+        }
+        self.expect('<')?;
+        let mut arguments = vec![self.argument()?];
+        while self.peek() == Some(',') {
+            self.expect(',')?;
+            arguments.push(self.argument()?);
+        }
+        self.expect('>')?;
+        TypeExpression::construct(name, arguments)
+    }
+}
+
+impl TypeExpression {
+    /// Parse a type. Bare names stand for the types declared in the model,
+    /// including `bytes`; see [`TypeExpression::resolve`].
+    pub fn parse(input: &str) -> Result<Self, TypeError> {
+        let mut parser = Parser { input, position: 0 };
+        let result = parser.type_expression()?;
+        match parser.peek() {
+            None => Ok(result),
+            Some(c) => Err(TypeError::Unexpected(c, parser.position)),
+        }
+    }
+
+    fn construct(constructor: String, arguments: Vec<Argument>) -> Result<Self, TypeError> {
+        let arity = |expected: usize| {
+            if arguments.len() == expected {
+                Ok(())
+            } else {
+                Err(TypeError::WrongArity {
+                    constructor: constructor.clone(),
+                    expected,
+                    found: arguments.len(),
+                })
+            }
+        };
+        let mut types = Vec::new();
+        let mut sizes = Vec::new();
+        for argument in &arguments {
+            match argument {
+                Argument::Type(t) => types.push(t.clone()),
+                Argument::Size(s) => sizes.push(*s),
+            }
+        }
+        let result = match constructor.as_str() {
+            "list" => {
+                arity(1)?;
+                types.pop().map(|t| TypeExpression::List(Box::new(t)))
+            }
+            "optional" => {
+                arity(1)?;
+                types.pop().map(|t| TypeExpression::Optional(Box::new(t)))
+            }
+            "map" => {
+                arity(2)?;
+                let value = types.pop();
+                let key = types.pop();
+                key.zip(value)
+                    .map(|(k, v)| TypeExpression::Map(Box::new(k), Box::new(v)))
+            }
+            "bytes" => {
+                arity(1)?;
+                sizes.pop().map(TypeExpression::ByteArray)
+            }
+            _ => return Err(TypeError::UnknownConstructor(constructor)),
+        };
+        result.ok_or(TypeError::WrongArgumentKind(constructor))
+    }
+
+    /// Take the bare name `bytes` for the built-in type, unless the model
+    /// declares a type with this name.
+    pub fn resolve(self, types: &BTreeMap<TypeName, TypeDescription>) -> Self {
+        match self {
+            TypeExpression::Named(name) if name == "bytes" && !types.contains_key(&name) => {
+                TypeExpression::Bytes
+            }
+            TypeExpression::List(t) => TypeExpression::List(Box::new(t.resolve(types))),
+            TypeExpression::Optional(t) => TypeExpression::Optional(Box::new(t.resolve(types))),
+            TypeExpression::Map(k, v) => {
+                TypeExpression::Map(Box::new(k.resolve(types)), Box::new(v.resolve(types)))
+            }
+            other => other,
+        }
+    }
+
+    /// Names of the declared types this type is built from.
+    pub fn named_types(&self) -> Vec<&TypeName> {
+        match self {
+            TypeExpression::Named(name) => vec![name],
+            TypeExpression::List(t) | TypeExpression::Optional(t) => t.named_types(),
+            TypeExpression::Map(k, v) => {
+                let mut result = k.named_types();
+                result.extend(v.named_types());
+                result
+            }
+            TypeExpression::ByteArray(_) | TypeExpression::Bytes => vec![],
+        }
+    }
+}
+
+impl std::fmt::Display for TypeExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeExpression::Named(name) => f.write_str(name),
+            TypeExpression::List(t) => write!(f, "list<{t}>"),
+            TypeExpression::Optional(t) => write!(f, "optional<{t}>"),
+            TypeExpression::Map(k, v) => write!(f, "map<{k}, {v}>"),
+            TypeExpression::ByteArray(size) => write!(f, "bytes<{size}>"),
+            TypeExpression::Bytes => f.write_str("bytes"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::error::TypeError;
+
+    use super::TypeExpression;
+
+    #[test]
+    fn parses_constructors() {
+        let parsed = TypeExpression::parse(" map<string,list< optional<bytes<32>> >>").unwrap();
+        assert_eq!(parsed.to_string(), "map<string, list<optional<bytes<32>>>>");
+        assert_eq!(parsed.named_types(), ["string"]);
+        assert_eq!(
+            TypeExpression::parse("list<bytes>")
+                .unwrap()
+                .resolve(&BTreeMap::new()),
+            TypeExpression::List(Box::new(TypeExpression::Bytes))
+        );
+
+        assert!(matches!(
+            TypeExpression::parse("map<string>"),
+            Err(TypeError::WrongArity {
+                expected: 2,
+                found: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            TypeExpression::parse("set<uint>"),
+            Err(TypeError::UnknownConstructor(name)) if name == "set"
+        ));
+        assert!(matches!(
+            TypeExpression::parse("list<uint"),
+            Err(TypeError::UnexpectedEnd)
+        ));
+        assert!(matches!(
+            TypeExpression::parse("bytes<uint>"),
+            Err(TypeError::WrongArgumentKind(_))
+        ));
+    }
+}
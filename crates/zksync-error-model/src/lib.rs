@@ -1,6 +1,11 @@
+pub mod compatibility;
 pub mod error;
 pub mod identifier;
 pub mod inner;
+pub mod lint;
 pub mod merger;
 pub mod unpacked;
 pub mod validator;
+
+#[cfg(test)]
+pub(crate) mod testing;
@@ -0,0 +1,358 @@
+//!
+//! Checks of the quality of error descriptions.
+//!
+//! Unlike the problems found by [`crate::validator`], lint findings do not
+//! prevent the code generation, unless the level of their rule is raised to
+//! [`Level::Deny`].
+//!
+
+use std::collections::BTreeMap;
+
+use crate::inner::ComponentMetadata;
+use crate::inner::DomainMetadata;
+use crate::inner::ErrorDescription;
+use crate::inner::Model;
+use crate::inner::Origin;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// An error has no documentation.
+    MissingDoc,
+    /// The summary of an error is blank.
+    EmptySummary,
+    /// A likely cause of an error suggests no fixes.
+    CauseWithoutFixes,
+    /// The message of an error does not end with a punctuation mark.
+    UnpunctuatedMessage,
+    /// A component has no description.
+    UndescribedComponent,
+    /// A domain or a component has an empty identifier encoding, which leads
+    /// to identifiers like `[--1]`.
+    EmptyIdentifierEncoding,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 6] = [
+        Rule::MissingDoc,
+        Rule::EmptySummary,
+        Rule::CauseWithoutFixes,
+        Rule::UnpunctuatedMessage,
+        Rule::UndescribedComponent,
+        Rule::EmptyIdentifierEncoding,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::MissingDoc => "missing-doc",
+            Rule::EmptySummary => "empty-summary",
+            Rule::CauseWithoutFixes => "cause-without-fixes",
+            Rule::UnpunctuatedMessage => "unpunctuated-message",
+            Rule::UndescribedComponent => "undescribed-component",
+            Rule::EmptyIdentifierEncoding => "empty-identifier-encoding",
+        }
+    }
+
+    /// Rules about the completeness of the documentation are only enabled on
+    /// request, since most definitions are written without it at first.
+    pub fn default_level(&self) -> Level {
+        match self {
+            Rule::MissingDoc | Rule::UndescribedComponent => Level::Allow,
+            _ => Level::Warn,
+        }
+    }
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rule::ALL
+            .into_iter()
+            .find(|rule| rule.name() == s)
+            .ok_or_else(|| format!("Unknown lint rule `{s}`"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Level::Allow => "allow",
+            Level::Warn => "warn",
+            Level::Deny => "deny",
+        })
+    }
+}
+
+impl std::str::FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "allow" => Ok(Level::Allow),
+            "warn" => Ok(Level::Warn),
+            "deny" => Ok(Level::Deny),
+            _ => Err(format!("Unknown lint level `{s}`")),
+        }
+    }
+}
+
+/// Levels of the rules; rules not mentioned here have their default level.
+#[derive(Debug, Clone, Default, Eq, PartialEq, serde::Serialize)]
+pub struct LintLevels(pub BTreeMap<Rule, Level>);
+
+impl LintLevels {
+    pub fn level(&self, rule: Rule) -> Level {
+        self.0
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+
+    /// Override the levels of the rules mentioned in `other`.
+    pub fn override_with(&mut self, other: &LintLevels) {
+        self.0.extend(other.0.iter().map(|(r, l)| (*r, *l)));
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize)]
+pub struct Finding {
+    pub rule: Rule,
+    pub level: Level,
+    /// The domain, component or error the finding is about.
+    pub element: String,
+    pub message: String,
+    pub origin: Option<Origin>,
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} [{}]: {}", self.element, self.rule, self.message)?;
+        if let Some(origin) = &self.origin {
+            write!(f, " (defined in `{origin}`)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Findings of the rules that are not allowed.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct LintReport {
+    pub findings: Vec<Finding>,
+}
+
+impl LintReport {
+    pub fn is_denied(&self) -> bool {
+        self.findings.iter().any(|f| f.level == Level::Deny)
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "denied": self.is_denied(),
+            "findings": self.findings,
+        })
+    }
+}
+
+impl std::fmt::Display for LintReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.findings.is_empty() {
+            return f.write_str("No lint findings.");
+        }
+        let denied = self
+            .findings
+            .iter()
+            .filter(|f| f.level == Level::Deny)
+            .count();
+        write!(
+            f,
+            "{denied} denied and {} other lint finding(s):",
+            self.findings.len() - denied
+        )?;
+        for finding in &self.findings {
+            write!(f, "\n - {}: {finding}", finding.level)?;
+        }
+        Ok(())
+    }
+}
+
+struct Linter<'a> {
+    levels: &'a LintLevels,
+    findings: Vec<Finding>,
+}
+
+impl Linter<'_> {
+    fn report(
+        &mut self,
+        rule: Rule,
+        element: impl FnOnce() -> String,
+        message: &str,
+        origin: &Option<Origin>,
+    ) {
+        let level = self.levels.level(rule);
+        if level != Level::Allow {
+            self.findings.push(Finding {
+                rule,
+                level,
+                element: element(),
+                message: message.to_owned(),
+                origin: origin.clone(),
+            });
+        }
+    }
+
+    fn check_domain(&mut self, domain: &DomainMetadata) {
+        if domain.identifier.is_empty() {
+            self.report(
+                Rule::EmptyIdentifierEncoding,
+                || format!("Domain `{}`", domain.name),
+                "identifier encoding is empty.",
+                &domain.origin,
+            );
+        }
+    }
+
+    fn check_component(&mut self, component: &ComponentMetadata) {
+        let element = || format!("Component `{}`", component.name);
+        if component.identifier.is_empty() {
+            self.report(
+                Rule::EmptyIdentifierEncoding,
+                element,
+                "identifier encoding is empty.",
+                &component.origin,
+            );
+        }
+        if component.description.trim().is_empty() {
+            self.report(
+                Rule::UndescribedComponent,
+                element,
+                "description is missing.",
+                &component.origin,
+            );
+        }
+    }
+
+    fn check_error(&mut self, error: &ErrorDescription) {
+        let element = || format!("Error `{}` {}", error.name, error.get_identifier());
+        if !error.message.raw.trim_end().ends_with(['.', '!', '?']) {
+            self.report(
+                Rule::UnpunctuatedMessage,
+                element,
+                "message does not end with a punctuation mark.",
+                &error.origin,
+            );
+        }
+        let Some(documentation) = &error.documentation else {
+            self.report(
+                Rule::MissingDoc,
+                element,
+                "documentation is missing.",
+                &error.origin,
+            );
+            return;
+        };
+        if documentation
+            .summary
+            .as_ref()
+            .is_some_and(|summary| summary.trim().is_empty())
+        {
+            self.report(
+                Rule::EmptySummary,
+                element,
+                "summary is empty.",
+                &error.origin,
+            );
+        }
+        for cause in &documentation.likely_causes {
+            if cause.fixes.is_empty() {
+                self.report(
+                    Rule::CauseWithoutFixes,
+                    element,
+                    &format!("likely cause `{}` suggests no fixes.", cause.cause),
+                    &error.origin,
+                );
+            }
+        }
+    }
+}
+
+/// Check the model against the rules that are not allowed in `levels`.
+pub fn lint(model: &Model, levels: &LintLevels) -> LintReport {
+    let mut linter = Linter {
+        levels,
+        findings: Vec::new(),
+    };
+    for domain in model.domains.values() {
+        linter.check_domain(&domain.meta);
+    }
+    for component in model.components() {
+        linter.check_component(&component.meta);
+    }
+    // Errors generated by the loader have no origin, and can not be fixed.
+    for error in model.errors().filter(|e| e.origin.is_some()) {
+        linter.check_error(error);
+    }
+    LintReport {
+        findings: linter.findings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inner::MessageTemplate;
+    use crate::inner::Origin;
+    use crate::testing::errors_mut;
+    use crate::testing::model;
+
+    use super::lint;
+    use super::Level;
+    use super::LintLevels;
+    use super::Rule;
+
+    #[test]
+    fn levels_select_the_reported_findings() {
+        let mut model = model(&[("A", 1, &[]), ("B", 2, &[])]);
+        for error in errors_mut(&mut model) {
+            let message = if error.name == "A" {
+                "Failed."
+            } else {
+                "Failed"
+            };
+            error.message = MessageTemplate::parse(message).unwrap();
+            error.origin = Some(Origin::default());
+        }
+
+        let report = lint(&model, &LintLevels::default());
+        assert!(!report.is_denied());
+        assert!(matches!(
+            report.findings.as_slice(),
+            [finding] if finding.rule == Rule::UnpunctuatedMessage && finding.element.starts_with("Error `B`")
+        ));
+
+        let levels = LintLevels(
+            [
+                (Rule::UnpunctuatedMessage, Level::Allow),
+                (Rule::MissingDoc, Level::Deny),
+            ]
+            .into(),
+        );
+        let report = lint(&model, &levels);
+        assert!(report.is_denied());
+        assert_eq!(report.findings.len(), 2);
+        assert!(report.findings.iter().all(|f| f.rule == Rule::MissingDoc));
+    }
+}
@@ -10,6 +10,17 @@ pub enum MergeError {
     StringsDiffer(String, String),
     #[error("Conflicting descriptions for component `{0}`")]
     ConflictingComponentDefinitions(String),
+    #[error(
+        "Conflicting code layouts `{0}` and `{1}`; the layout should be set once, in the root file"
+    )]
+    ConflictingCodeLayouts(String, String),
     #[error("Conflicting error descriptions for errors `{0}` and `{1}`")]
     ConflictingErrorDescriptions(String, String),
+    #[error("Conflicting {attribute} of {entity}: `{existing}` and `{new}`")]
+    ConflictingAttribute {
+        entity: String,
+        attribute: String,
+        existing: String,
+        new: String,
+    },
 }
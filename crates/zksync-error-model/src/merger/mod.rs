@@ -2,22 +2,70 @@ pub mod error;
 
 use error::MergeError;
 use std::collections::BTreeMap;
+use std::rc::Rc;
 
 use super::inner::{
-    ComponentDescription, DomainDescription, ErrorDescription, ErrorDocumentation, Model,
-    TypeDescription,
+    ComponentDescription, DomainDescription, ErrorDescription, ErrorDocumentation, Lifecycle,
+    LikelyCause, Model, TypeDescription,
 };
 
-fn merge_maps<K, V>(main: &mut BTreeMap<K, V>, other: &BTreeMap<K, V>) -> Result<(), MergeError>
+/// How to treat conflicting attributes of the merged definitions, such as two
+/// different descriptions of the same error. Conflicts in the structure of the
+/// model, such as different fields of the same error, are always errors.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MergeMode {
+    /// Fail on the first conflict.
+    #[default]
+    Strict,
+    /// Keep the attribute of the definition merged into, and report the
+    /// conflict.
+    Lenient,
+}
+
+/// Conflicts met while merging in the given [`MergeMode`].
+#[derive(Debug, Default)]
+pub struct Conflicts {
+    mode: MergeMode,
+    reported: Vec<MergeError>,
+}
+
+impl Conflicts {
+    pub fn new(mode: MergeMode) -> Self {
+        Self {
+            mode,
+            reported: vec![],
+        }
+    }
+
+    /// Fails in strict mode; in lenient mode, records the conflict and lets the
+    /// merge continue.
+    pub fn report(&mut self, conflict: MergeError) -> Result<(), MergeError> {
+        match self.mode {
+            MergeMode::Strict => Err(conflict),
+            MergeMode::Lenient => {
+                self.reported.push(conflict);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn into_reported(self) -> Vec<MergeError> {
+        self.reported
+    }
+}
+
+fn merge_maps<K, V>(
+    main: &mut BTreeMap<K, V>,
+    other: &BTreeMap<K, V>,
+    conflicts: &mut Conflicts,
+) -> Result<(), MergeError>
 where
     K: Eq + PartialEq + Ord + Clone,
     V: Merge + Clone,
 {
     for (key, value) in other {
-        if let Some(existing_value) = main.get(key).as_mut() {
-            let mut new = existing_value.clone();
-            new.merge(value)?;
-            main.insert(key.clone(), new);
+        if let Some(existing_value) = main.get_mut(key) {
+            existing_value.merge_with(value, conflicts)?;
         } else {
             main.insert(key.clone(), value.clone());
         }
@@ -25,102 +73,295 @@ where
     Ok(())
 }
 
+/// Empty attributes are treated as missing and take the value of the other
+/// definition.
+fn merge_attribute(
+    entity: &str,
+    attribute: &str,
+    main: &mut String,
+    other: &str,
+    conflicts: &mut Conflicts,
+) -> Result<(), MergeError> {
+    if main == other || other.is_empty() {
+        Ok(())
+    } else if main.is_empty() {
+        *main = other.to_owned();
+        Ok(())
+    } else {
+        conflicts.report(MergeError::ConflictingAttribute {
+            entity: entity.to_owned(),
+            attribute: attribute.to_owned(),
+            existing: main.clone(),
+            new: other.to_owned(),
+        })
+    }
+}
+
+/// Bindings omitted in a definition default to the name of the bound entity,
+/// so a binding equal to `default` yields to a different one.
+fn merge_bindings<V: Clone>(
+    entity: &str,
+    default: &str,
+    main: &mut BTreeMap<String, V>,
+    other: &BTreeMap<String, V>,
+    name: impl Fn(&V) -> &str,
+    conflicts: &mut Conflicts,
+) -> Result<(), MergeError> {
+    for (language, binding) in other {
+        match main.get_mut(language) {
+            None => {
+                main.insert(language.clone(), binding.clone());
+            }
+            Some(existing) if name(existing) == name(binding) || name(binding) == default => {}
+            Some(existing) if name(existing) == default => *existing = binding.clone(),
+            Some(existing) => conflicts.report(MergeError::ConflictingAttribute {
+                entity: entity.to_owned(),
+                attribute: format!("{language} binding"),
+                existing: name(existing).to_owned(),
+                new: name(binding).to_owned(),
+            })?,
+        }
+    }
+    Ok(())
+}
+
 pub trait Merge {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeError>;
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeError>;
+
+    /// Merge `other` into `self`, failing on any conflict.
+    fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
+        self.merge_with(other, &mut Conflicts::new(MergeMode::Strict))
+    }
+
+    /// Merge `other` into `self`, returning the conflicts tolerated in `mode`.
+    fn merge_in_mode(
+        &mut self,
+        other: &Self,
+        mode: MergeMode,
+    ) -> Result<Vec<MergeError>, MergeError> {
+        let mut conflicts = Conflicts::new(mode);
+        self.merge_with(other, &mut conflicts)?;
+        Ok(conflicts.into_reported())
+    }
 }
 
 impl Merge for String {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
-        if self == other {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeError> {
+        if self == other || other.is_empty() {
             Ok(())
         } else if self.is_empty() {
             *self = other.clone();
             Ok(())
-        } else if other.is_empty() {
+        } else {
+            conflicts.report(MergeError::StringsDiffer(self.clone(), other.clone()))
+        }
+    }
+}
+
+impl Merge for super::inner::MessageTemplate {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeError> {
+        if self.raw.is_empty() {
+            *self = other.clone();
+            Ok(())
+        } else if other.raw.is_empty() || self.raw == other.raw {
             Ok(())
         } else {
-            Err(MergeError::StringsDiffer(self.clone(), other.clone()))
+            conflicts.report(MergeError::StringsDiffer(
+                self.raw.clone(),
+                other.raw.clone(),
+            ))
         }
     }
 }
 
 impl Merge for super::inner::TargetLanguageType {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
-        self.name.merge(&other.name)
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeError> {
+        self.name.merge_with(&other.name, conflicts)
     }
 }
 
 impl Merge for super::inner::FullyQualifiedTargetLanguageType {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
-        self.name.merge(&other.name)?;
-        self.path.merge(&other.path)
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeError> {
+        self.name.merge_with(&other.name, conflicts)?;
+        self.path.merge_with(&other.path, conflicts)
     }
 }
 impl Merge for TypeDescription {
-    fn merge(&mut self, other: &TypeDescription) -> Result<(), MergeError> {
-        if self.name == other.name {
-            if self.meta.description.is_empty() && !other.meta.description.is_empty() {
-                self.meta.description = other.meta.description.clone();
-            } else if !self.meta.description.is_empty() && !other.meta.description.is_empty() {
-                return Err(MergeError::ConflictingTypeDescriptions(self.name.clone()));
-            }
-            merge_maps(&mut self.bindings, &other.bindings)
-        } else {
-            Err(MergeError::ConflictingTypeDescriptions(self.name.clone()))
+    fn merge_with(
+        &mut self,
+        other: &TypeDescription,
+        conflicts: &mut Conflicts,
+    ) -> Result<(), MergeError> {
+        if self.name != other.name {
+            return Err(MergeError::ConflictingTypeDescriptions(self.name.clone()));
         }
+        merge_attribute(
+            &format!("the type `{}`", self.name),
+            "descriptions",
+            &mut self.meta.description,
+            &other.meta.description,
+            conflicts,
+        )?;
+        merge_maps(&mut self.bindings, &other.bindings, conflicts)
     }
 }
 
 impl Merge for Model {
-    fn merge(&mut self, other: &Model) -> Result<(), MergeError> {
-        merge_maps(&mut self.types, &other.types)?;
-        merge_maps(&mut self.domains, &other.domains)
+    /// Models that keep the default code layout adopt the layout of the model
+    /// they are merged into.
+    fn merge_with(&mut self, other: &Model, conflicts: &mut Conflicts) -> Result<(), MergeError> {
+        if self.code_layout != other.code_layout
+            && other.code_layout != super::inner::CodeLayout::default()
+        {
+            return Err(MergeError::ConflictingCodeLayouts(
+                self.code_layout.to_string(),
+                other.code_layout.to_string(),
+            ));
+        }
+        for (rule, level) in &other.lints.0 {
+            match self.lints.0.get(rule) {
+                Some(existing) if existing != level => {
+                    conflicts.report(MergeError::ConflictingAttribute {
+                        entity: format!("the lint rule `{rule}`"),
+                        attribute: "levels".to_owned(),
+                        existing: existing.to_string(),
+                        new: level.to_string(),
+                    })?;
+                }
+                Some(_) => {}
+                None => {
+                    self.lints.0.insert(*rule, *level);
+                }
+            }
+        }
+        merge_maps(&mut self.types, &other.types, conflicts)?;
+        merge_maps(&mut self.domains, &other.domains, conflicts)
     }
 }
 
 impl Merge for DomainDescription {
-    fn merge(&mut self, other: &DomainDescription) -> Result<(), MergeError> {
+    fn merge_with(
+        &mut self,
+        other: &DomainDescription,
+        conflicts: &mut Conflicts,
+    ) -> Result<(), MergeError> {
         if self.meta.name != other.meta.name || self.meta.code != other.meta.code {
             return Err(MergeError::ConflictingDomainDefinitions(
                 self.meta.name.clone(),
             ));
         }
-        // merge_maps(&mut self.meta.bindings, &other.meta.bindings)?;
-        // if !other.meta.identifier.is_empty() {
-        //     self.meta.identifier = other.meta.identifier.clone();
-        // }
-        // if !other.meta.description.is_empty() {
-        //     self.meta.description = other.meta.description.clone();
-        // }
-        merge_maps(&mut self.components, &other.components)
+        let entity = format!("the domain `{}`", self.meta.name);
+        let meta = Rc::make_mut(&mut self.meta);
+        merge_bindings(
+            &entity,
+            &other.meta.name,
+            &mut meta.bindings,
+            &other.meta.bindings,
+            String::as_str,
+            conflicts,
+        )?;
+        let attributes = [
+            ("identifiers", &mut meta.identifier, &other.meta.identifier),
+            (
+                "descriptions",
+                &mut meta.description,
+                &other.meta.description,
+            ),
+        ];
+        for (attribute, main, other) in attributes {
+            merge_attribute(&entity, attribute, main, other, conflicts)?;
+        }
+        merge_maps(&mut self.components, &other.components, conflicts)?;
+
+        // Components and errors refer to the metadata of the domain they were
+        // defined in, which now lacks the merged attributes.
+        for component in self.components.values_mut() {
+            if !Rc::ptr_eq(&component.meta.domain, &self.meta) {
+                Rc::make_mut(&mut component.meta).domain = self.meta.clone();
+            }
+            component.refresh_metadata();
+        }
+        Ok(())
     }
 }
 
 impl Merge for ComponentDescription {
-    fn merge(&mut self, other: &ComponentDescription) -> Result<(), MergeError> {
+    fn merge_with(
+        &mut self,
+        other: &ComponentDescription,
+        conflicts: &mut Conflicts,
+    ) -> Result<(), MergeError> {
         if !self.mergeable_with(other) {
             return Err(MergeError::ConflictingComponentDefinitions(
                 self.meta.name.clone(),
             ));
         }
-        // merge_maps(&mut self.meta.bindings, &other.meta.bindings)?;
+        let entity = format!("the component `{}`", self.meta.name);
+        let meta = Rc::make_mut(&mut self.meta);
+        merge_bindings(
+            &entity,
+            &other.meta.name,
+            &mut meta.bindings,
+            &other.meta.bindings,
+            String::as_str,
+            conflicts,
+        )?;
+        let attributes = [
+            ("identifiers", &mut meta.identifier, &other.meta.identifier),
+            (
+                "descriptions",
+                &mut meta.description,
+                &other.meta.description,
+            ),
+        ];
+        for (attribute, main, other) in attributes {
+            merge_attribute(&entity, attribute, main, other, conflicts)?;
+        }
+        for range in &other.meta.reserved {
+            if !meta.reserved.contains(range) {
+                meta.reserved.push(*range);
+            }
+        }
+
         for error in &other.errors {
             if let Some(existing_error) = self.errors.iter_mut().find(|e| e.code == error.code) {
-                existing_error.merge(error)?;
+                existing_error.merge_with(error, conflicts)?;
             } else {
                 self.errors.push(error.clone());
             }
         }
+        self.refresh_metadata();
         Ok(())
     }
 }
 
+impl ComponentDescription {
+    /// Make the errors refer to the current metadata of the component.
+    fn refresh_metadata(&mut self) {
+        for error in &mut self.errors {
+            if !Rc::ptr_eq(&error.component, &self.meta) {
+                error.component = self.meta.clone();
+            }
+            if !Rc::ptr_eq(&error.domain, &self.meta.domain) {
+                error.domain = self.meta.domain.clone();
+            }
+        }
+    }
+}
+
 impl Merge for ErrorDescription {
-    fn merge(&mut self, other: &ErrorDescription) -> Result<(), MergeError> {
+    /// Errors are identified by their names and codes, and by the names and
+    /// codes of their domains and components.
+    fn merge_with(
+        &mut self,
+        other: &ErrorDescription,
+        conflicts: &mut Conflicts,
+    ) -> Result<(), MergeError> {
         if self.name != other.name
             || self.code != other.code
-            || self.domain != other.domain
-            || self.component != other.component
+            || (self.domain.name != other.domain.name || self.domain.code != other.domain.code)
+            || (self.component.name != other.component.name
+                || self.component.code != other.component.code)
             || self.fields != other.fields
         {
             return Err(MergeError::ConflictingErrorDescriptions(
@@ -128,28 +369,136 @@ impl Merge for ErrorDescription {
                 other.name.clone(),
             ));
         }
-        let _ = self.documentation.merge(&other.documentation);
-        let _ = self.message.merge(&other.message);
-        merge_maps(&mut self.bindings, &other.bindings)
+        let entity = format!("the error `{}`", self.name);
+        if self.message.raw.is_empty() {
+            self.message = other.message.clone();
+        } else if !other.message.raw.is_empty() && self.message.raw != other.message.raw {
+            conflicts.report(MergeError::ConflictingAttribute {
+                entity: entity.clone(),
+                attribute: "messages".into(),
+                existing: self.message.raw.clone(),
+                new: other.message.raw.clone(),
+            })?;
+        }
+        match (&mut self.documentation, &other.documentation) {
+            (Some(documentation), Some(other)) => {
+                merge_documentation(&entity, documentation, other, conflicts)?
+            }
+            (None, Some(other)) => self.documentation = Some(other.clone()),
+            (_, None) => {}
+        }
+        if self.lifecycle == Lifecycle::Active {
+            self.lifecycle = other.lifecycle.clone();
+        } else if other.lifecycle != Lifecycle::Active && self.lifecycle != other.lifecycle {
+            conflicts.report(MergeError::ConflictingAttribute {
+                entity: entity.clone(),
+                attribute: "lifecycles".into(),
+                existing: self.lifecycle.to_string(),
+                new: other.lifecycle.to_string(),
+            })?;
+        }
+        merge_bindings(
+            &entity,
+            &other.name,
+            &mut self.bindings,
+            &other.bindings,
+            |binding| binding.name.as_str(),
+            conflicts,
+        )
     }
 }
 
-impl Merge for ErrorDocumentation {
-    fn merge(&mut self, other: &ErrorDocumentation) -> Result<(), MergeError> {
-        let _ = self.description.merge(&other.description);
-        let _ = self.summary.merge(&other.summary);
-        //FIXME: merge likely causes properly too
-        self.likely_causes
-            .extend(other.likely_causes.iter().cloned());
-        Ok(())
+fn merge_documentation(
+    entity: &str,
+    main: &mut ErrorDocumentation,
+    other: &ErrorDocumentation,
+    conflicts: &mut Conflicts,
+) -> Result<(), MergeError> {
+    merge_attribute(
+        entity,
+        "descriptions",
+        &mut main.description,
+        &other.description,
+        conflicts,
+    )?;
+    if let Some(other_summary) = other.summary.as_ref().filter(|s| !s.is_empty()) {
+        let summary = main.summary.get_or_insert_with(String::new);
+        merge_attribute(entity, "summaries", summary, other_summary, conflicts)?;
+    }
+    merge_likely_causes(
+        entity,
+        &mut main.likely_causes,
+        &other.likely_causes,
+        conflicts,
+    )
+}
+
+/// Add the likely causes `other` of `entity` to `main`. Causes with the same
+/// text as an existing one are merged into it.
+pub fn merge_likely_causes(
+    entity: &str,
+    main: &mut Vec<LikelyCause>,
+    other: &[LikelyCause],
+    conflicts: &mut Conflicts,
+) -> Result<(), MergeError> {
+    for cause in other {
+        match main
+            .iter_mut()
+            .find(|existing| existing.cause == cause.cause)
+        {
+            Some(existing) => merge_likely_cause(entity, existing, cause, conflicts)?,
+            None => main.push(cause.clone()),
+        }
     }
+    Ok(())
+}
+
+/// Likely causes are identified by the text of the cause; their fixes and
+/// references are united.
+fn merge_likely_cause(
+    entity: &str,
+    main: &mut LikelyCause,
+    other: &LikelyCause,
+    conflicts: &mut Conflicts,
+) -> Result<(), MergeError> {
+    for fix in &other.fixes {
+        if !main.fixes.contains(fix) {
+            main.fixes.push(fix.clone());
+        }
+    }
+    for reference in &other.references {
+        if !main.references.contains(reference) {
+            main.references.push(reference.clone());
+        }
+    }
+    let entity = format!("the likely cause `{}` of {entity}", main.cause);
+    merge_attribute(
+        &entity,
+        "reports",
+        &mut main.report,
+        &other.report,
+        conflicts,
+    )?;
+    match (&main.owner, &other.owner) {
+        (None, Some(owner)) => main.owner = Some(owner.clone()),
+        (Some(existing), Some(owner)) if existing != owner => {
+            conflicts.report(MergeError::ConflictingAttribute {
+                entity,
+                attribute: "owners".into(),
+                existing: format!("{}@{}", existing.name, existing.version),
+                new: format!("{}@{}", owner.name, owner.version),
+            })?
+        }
+        _ => {}
+    }
+    Ok(())
 }
 
 impl<T> Merge for Option<T>
 where
     T: Merge + Clone,
 {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeError> {
         match (self.as_mut(), other) {
             (None, None) => Ok(()),
             (Some(_), None) => Ok(()),
@@ -157,7 +506,95 @@ where
                 *self = other.clone();
                 Ok(())
             }
-            (Some(x), Some(y)) => x.merge(y),
+            (Some(x), Some(y)) => x.merge_with(y, conflicts),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::inner::{ErrorDocumentation, LikelyCause, Model};
+    use crate::testing::model;
+
+    use super::{Merge as _, MergeMode};
+
+    fn documented(description: &str, causes: &[(&str, &str)]) -> Model {
+        let mut model = model(&[("A", 1, &[])]);
+        let component = model.domains.get_mut("core").unwrap();
+        let component = component.components.get_mut("sequencer").unwrap();
+        component.errors[0].documentation = Some(ErrorDocumentation {
+            description: description.into(),
+            summary: None,
+            likely_causes: causes
+                .iter()
+                .map(|(cause, fix)| LikelyCause {
+                    cause: cause.to_string(),
+                    fixes: vec![fix.to_string()],
+                    report: "".into(),
+                    owner: None,
+                    references: vec![],
+                })
+                .collect(),
+        });
+        model
+    }
+
+    fn documentation(model: &Model) -> &ErrorDocumentation {
+        model
+            .errors()
+            .next()
+            .unwrap()
+            .documentation
+            .as_ref()
+            .unwrap()
+    }
+
+    #[test]
+    fn conflicts_depend_on_the_mode() {
+        let main = documented("First", &[("Overflow", "Retry")]);
+        let other = documented("Second", &[("Overflow", "Wait"), ("Timeout", "Retry")]);
+
+        let error = main.clone().merge(&other).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Conflicting descriptions of the error `A`: `First` and `Second`"
+        );
+
+        let mut merged = main.clone();
+        let conflicts = merged.merge_in_mode(&other, MergeMode::Lenient).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        let documentation = documentation(&merged);
+        assert_eq!(documentation.description, "First");
+        let causes: Vec<_> = documentation
+            .likely_causes
+            .iter()
+            .map(|c| (c.cause.as_str(), c.fixes.join(", ")))
+            .collect();
+        assert_eq!(
+            causes,
+            [
+                ("Overflow", "Retry, Wait".into()),
+                ("Timeout", "Retry".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn metadata_is_merged_and_shared() {
+        let mut main = model(&[("A", 1, &[])]);
+        let mut other = model(&[("B", 2, &[])]);
+        let domain = other.domains.get_mut("core").unwrap();
+        Rc::make_mut(&mut domain.meta).description = "Core".into();
+        Rc::make_mut(&mut domain.meta)
+            .bindings
+            .insert("rust".into(), "CoreDomain".into());
+
+        main.merge(&other).unwrap();
+        let domain = &main.domains["core"];
+        assert_eq!(domain.meta.description, "Core");
+        assert_eq!(domain.meta.bindings["rust"], "CoreDomain");
+        assert!(main.errors().all(|e| Rc::ptr_eq(&e.domain, &domain.meta)));
+    }
+}
@@ -0,0 +1,107 @@
+//!
+//! Small models for the tests of the validator and of other model passes.
+//!
+
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::inner::{
+    ComponentDescription, ComponentMetadata, DomainDescription, DomainMetadata, ErrorDescription,
+    FieldDescription, FullyQualifiedTargetLanguageType, Lifecycle, Model, TargetLanguageType,
+    TypeDescription, TypeMetadata,
+};
+
+/// Error name, code and fields with their types.
+pub type ErrorSketch<'a> = (&'a str, u32, &'a [(&'a str, &'a str)]);
+
+/// Model with a single domain `core` holding a single component `sequencer`
+/// with the given errors. The type `uint` is bound to `u32` in Rust.
+pub fn model(errors: &[ErrorSketch]) -> Model {
+    let domain = Rc::new(DomainMetadata {
+        name: "core".into(),
+        code: 1,
+        bindings: BTreeMap::new(),
+        identifier: "core".into(),
+        description: "".into(),
+        origin: None,
+    });
+    let component = Rc::new(ComponentMetadata {
+        name: "sequencer".into(),
+        code: 1,
+        domain: domain.clone(),
+        bindings: BTreeMap::new(),
+        identifier: "seq".into(),
+        description: "".into(),
+        reserved: Vec::new(),
+        origin: None,
+    });
+    let errors = errors
+        .iter()
+        .map(|(name, code, fields)| ErrorDescription {
+            domain: domain.clone(),
+            component: component.clone(),
+            name: name.to_string(),
+            code: *code,
+            message: Default::default(),
+            fields: fields
+                .iter()
+                .map(|(name, typ)| FieldDescription {
+                    name: name.to_string(),
+                    r#type: typ.to_string(),
+                    description: String::new(),
+                    optional: false,
+                    default: None,
+                })
+                .collect(),
+            documentation: None,
+            bindings: BTreeMap::from([(
+                "rust".into(),
+                TargetLanguageType {
+                    name: name.to_string(),
+                },
+            )]),
+            lifecycle: Lifecycle::Active,
+            origin: None,
+        })
+        .collect();
+    let types = BTreeMap::from([(
+        "uint".to_string(),
+        TypeDescription {
+            name: "uint".into(),
+            meta: TypeMetadata {
+                description: "".into(),
+            },
+            bindings: BTreeMap::from([(
+                "rust".into(),
+                FullyQualifiedTargetLanguageType::from("u32"),
+            )]),
+            origin: None,
+        },
+    )]);
+    let components = BTreeMap::from([(
+        "sequencer".to_string(),
+        ComponentDescription {
+            meta: component,
+            errors,
+        },
+    )]);
+    Model::new(
+        types,
+        BTreeMap::from([(
+            "core".to_string(),
+            DomainDescription {
+                meta: domain,
+                components,
+            },
+        )]),
+    )
+}
+
+/// All errors of the model, to adjust the attributes [`model`] does not set.
+pub fn errors_mut(model: &mut Model) -> impl Iterator<Item = &mut ErrorDescription> {
+    model
+        .domains
+        .values_mut()
+        .flat_map(|domain| domain.components.values_mut())
+        .flat_map(|component| component.errors.iter_mut())
+}
@@ -13,6 +13,12 @@ pub struct TargetLanguageType {
     pub path: String,
 }
 
+#[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Origin {
+    pub source: String,
+    pub pointer: String,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TypeMetadata {
     pub description: String,
@@ -23,6 +29,8 @@ pub struct TypeDescription {
     pub name: TypeName,
     pub meta: TypeMetadata,
     pub bindings: BTreeMap<LanguageName, TargetLanguageType>,
+    #[serde(default)]
+    pub origin: Option<Origin>,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
@@ -33,6 +41,8 @@ pub struct DomainMetadata {
     pub bindings: BTreeMap<LanguageName, String>,
     pub identifier: String,
     pub description: String,
+    #[serde(default)]
+    pub origin: Option<Origin>,
 }
 
 #[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
@@ -51,6 +61,28 @@ pub struct ComponentMetadata {
     pub bindings: BTreeMap<LanguageName, String>,
     pub identifier: String,
     pub description: String,
+    #[serde(default)]
+    pub reserved: Vec<CodeRange>,
+    #[serde(default)]
+    pub origin: Option<Origin>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CodeRange {
+    pub start: ErrorCode,
+    pub end: ErrorCode,
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum Lifecycle {
+    #[default]
+    Active,
+    Deprecated {
+        message: String,
+        replacement: Option<String>,
+    },
+    Retired,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
@@ -64,12 +96,22 @@ pub struct ErrorDescription {
     pub fields: Vec<FieldDescription>,
     pub documentation: Option<ErrorDocumentation>,
     pub bindings: BTreeMap<LanguageName, TargetLanguageType>,
+    #[serde(default)]
+    pub lifecycle: Lifecycle,
+    #[serde(default)]
+    pub origin: Option<Origin>,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FieldDescription {
     pub name: FieldName,
     pub r#type: TypeName,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Default, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
@@ -94,6 +136,25 @@ pub struct VersionedOwner {
     pub version: Semver,
 }
 
+fn translate_origin(origin: crate::inner::Origin) -> Origin {
+    let crate::inner::Origin { source, pointer } = origin;
+    Origin { source, pointer }
+}
+
+fn translate_lifecycle(lifecycle: crate::inner::Lifecycle) -> Lifecycle {
+    match lifecycle {
+        crate::inner::Lifecycle::Active => Lifecycle::Active,
+        crate::inner::Lifecycle::Deprecated {
+            message,
+            replacement,
+        } => Lifecycle::Deprecated {
+            message,
+            replacement,
+        },
+        crate::inner::Lifecycle::Retired => Lifecycle::Retired,
+    }
+}
+
 fn translate_domain_metadata(
     meta: &crate::inner::DomainMetadata,
     components: Vec<ComponentName>,
@@ -104,6 +165,7 @@ fn translate_domain_metadata(
         bindings,
         identifier,
         description,
+        origin,
     } = meta.clone();
     DomainMetadata {
         name,
@@ -112,6 +174,7 @@ fn translate_domain_metadata(
         identifier,
         description,
         components,
+        origin: origin.map(translate_origin),
     }
 }
 
@@ -123,6 +186,8 @@ fn translate_component_metadata(meta: &crate::inner::ComponentMetadata) -> Compo
         identifier,
         description,
         domain,
+        reserved,
+        origin,
     } = meta.clone();
     ComponentMetadata {
         name,
@@ -131,11 +196,28 @@ fn translate_component_metadata(meta: &crate::inner::ComponentMetadata) -> Compo
         identifier,
         description,
         domain_name: domain.name.to_string(),
+        reserved: reserved
+            .into_iter()
+            .map(|crate::inner::CodeRange { start, end }| CodeRange { start, end })
+            .collect(),
+        origin: origin.map(translate_origin),
     }
 }
 fn translate_field(field: &crate::inner::FieldDescription) -> FieldDescription {
-    let crate::inner::FieldDescription { name, r#type } = field.clone();
-    FieldDescription { name, r#type }
+    let crate::inner::FieldDescription {
+        name,
+        r#type,
+        description,
+        optional,
+        default,
+    } = field.clone();
+    FieldDescription {
+        name,
+        r#type,
+        description,
+        optional,
+        default,
+    }
 }
 fn translate_error(meta: &crate::inner::ErrorDescription) -> ErrorDescription {
     let crate::inner::ErrorDescription {
@@ -147,6 +229,8 @@ fn translate_error(meta: &crate::inner::ErrorDescription) -> ErrorDescription {
         fields,
         documentation,
         bindings,
+        lifecycle,
+        origin,
     } = meta;
     let new_bindings: BTreeMap<_, _> = bindings
         .iter()
@@ -172,10 +256,12 @@ fn translate_error(meta: &crate::inner::ErrorDescription) -> ErrorDescription {
         name: name.clone(),
         code: *code,
         identifier,
-        message: message.clone(),
+        message: message.to_string(),
         fields: fields.iter().map(translate_field).collect(),
         documentation: documentation.clone().map(|d| translate_documentation(&d)),
         bindings: new_bindings,
+        lifecycle: translate_lifecycle(lifecycle.clone()),
+        origin: origin.clone().map(translate_origin),
     }
 }
 
@@ -222,6 +308,7 @@ fn translate_type(typ: &crate::inner::TypeDescription) -> TypeDescription {
         name,
         meta: crate::inner::TypeMetadata { description },
         bindings,
+        origin,
     } = typ.clone();
 
     let new_bindings: BTreeMap<_, _> = bindings
@@ -240,10 +327,11 @@ fn translate_type(typ: &crate::inner::TypeDescription) -> TypeDescription {
         name,
         meta: TypeMetadata { description },
         bindings: new_bindings,
+        origin: origin.map(translate_origin),
     }
 }
 pub fn flatten(model: &Model) -> UnpackedModel {
-    let Model { types, domains } = model;
+    let Model { types, domains, .. } = model;
     let mut result = UnpackedModel::default();
     for (name, typ) in types {
         result.types.insert(name.clone(), translate_type(typ));
@@ -1,6 +1,597 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
 use super::error::ModelValidationError;
+use super::error::ModelValidationErrors;
+use super::inner::ComponentDescription;
+use super::inner::ErrorDescription;
+use super::inner::Lifecycle;
 use super::inner::Model;
+use super::inner::TypeExpression;
+
+/// Group `items` by `key` and return the groups with more than one element.
+fn duplicates<'a, T, K>(
+    items: impl IntoIterator<Item = &'a T>,
+    key: impl Fn(&T) -> K,
+    name: impl Fn(&T) -> String,
+) -> Vec<(K, Vec<String>)>
+where
+    T: 'a,
+    K: Ord,
+{
+    let mut groups: BTreeMap<K, Vec<String>> = BTreeMap::new();
+    for item in items {
+        groups.entry(key(item)).or_default().push(name(item));
+    }
+    groups
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .collect()
+}
+
+/// Keywords of Rust and reserved words of TypeScript, which the backends can
+/// not use as names of fields or types.
+const KEYWORDS: [&str; 2] = [
+    // Rust, strict and reserved.
+    "Self abstract as async await become box break const continue crate do dyn else enum \
+     extern false final fn for if impl in let loop macro match mod move mut override priv pub \
+     ref return self static struct super trait true try type typeof unsafe unsized use \
+     virtual where while yield",
+    // TypeScript, not listed above.
+    "case catch class debugger default delete export extends finally function implements \
+     import instanceof interface new null package private protected public switch this throw \
+     var void with",
+];
+
+pub fn is_valid_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+        && !KEYWORDS
+            .iter()
+            .flat_map(|keywords| keywords.split_whitespace())
+            .any(|keyword| keyword == name)
+}
+
+fn check_domains(model: &Model, result: &mut Vec<ModelValidationError>) {
+    let domains: Vec<_> = model.domains.values().map(|d| d.meta.as_ref()).collect();
+
+    for (code, domains) in duplicates(domains.iter().copied(), |d| d.code, |d| d.name.clone()) {
+        result.push(ModelValidationError::DuplicateDomainCode { code, domains });
+    }
+    for (identifier, domains) in duplicates(
+        domains.iter().copied(),
+        |d| d.identifier.clone(),
+        |d| d.name.clone(),
+    ) {
+        result.push(ModelValidationError::DuplicateDomainIdentifier {
+            identifier,
+            domains,
+        });
+    }
+
+    for domain in model.domains.values() {
+        let components: Vec<_> = domain
+            .components
+            .values()
+            .map(|c| c.meta.as_ref())
+            .collect();
+        for (code, components) in
+            duplicates(components.iter().copied(), |c| c.code, |c| c.name.clone())
+        {
+            result.push(ModelValidationError::DuplicateComponentCode {
+                domain: domain.meta.name.clone(),
+                code,
+                components,
+            });
+        }
+        for (identifier, components) in duplicates(
+            components.iter().copied(),
+            |c| c.identifier.clone(),
+            |c| c.name.clone(),
+        ) {
+            result.push(ModelValidationError::DuplicateComponentIdentifier {
+                domain: domain.meta.name.clone(),
+                identifier,
+                components,
+            });
+        }
+    }
+}
+
+fn check_code_layout(model: &Model, result: &mut Vec<ModelValidationError>) {
+    let layout = model.code_layout;
+    if !layout.is_valid() {
+        result.push(ModelValidationError::InvalidCodeLayout(layout));
+        return;
+    }
+
+    for domain in model.domains.values() {
+        if u64::from(domain.meta.code) >= layout.domain_capacity() {
+            result.push(ModelValidationError::DomainCodeOutOfRange {
+                domain: domain.meta.name.clone(),
+                code: domain.meta.code,
+                layout,
+            });
+        }
+    }
+    for component in model.components() {
+        if u64::from(component.meta.code) >= layout.component_capacity() {
+            result.push(ModelValidationError::ComponentCodeOutOfRange {
+                component: component.meta.name.clone(),
+                code: component.meta.code,
+                layout,
+            });
+        }
+    }
+    for error in model.errors() {
+        if u64::from(error.code) >= layout.error_capacity() {
+            result.push(ModelValidationError::ErrorCodeOutOfRange {
+                error: error.name.clone(),
+                identifier: Box::new(error.get_identifier()),
+                code: error.code,
+                layout,
+            });
+        }
+    }
+}
+
+fn check_component(component: &ComponentDescription, result: &mut Vec<ModelValidationError>) {
+    let component_name = &component.meta.name;
+
+    for (code, errors) in duplicates(&component.errors, |e| e.code, |e| e.name.clone()) {
+        let retired = component
+            .errors
+            .iter()
+            .find(|e| e.code == code && e.is_retired());
+        result.push(match retired {
+            Some(retired) => ModelValidationError::RetiredErrorCode {
+                component: component_name.clone(),
+                code,
+                retired: retired.name.clone(),
+                errors: errors
+                    .into_iter()
+                    .filter(|name| *name != retired.name)
+                    .collect(),
+            },
+            None => ModelValidationError::DuplicateErrorCode {
+                component: component_name.clone(),
+                code,
+                errors,
+            },
+        });
+    }
+
+    for range in &component.meta.reserved {
+        if range.start > range.end {
+            result.push(ModelValidationError::InvalidReservedRange {
+                component: component_name.clone(),
+                range: *range,
+            });
+        }
+    }
+    // Retired errors may keep their codes in a reserved range.
+    for error in component.emitted_errors() {
+        if let Some(range) = component
+            .meta
+            .reserved
+            .iter()
+            .find(|range| range.contains(error.code))
+        {
+            result.push(ModelValidationError::ReservedErrorCode {
+                error: error.name.clone(),
+                identifier: Box::new(error.get_identifier()),
+                code: error.code,
+                range: *range,
+            });
+        }
+    }
+
+    for (error, _) in duplicates(&component.errors, |e| e.name.clone(), |e| e.name.clone()) {
+        result.push(ModelValidationError::DuplicateErrorName {
+            component: component_name.clone(),
+            error,
+        });
+    }
+
+    let languages: BTreeSet<_> = component
+        .errors
+        .iter()
+        .flat_map(|e| e.bindings.keys())
+        .collect();
+    for language in languages {
+        let bound_errors = component
+            .errors
+            .iter()
+            .filter_map(|e| e.bindings.get(language).map(|binding| (e, binding)));
+        let bound_errors: Vec<_> = bound_errors.collect();
+        for (name, errors) in duplicates(
+            &bound_errors,
+            |(_, binding)| binding.name.clone(),
+            |(e, _)| e.name.clone(),
+        ) {
+            // Errors sharing the same name are already reported.
+            if errors.iter().collect::<BTreeSet<_>>().len() > 1 {
+                result.push(ModelValidationError::DuplicateVariantName {
+                    component: component_name.clone(),
+                    language: language.clone(),
+                    name,
+                    errors,
+                });
+            }
+        }
+    }
+}
+
+/// Whether `value` is the JSON representation of a value of `typ`. Declared
+/// types are recognized by their Rust bindings; values of the types bound to
+/// other Rust types can not be checked, and are not admitted.
+fn admits(model: &Model, typ: &TypeExpression, value: &serde_json::Value) -> bool {
+    fn is_hex(value: &serde_json::Value, length: Option<usize>) -> bool {
+        let Some(text) = value.as_str() else {
+            return false;
+        };
+        let digits = text.strip_prefix("0x").unwrap_or(text);
+        digits.len() % 2 == 0
+            && digits.chars().all(|c| c.is_ascii_hexdigit())
+            && length.is_none_or(|length| digits.len() == 2 * length)
+    }
+
+    match typ {
+        TypeExpression::Named(name) => {
+            let binding = model
+                .types
+                .get(name)
+                .and_then(|t| t.bindings.get("rust"))
+                .map(|binding| binding.name.as_str());
+            match binding {
+                Some("u8") => value.as_u64().is_some_and(|v| u8::try_from(v).is_ok()),
+                Some("u16") => value.as_u64().is_some_and(|v| u16::try_from(v).is_ok()),
+                Some("u32") => value.as_u64().is_some_and(|v| u32::try_from(v).is_ok()),
+                Some("u64" | "u128" | "usize") => value.is_u64(),
+                Some("i8") => value.as_i64().is_some_and(|v| i8::try_from(v).is_ok()),
+                Some("i16") => value.as_i64().is_some_and(|v| i16::try_from(v).is_ok()),
+                Some("i32") => value.as_i64().is_some_and(|v| i32::try_from(v).is_ok()),
+                Some("i64" | "i128" | "isize") => value.is_i64(),
+                Some("f32" | "f64") => value.is_number(),
+                Some("bool") => value.is_boolean(),
+                Some("String") => value.is_string(),
+                _ => false,
+            }
+        }
+        TypeExpression::List(item) => value
+            .as_array()
+            .is_some_and(|items| items.iter().all(|v| admits(model, item, v))),
+        TypeExpression::Optional(inner) => value.is_null() || admits(model, inner, value),
+        // Keys are strings in JSON; numeric keys are written as strings of
+        // digits.
+        TypeExpression::Map(key, item) => value.as_object().is_some_and(|entries| {
+            entries.iter().all(|(k, v)| {
+                let number = k.parse().ok().filter(serde_json::Value::is_number);
+                (admits(model, key, &k.as_str().into())
+                    || number.is_some_and(|number| admits(model, key, &number)))
+                    && admits(model, item, v)
+            })
+        }),
+        TypeExpression::ByteArray(length) => is_hex(value, Some(*length)),
+        TypeExpression::Bytes => is_hex(value, None),
+    }
+}
+
+fn check_fields(
+    model: &Model,
+    error: &ErrorDescription,
+    languages: &[&str],
+    result: &mut Vec<ModelValidationError>,
+) {
+    let identifier = Box::new(error.get_identifier());
+
+    for (field, _) in duplicates(&error.fields, |f| f.name.clone(), |f| f.name.clone()) {
+        result.push(ModelValidationError::DuplicateFieldName {
+            error: error.name.clone(),
+            identifier: identifier.clone(),
+            field,
+        });
+    }
+
+    for field in &error.fields {
+        if !is_valid_identifier(&field.name) {
+            result.push(ModelValidationError::InvalidFieldName {
+                error: error.name.clone(),
+                identifier: identifier.clone(),
+                field: field.name.clone(),
+            });
+        }
+
+        let expression = match model.resolve_type(&field.r#type) {
+            Ok(expression) => expression,
+            Err(ModelValidationError::InvalidType { inner, .. }) => {
+                result.push(ModelValidationError::InvalidFieldType {
+                    error: error.name.clone(),
+                    identifier: identifier.clone(),
+                    field: field.name.clone(),
+                    r#type: field.r#type.clone(),
+                    inner: Box::new(inner),
+                });
+                continue;
+            }
+            Err(other) => {
+                result.push(other);
+                continue;
+            }
+        };
+        if let Some(default) = &field.default {
+            let admitted =
+                admits(model, &expression, default) || (field.optional && default.is_null());
+            if !admitted {
+                result.push(ModelValidationError::InvalidDefault {
+                    error: error.name.clone(),
+                    identifier: identifier.clone(),
+                    field: field.name.clone(),
+                    r#type: field.r#type.clone(),
+                    default: default.to_string(),
+                });
+            }
+        }
+        for name in expression.named_types() {
+            match model.types.get(name) {
+                None => result.push(ModelValidationError::UnknownFieldType {
+                    error: error.name.clone(),
+                    identifier: identifier.clone(),
+                    field: field.name.clone(),
+                    r#type: name.clone(),
+                }),
+                Some(type_description) => {
+                    for language in languages {
+                        if !type_description.bindings.contains_key(*language) {
+                            result.push(ModelValidationError::MissingTypeBinding {
+                                r#type: name.clone(),
+                                language: language.to_string(),
+                                error: error.name.clone(),
+                                identifier: identifier.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_lifecycle(
+    model: &Model,
+    error: &ErrorDescription,
+    result: &mut Vec<ModelValidationError>,
+) {
+    if let Lifecycle::Deprecated {
+        replacement: Some(replacement),
+        ..
+    } = &error.lifecycle
+    {
+        let is_emitted = |e: &&ErrorDescription| !e.is_retired();
+        if !model
+            .errors()
+            .filter(is_emitted)
+            .any(|e| e.get_identifier().to_string() == *replacement)
+        {
+            result.push(ModelValidationError::UnknownReplacement {
+                error: error.name.clone(),
+                identifier: Box::new(error.get_identifier()),
+                replacement: replacement.clone(),
+            });
+        }
+    }
+}
+
+/// Check the model for problems that would make the generated code invalid.
+/// Types used by error fields should have bindings for every language in
+/// `languages`. All problems are collected before returning.
+pub fn validate(model: &Model, languages: &[&str]) -> Result<(), ModelValidationErrors> {
+    let mut result = Vec::new();
+
+    check_domains(model, &mut result);
+    check_code_layout(model, &mut result);
+    for component in model.components() {
+        check_component(component, &mut result);
+    }
+    for error in model.errors() {
+        check_fields(model, error, languages, &mut result);
+        check_lifecycle(model, error, &mut result);
+    }
+
+    if result.is_empty() {
+        Ok(())
+    } else {
+        Err(ModelValidationErrors(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ModelValidationError;
+    use crate::inner::{CodeLayout, CodeLayoutUnit, CodeRange, Lifecycle};
+    use crate::testing::errors_mut;
+    use crate::testing::model;
+
+    use super::validate;
+
+    #[test]
+    fn valid_model() {
+        let model = model(&[("A", 1, &[("index", "uint")]), ("B", 2, &[])]);
+        assert!(validate(&model, &["rust"]).is_ok());
+    }
+
+    #[test]
+    fn keywords_are_not_field_names() {
+        for keyword in ["type", "match", "class", "delete"] {
+            let model = model(&[("A", 1, &[(keyword, "uint")])]);
+            assert!(matches!(
+                validate(&model, &["rust"]).unwrap_err().0.as_slice(),
+                [ModelValidationError::InvalidFieldName { field, .. }] if field == keyword
+            ));
+        }
+        let model = model(&[("A", 1, &[("types", "uint"), ("r#type", "uint")])]);
+        assert!(matches!(
+            validate(&model, &["rust"]).unwrap_err().0.as_slice(),
+            [ModelValidationError::InvalidFieldName { field, .. }] if field == "r#type"
+        ));
+    }
+
+    #[test]
+    fn collects_all_problems() {
+        let model = model(&[
+            ("A", 1, &[("index", "uint"), ("path", "string")]),
+            ("B", 1, &[("1st", "uint")]),
+        ]);
+        let errors = validate(&model, &["rust", "typescript"]).unwrap_err().0;
+        assert!(matches!(
+            errors.as_slice(),
+            [
+                ModelValidationError::DuplicateErrorCode { code: 1, .. },
+                ModelValidationError::MissingTypeBinding { language, .. },
+                ModelValidationError::UnknownFieldType { field, .. },
+                ModelValidationError::InvalidFieldName { .. },
+                ModelValidationError::MissingTypeBinding { .. },
+            ] if language == "typescript" && field == "path"
+        ));
+    }
+
+    #[test]
+    fn checks_composite_types() {
+        let model = model(&[(
+            "A",
+            1,
+            &[
+                ("counts", "map<bytes<4>, list<uint>>"),
+                ("paths", "optional<list<string>>"),
+                ("set", "set<uint>"),
+            ],
+        )]);
+        let errors = validate(&model, &["rust"]).unwrap_err().0;
+        assert!(
+            matches!(
+                errors.as_slice(),
+                [
+                    ModelValidationError::UnknownFieldType { r#type, .. },
+                    ModelValidationError::InvalidFieldType { field, .. },
+                ] if r#type == "string" && field == "set"
+            ),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn defaults_match_field_types() {
+        let mut model = model(&[(
+            "A",
+            1,
+            &[
+                ("retries", "uint"),
+                ("limits", "list<uint>"),
+                ("hash", "bytes<2>"),
+                ("owner", "optional<uint>"),
+                ("counts", "map<uint, uint>"),
+                ("sizes", "map<uint, uint>"),
+                ("payload", "json"),
+            ],
+        )]);
+        let mut json = model.types["uint"].clone();
+        json.name = "json".into();
+        json.bindings
+            .insert("rust".into(), "serde_json::Value".into());
+        model.types.insert("json".into(), json);
+        let defaults = [
+            serde_json::json!("three"),
+            serde_json::json!([1, -2]),
+            serde_json::json!("0x0102"),
+            serde_json::json!(null),
+            serde_json::json!({ "1": 2, "x": 3 }),
+            serde_json::json!({ "1": 2 }),
+            serde_json::json!({}),
+        ];
+        let error = errors_mut(&mut model).next().unwrap();
+        for (field, default) in error.fields.iter_mut().zip(defaults) {
+            field.default = Some(default);
+        }
+
+        let errors = validate(&model, &["rust"]).unwrap_err().0;
+        assert!(
+            matches!(
+                errors.as_slice(),
+                [
+                    ModelValidationError::InvalidDefault { field: retries, .. },
+                    ModelValidationError::InvalidDefault { field: limits, .. },
+                    ModelValidationError::InvalidDefault { field: counts, .. },
+                    ModelValidationError::InvalidDefault { field: payload, .. },
+                ] if retries == "retries" && limits == "limits" && counts == "counts" && payload == "payload"
+            ),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn retired_and_reserved_codes_are_not_reused() {
+        let mut model = model(&[("A", 1, &[]), ("B", 1, &[]), ("C", 5, &[]), ("D", 6, &[])]);
+        for error in errors_mut(&mut model) {
+            match error.name.as_str() {
+                "A" => error.lifecycle = Lifecycle::Retired,
+                "D" => {
+                    error.lifecycle = Lifecycle::Deprecated {
+                        message: String::new(),
+                        replacement: Some("[core-seq-7]".into()),
+                    }
+                }
+                _ => {}
+            }
+        }
+        let component = model
+            .domains
+            .get_mut("core")
+            .unwrap()
+            .components
+            .get_mut("sequencer")
+            .unwrap();
+        std::rc::Rc::make_mut(&mut component.meta).reserved = vec![CodeRange { start: 4, end: 5 }];
+
+        let errors = validate(&model, &["rust"]).unwrap_err().0;
+        assert!(
+            matches!(
+                errors.as_slice(),
+                [
+                    ModelValidationError::RetiredErrorCode { retired, errors: reusing, .. },
+                    ModelValidationError::ReservedErrorCode { error, .. },
+                    ModelValidationError::UnknownReplacement { replacement, .. },
+                ] if retired == "A" && reusing == &["B"] && error == "C" && replacement == "[core-seq-7]"
+            ),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn codes_fit_into_layout() {
+        let mut model = model(&[("A", 999, &[]), ("B", 1000, &[])]);
+        let errors = validate(&model, &["rust"]).unwrap_err().0;
+        assert!(matches!(
+            errors.as_slice(),
+            [ModelValidationError::ErrorCodeOutOfRange { code: 1000, .. }]
+        ));
+
+        model.code_layout = CodeLayout {
+            unit: CodeLayoutUnit::Bits,
+            domain: 4,
+            component: 4,
+            error: 16,
+        };
+        assert!(validate(&model, &["rust"]).is_ok());
 
-pub fn validate(_model: &Model) -> Result<(), ModelValidationError> {
-    Ok(())
+        model.code_layout.domain = 20;
+        let errors = validate(&model, &["rust"]).unwrap_err().0;
+        assert!(matches!(
+            errors.as_slice(),
+            [ModelValidationError::InvalidCodeLayout(_)]
+        ));
+    }
 }